@@ -9,6 +9,7 @@ use ruma_common::{
     metadata,
 };
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 const METADATA: Metadata = metadata! {
     method: GET,
@@ -95,6 +96,14 @@ impl HomeserverInfo {
     pub fn new(base_url: String) -> Self {
         Self { base_url }
     }
+
+    /// Parse the `base_url` of the homeserver.
+    ///
+    /// Returns `None` if it is not a valid `http` or `https` URL with a host. According to the
+    /// spec, clients should then stop the discovery process.
+    pub fn validated_base_url(&self) -> Option<Url> {
+        validate_base_url(&self.base_url)
+    }
 }
 
 /// Information about a discovered identity server.
@@ -110,6 +119,20 @@ impl IdentityServerInfo {
     pub fn new(base_url: String) -> Self {
         Self { base_url }
     }
+
+    /// Parse the `base_url` of the identity server.
+    ///
+    /// Returns `None` if it is not a valid `http` or `https` URL with a host. According to the
+    /// spec, clients should then stop the discovery process.
+    pub fn validated_base_url(&self) -> Option<Url> {
+        validate_base_url(&self.base_url)
+    }
+}
+
+/// Parse the given base URL and check that it is an `http` or `https` URL with a host.
+fn validate_base_url(base_url: &str) -> Option<Url> {
+    let url = Url::parse(base_url).ok()?;
+    (matches!(url.scheme(), "http" | "https") && url.has_host()).then_some(url)
 }
 
 /// Information about a discovered map tile server.
@@ -169,3 +192,30 @@ impl SlidingSyncProxyInfo {
         Self { url }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::{HomeserverInfo, IdentityServerInfo};
+
+    #[test]
+    fn validated_base_url() {
+        let info = HomeserverInfo::new("https://matrix.example.org/".to_owned());
+        assert_eq!(info.validated_base_url().unwrap().as_str(), "https://matrix.example.org/");
+
+        let info = HomeserverInfo::new("http://localhost:8008/matrix".to_owned());
+        assert_eq!(info.validated_base_url().unwrap().as_str(), "http://localhost:8008/matrix");
+
+        assert_eq!(HomeserverInfo::new("matrix.example.org".to_owned()).validated_base_url(), None);
+        assert_eq!(HomeserverInfo::new("ftp://example.org".to_owned()).validated_base_url(), None);
+        assert_eq!(HomeserverInfo::new("https://".to_owned()).validated_base_url(), None);
+
+        let info: IdentityServerInfo =
+            from_json_value(json!({ "base_url": "https://identity.example.org" })).unwrap();
+        assert_eq!(info.validated_base_url().unwrap().as_str(), "https://identity.example.org/");
+
+        let info: IdentityServerInfo = from_json_value(json!({ "base_url": "" })).unwrap();
+        assert_eq!(info.validated_base_url(), None);
+    }
+}
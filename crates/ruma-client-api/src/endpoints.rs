@@ -0,0 +1,405 @@
+//! Lookup of the endpoints of this crate by path.
+
+use http::Method;
+use ruma_common::api::{IncomingRequest, Metadata};
+
+/// Generate the list of the metadata of the given endpoint modules.
+///
+/// The modules are grouped by their top-level module, and each one must contain a `Request` type.
+/// `cfg` attributes can be set on a group or on a single module.
+macro_rules! endpoints {
+    ($(
+        $( #[$group_attr:meta] )*
+        $group:ident::{
+            $(
+                $( #[$attr:meta] )*
+                $( $module:ident )::+
+            ),* $(,)?
+        }
+    ),* $(,)?) => {
+        /// The metadata of all the endpoints of this crate, grouped by top-level module.
+        #[allow(deprecated)]
+        const ENDPOINTS: &[&[Metadata]] = &[$(
+            $( #[$group_attr] )*
+            &[$(
+                $( #[$attr] )*
+                <crate::$group::$( $module )::+::Request as IncomingRequest>::METADATA,
+            )*],
+        )*];
+
+        /// The paths of all the endpoint modules of this crate, regardless of the enabled cargo
+        /// features.
+        #[cfg(test)]
+        const ENDPOINT_MODULES: &[&str] = &[$($(
+            concat!(stringify!($group) $(, "::", stringify!($module) )+),
+        )*)*];
+    };
+}
+
+// When adding an endpoint, its module must be added here too. The `all_endpoints_are_listed` test
+// fails if an endpoint is missing.
+endpoints! {
+    account::{
+        add_3pid::v3,
+        bind_3pid::v3,
+        change_password::v3,
+        check_registration_token_validity::v1,
+        deactivate::v3,
+        delete_3pid::v3,
+        get_3pids::v3,
+        get_username_availability::v3,
+        register::v3,
+        request_3pid_management_token_via_email::v3,
+        request_3pid_management_token_via_msisdn::v3,
+        request_openid_token::v3,
+        request_password_change_token_via_email::v3,
+        request_password_change_token_via_msisdn::v3,
+        request_registration_token_via_email::v3,
+        request_registration_token_via_msisdn::v3,
+        unbind_3pid::v3,
+        whoami::v3,
+    },
+    alias::{
+        create_alias::v3,
+        delete_alias::v3,
+        get_alias::v3,
+    },
+    appservice::{
+        request_ping::v1,
+        set_room_visibility::v3,
+    },
+    authenticated_media::{
+        get_content::v1,
+        get_content_as_filename::v1,
+        get_content_thumbnail::v1,
+        get_media_config::v1,
+        get_media_preview::v1,
+    },
+    backup::{
+        add_backup_keys::v3,
+        add_backup_keys_for_room::v3,
+        add_backup_keys_for_session::v3,
+        create_backup_version::v3,
+        delete_backup_keys::v3,
+        delete_backup_keys_for_room::v3,
+        delete_backup_keys_for_session::v3,
+        delete_backup_version::v3,
+        get_backup_info::v3,
+        get_backup_keys::v3,
+        get_backup_keys_for_room::v3,
+        get_backup_keys_for_session::v3,
+        get_latest_backup_info::v3,
+        update_backup_version::v3,
+    },
+    config::{
+        get_global_account_data::v3,
+        get_room_account_data::v3,
+        set_global_account_data::v3,
+        set_room_account_data::v3,
+    },
+    context::{
+        get_context::v3,
+    },
+    #[cfg(feature = "unstable-msc3814")]
+    dehydrated_device::{
+        delete_dehydrated_device::unstable,
+        get_dehydrated_device::unstable,
+        get_events::unstable,
+        put_dehydrated_device::unstable,
+    },
+    #[cfg(feature = "unstable-msc4140")]
+    delayed_events::{
+        delayed_message_event::unstable,
+        delayed_state_event::unstable,
+        update_delayed_event::unstable,
+    },
+    device::{
+        delete_device::v3,
+        delete_devices::v3,
+        get_device::v3,
+        get_devices::v3,
+        update_device::v3,
+    },
+    directory::{
+        get_public_rooms::v3,
+        get_public_rooms_filtered::v3,
+        get_room_visibility::v3,
+        set_room_visibility::v3,
+    },
+    discovery::{
+        discover_homeserver,
+        discover_support,
+        #[cfg(feature = "unstable-msc2965")]
+        get_authentication_issuer::msc2965,
+        get_capabilities::v3,
+        get_supported_versions,
+    },
+    filter::{
+        create_filter::v3,
+        get_filter::v3,
+    },
+    keys::{
+        claim_keys::v3,
+        #[cfg(feature = "unstable-msc3983")]
+        claim_keys::v4,
+        get_key_changes::v3,
+        get_keys::v3,
+        upload_keys::v3,
+        upload_signatures::v3,
+        upload_signing_keys::v3,
+    },
+    knock::{
+        knock_room::v3,
+    },
+    media::{
+        create_content::v3,
+        create_content_async::v3,
+        create_mxc_uri::v1,
+        get_content::v3,
+        get_content_as_filename::v3,
+        get_content_thumbnail::v3,
+        get_media_config::v3,
+        get_media_preview::v3,
+    },
+    membership::{
+        ban_user::v3,
+        forget_room::v3,
+        get_member_events::v3,
+        invite_user::v3,
+        join_room_by_id::v3,
+        join_room_by_id_or_alias::v3,
+        joined_members::v3,
+        joined_rooms::v3,
+        kick_user::v3,
+        leave_room::v3,
+        #[cfg(feature = "unstable-msc2666")]
+        mutual_rooms::unstable,
+        unban_user::v3,
+    },
+    message::{
+        get_message_events::v3,
+        send_message_event::v3,
+    },
+    presence::{
+        get_presence::v3,
+        set_presence::v3,
+    },
+    profile::{
+        get_avatar_url::v3,
+        get_display_name::v3,
+        get_profile::v3,
+        set_avatar_url::v3,
+        set_display_name::v3,
+    },
+    push::{
+        delete_pushrule::v3,
+        get_notifications::v3,
+        get_pushers::v3,
+        get_pushrule::v3,
+        get_pushrule_actions::v3,
+        get_pushrule_enabled::v3,
+        get_pushrules_all::v3,
+        get_pushrules_global_scope::v3,
+        set_pusher::v3,
+        set_pushrule::v3,
+        set_pushrule_actions::v3,
+        set_pushrule_enabled::v3,
+    },
+    read_marker::{
+        set_read_marker::v3,
+    },
+    receipt::{
+        create_receipt::v3,
+    },
+    redact::{
+        redact_event::v3,
+    },
+    relations::{
+        get_relating_events::v1,
+        get_relating_events_with_rel_type::v1,
+        get_relating_events_with_rel_type_and_event_type::v1,
+    },
+    #[cfg(feature = "unstable-msc4108")]
+    rendezvous::{
+        create_rendezvous_session::unstable,
+    },
+    room::{
+        aliases::v3,
+        create_room::v3,
+        get_event_by_timestamp::v1,
+        get_room_event::v3,
+        #[cfg(feature = "unstable-msc3266")]
+        get_summary::msc3266,
+        report_content::v3,
+        #[cfg(feature = "unstable-msc4151")]
+        report_room::v3,
+        upgrade_room::v3,
+    },
+    search::{
+        search_events::v3,
+    },
+    server::{
+        get_user_info::v3,
+    },
+    session::{
+        get_login_token::v1,
+        get_login_types::v3,
+        login::v3,
+        login_fallback,
+        logout::v3,
+        logout_all::v3,
+        refresh_token::v3,
+        sso_login::v3,
+        sso_login_with_provider::v3,
+    },
+    space::{
+        get_hierarchy::v1,
+    },
+    state::{
+        get_state_events::v3,
+        get_state_events_for_key::v3,
+        send_state_event::v3,
+    },
+    sync::{
+        sync_events::v3,
+        #[cfg(feature = "unstable-msc3575")]
+        sync_events::v4,
+        #[cfg(feature = "unstable-msc4186")]
+        sync_events::v5,
+    },
+    tag::{
+        create_tag::v3,
+        delete_tag::v3,
+        get_tags::v3,
+    },
+    thirdparty::{
+        get_location_for_protocol::v3,
+        get_location_for_room_alias::v3,
+        get_protocol::v3,
+        get_protocols::v3,
+        get_user_for_protocol::v3,
+        get_user_for_user_id::v3,
+    },
+    threads::{
+        get_threads::v1,
+    },
+    to_device::{
+        send_event_to_device::v3,
+    },
+    typing::{
+        create_typing_event::v3,
+    },
+    uiaa::{
+        get_uiaa_fallback_page::v3,
+    },
+    user_directory::{
+        search_users::v3,
+    },
+    voip::{
+        get_turn_server_info::v3,
+    },
+}
+
+/// Get the HTTP methods supported by the endpoints matching the given request path.
+///
+/// This can be used by a server to answer CORS preflight requests with the
+/// `Access-Control-Allow-Methods` header. The path must not contain a query string. An empty list
+/// is returned if the path doesn't match any endpoint of this crate.
+///
+/// # Example
+///
+/// ```
+/// use http::Method;
+/// use ruma_client_api::supported_methods_for_path;
+///
+/// let methods = supported_methods_for_path("/_matrix/client/v3/rooms/!room:example.org/join");
+/// assert_eq!(methods, [Method::POST]);
+/// ```
+pub fn supported_methods_for_path(path: &str) -> Vec<Method> {
+    // The trailing slash of state event paths is optional when the state key is empty.
+    let path_with_empty_state_key = format!("{path}/");
+    let mut methods = Vec::new();
+
+    for metadata in ENDPOINTS.iter().copied().flatten() {
+        if methods.contains(&metadata.method) {
+            continue;
+        }
+
+        let has_trailing_state_key =
+            metadata.history.all_paths().any(|canon| canon.ends_with("/:state_key"));
+
+        if metadata.history.matches_path(path)
+            || has_trailing_state_key && metadata.history.matches_path(&path_with_empty_state_key)
+        {
+            methods.push(metadata.method.clone());
+        }
+    }
+
+    methods
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs, path::Path};
+
+    use syn::Item;
+
+    use super::ENDPOINT_MODULES;
+
+    /// Collect the paths of the modules that define the `METADATA` of an endpoint, in the given
+    /// items of the module at the given path.
+    ///
+    /// `dir` is the directory that contains the files of the child modules.
+    fn collect_endpoints(
+        items: &[Item],
+        module_path: &str,
+        dir: &Path,
+        endpoints: &mut BTreeSet<String>,
+    ) {
+        for item in items {
+            match item {
+                Item::Const(item) if item.ident == "METADATA" => {
+                    endpoints.insert(module_path.to_owned());
+                }
+                Item::Mod(item) => {
+                    let name = item.ident.to_string();
+                    let child_path = if module_path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{module_path}::{name}")
+                    };
+                    let child_dir = dir.join(&name);
+
+                    if let Some((_, items)) = &item.content {
+                        collect_endpoints(items, &child_path, &child_dir, endpoints);
+                    } else {
+                        let mut file = dir.join(format!("{name}.rs"));
+                        if !file.exists() {
+                            file = child_dir.join("mod.rs");
+                        }
+
+                        let source = fs::read_to_string(&file).unwrap();
+                        let items = syn::parse_file(&source).unwrap().items;
+                        collect_endpoints(&items, &child_path, &child_dir, endpoints);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Check that the list of endpoints contains every endpoint of the crate.
+    ///
+    /// The source files are parsed, so this doesn't depend on the enabled cargo features.
+    #[test]
+    fn all_endpoints_are_listed() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let lib = syn::parse_file(&fs::read_to_string(src.join("lib.rs")).unwrap()).unwrap();
+
+        let mut endpoints = BTreeSet::new();
+        collect_endpoints(&lib.items, "", &src, &mut endpoints);
+
+        let listed = ENDPOINT_MODULES.iter().map(|path| (*path).to_owned()).collect();
+        assert_eq!(endpoints, listed);
+    }
+}
@@ -3,6 +3,7 @@
 pub mod create_filter;
 pub mod get_filter;
 
+mod builder;
 mod lazy_load;
 mod url;
 
@@ -10,7 +11,7 @@ use js_int::UInt;
 use ruma_common::{serde::StringEnum, OwnedRoomId, OwnedUserId};
 use serde::{Deserialize, Serialize};
 
-pub use self::{lazy_load::LazyLoadOptions, url::UrlFilter};
+pub use self::{builder::FilterDefinitionBuilder, lazy_load::LazyLoadOptions, url::UrlFilter};
 use crate::PrivOwnedStr;
 
 /// Format to use for returned events.
@@ -340,6 +341,11 @@ impl FilterDefinition {
         Self { room: RoomFilter::with_lazy_loading(), ..Default::default() }
     }
 
+    /// Creates a builder for a `FilterDefinition`.
+    pub fn builder() -> FilterDefinitionBuilder {
+        FilterDefinitionBuilder::default()
+    }
+
     /// Returns `true` if all fields are empty.
     pub fn is_empty(&self) -> bool {
         self.event_fields.is_none()
@@ -367,6 +373,8 @@ can_be_empty!(RoomFilter);
 
 #[cfg(test)]
 mod tests {
+    use js_int::uint;
+    use ruma_common::{owned_room_id, owned_user_id};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{
@@ -434,4 +442,39 @@ mod tests {
             LazyLoadOptions::Enabled { include_redundant_members: false }
         );
     }
+
+    #[test]
+    fn serialize_built_filter_definition() {
+        let filter = FilterDefinition::builder()
+            .lazy_load_members()
+            .timeline_limit(uint!(10))
+            .include_room(owned_room_id!("!a:example.org"))
+            .include_room(owned_room_id!("!b:example.org"))
+            .exclude_room(owned_room_id!("!c:example.org"))
+            .not_senders([owned_user_id!("@bot:example.org")])
+            .ignore_presence()
+            .build();
+
+        assert_eq!(
+            to_json_value(filter).unwrap(),
+            json!({
+                "presence": {
+                    "types": [],
+                },
+                "room": {
+                    "rooms": ["!a:example.org", "!b:example.org"],
+                    "not_rooms": ["!c:example.org"],
+                    "state": {
+                        "lazy_load_members": true,
+                    },
+                    "timeline": {
+                        "limit": 10,
+                        "not_senders": ["@bot:example.org"],
+                    },
+                },
+            })
+        );
+
+        assert!(FilterDefinition::builder().build().is_empty());
+    }
 }
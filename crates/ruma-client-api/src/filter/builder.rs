@@ -0,0 +1,93 @@
+use js_int::UInt;
+use ruma_common::{OwnedRoomId, OwnedUserId};
+
+use super::{Filter, FilterDefinition, LazyLoadOptions};
+
+/// A builder for a [`FilterDefinition`] covering the common cases.
+///
+/// To get an instance of it, call [`FilterDefinition::builder()`]. For cases that are not
+/// covered, the fields of the [`FilterDefinition`] returned by [`build()`] can be modified
+/// directly.
+///
+/// [`build()`]: Self::build
+///
+/// # Example
+///
+/// ```
+/// use js_int::uint;
+/// use ruma_client_api::filter::FilterDefinition;
+/// use ruma_common::{owned_room_id, owned_user_id};
+///
+/// let filter = FilterDefinition::builder()
+///     .lazy_load_members()
+///     .timeline_limit(uint!(20))
+///     .include_room(owned_room_id!("!n8f893n9:example.com"))
+///     .not_senders([owned_user_id!("@bot:example.com")])
+///     .build();
+///
+/// assert_eq!(filter.room.timeline.limit, Some(uint!(20)));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct FilterDefinitionBuilder {
+    filter: FilterDefinition,
+}
+
+impl FilterDefinitionBuilder {
+    /// Enable [room member lazy-loading] for the state of rooms.
+    ///
+    /// Redundant membership events are disabled.
+    ///
+    /// [room member lazy-loading]: https://spec.matrix.org/latest/client-server-api/#lazy-loading-room-members
+    pub fn lazy_load_members(mut self) -> Self {
+        self.filter.room.state.lazy_load_options =
+            LazyLoadOptions::Enabled { include_redundant_members: false };
+        self
+    }
+
+    /// Set the maximum number of events to return in the timeline of each room.
+    pub fn timeline_limit(mut self, limit: UInt) -> Self {
+        self.filter.room.timeline.limit = Some(limit);
+        self
+    }
+
+    /// Only include the given room.
+    ///
+    /// This can be called several times to include several rooms. If this is never called, all
+    /// rooms are included.
+    pub fn include_room(mut self, room_id: OwnedRoomId) -> Self {
+        self.filter.room.rooms.get_or_insert_with(Vec::new).push(room_id);
+        self
+    }
+
+    /// Exclude the given room.
+    ///
+    /// This takes precedence over [`include_room()`](Self::include_room).
+    pub fn exclude_room(mut self, room_id: OwnedRoomId) -> Self {
+        self.filter.room.not_rooms.push(room_id);
+        self
+    }
+
+    /// Exclude the timeline events sent by the given users.
+    pub fn not_senders(mut self, user_ids: impl IntoIterator<Item = OwnedUserId>) -> Self {
+        self.filter.room.timeline.not_senders.extend(user_ids);
+        self
+    }
+
+    /// Include rooms that the user has left.
+    pub fn include_leave(mut self) -> Self {
+        self.filter.room.include_leave = true;
+        self
+    }
+
+    /// Ignore all presence updates.
+    pub fn ignore_presence(mut self) -> Self {
+        self.filter.presence = Filter::ignore_all();
+        self
+    }
+
+    /// Build the [`FilterDefinition`].
+    pub fn build(self) -> FilterDefinition {
+        self.filter
+    }
+}
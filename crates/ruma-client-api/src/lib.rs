@@ -23,6 +23,8 @@ pub mod delayed_events;
 pub mod device;
 pub mod directory;
 pub mod discovery;
+#[cfg(feature = "server")]
+mod endpoints;
 pub mod error;
 pub mod filter;
 pub mod http_headers;
@@ -58,6 +60,8 @@ pub mod voip;
 
 use std::fmt;
 
+#[cfg(feature = "server")]
+pub use endpoints::supported_methods_for_path;
 pub use error::Error;
 
 // Wrapper around `Box<str>` that cannot be used in a meaningful way outside of
@@ -10,12 +10,12 @@ use ruma_common::{
     metadata,
     presence::PresenceState,
     serde::Raw,
-    OneTimeKeyAlgorithm, OwnedEventId, OwnedRoomId, OwnedUserId,
+    OneTimeKeyAlgorithm, OwnedEventId, OwnedRoomId, OwnedUserId, RoomId,
 };
 use ruma_events::{
     presence::PresenceEvent, AnyGlobalAccountDataEvent, AnyRoomAccountDataEvent,
     AnyStrippedStateEvent, AnySyncEphemeralRoomEvent, AnySyncStateEvent, AnySyncTimelineEvent,
-    AnyToDeviceEvent,
+    AnyTimelineEvent, AnyToDeviceEvent,
 };
 use serde::{Deserialize, Serialize};
 
@@ -396,6 +396,21 @@ impl Timeline {
     pub fn is_empty(&self) -> bool {
         !self.limited && self.prev_batch.is_none() && self.events.is_empty()
     }
+
+    /// Deserializes the events of this timeline and converts them to full events, by adding the
+    /// given room ID.
+    ///
+    /// The events that fail to deserialize are returned as-is in the `Err` variant.
+    pub fn full_events<'a>(
+        &'a self,
+        room_id: &'a RoomId,
+    ) -> impl Iterator<Item = Result<AnyTimelineEvent, &'a Raw<AnySyncTimelineEvent>>> + 'a {
+        self.events.iter().map(move |raw| {
+            raw.deserialize()
+                .map(|event| event.into_full_event(room_id.to_owned()))
+                .map_err(|_| raw)
+        })
+    }
 }
 
 /// State events in the room.
@@ -628,7 +643,10 @@ impl ToDevice {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches2::assert_matches;
     use assign::assign;
+    use ruma_common::room_id;
+    use ruma_events::{AnyMessageLikeEvent, AnyStateEvent, AnyTimelineEvent};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::Timeline;
@@ -649,6 +667,52 @@ mod tests {
             from_json_value::<Timeline>(json!({ "events": [] })).unwrap();
         assert!(!timeline_default_deserialized.limited);
     }
+
+    #[test]
+    fn timeline_full_events() {
+        let timeline = from_json_value::<Timeline>(json!({
+            "events": [
+                {
+                    "content": { "body": "Hello", "msgtype": "m.text" },
+                    "event_id": "$message",
+                    "origin_server_ts": 1,
+                    "sender": "@alice:example.org",
+                    "type": "m.room.message",
+                },
+                {
+                    "content": { "name": "Room" },
+                    "event_id": "$name",
+                    "origin_server_ts": 2,
+                    "sender": "@alice:example.org",
+                    "state_key": "",
+                    "type": "m.room.name",
+                },
+                {
+                    "content": { "body": "Missing event ID", "msgtype": "m.text" },
+                    "origin_server_ts": 3,
+                    "sender": "@alice:example.org",
+                    "type": "m.room.message",
+                },
+            ],
+        }))
+        .unwrap();
+        let room_id = room_id!("!room:example.org");
+
+        let mut events = timeline.full_events(room_id);
+
+        assert_matches!(
+            events.next(),
+            Some(Ok(AnyTimelineEvent::MessageLike(AnyMessageLikeEvent::RoomMessage(_))))
+        );
+        assert_matches!(
+            events.next(),
+            Some(Ok(event @ AnyTimelineEvent::State(AnyStateEvent::RoomName(_))))
+        );
+        assert_eq!(event.room_id(), room_id);
+        assert_matches!(events.next(), Some(Err(raw)));
+        assert_eq!(raw.get_field::<u64>("origin_server_ts").unwrap(), Some(3));
+        assert_matches!(events.next(), None);
+    }
 }
 
 #[cfg(all(test, feature = "client"))]
@@ -0,0 +1,42 @@
+#![cfg(feature = "server")]
+
+use http::Method;
+use ruma_client_api::supported_methods_for_path;
+
+#[test]
+fn single_method() {
+    assert_eq!(supported_methods_for_path("/_matrix/client/v3/account/whoami"), [Method::GET]);
+    assert_eq!(supported_methods_for_path("/_matrix/client/r0/account/whoami"), [Method::GET]);
+}
+
+#[test]
+fn several_methods() {
+    let mut methods = supported_methods_for_path(
+        "/_matrix/client/v3/rooms/!room:example.org/state/m.room.topic/",
+    );
+    methods.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(methods, [Method::GET, Method::PUT]);
+
+    // The trailing slash is optional when the state key is empty.
+    let mut methods =
+        supported_methods_for_path("/_matrix/client/v3/rooms/!room:example.org/state/m.room.topic");
+    methods.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(methods, [Method::GET, Method::PUT]);
+
+    // Other trailing parameters can't be omitted.
+    assert_eq!(
+        supported_methods_for_path("/_matrix/client/v3/user/@alice:example.org/filter"),
+        [Method::POST]
+    );
+
+    let mut methods =
+        supported_methods_for_path("/_matrix/client/v3/pushrules/global/override/my_rule");
+    methods.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(methods, [Method::DELETE, Method::GET, Method::PUT]);
+}
+
+#[test]
+fn unknown_path() {
+    assert!(supported_methods_for_path("/_matrix/client/v3/unknown").is_empty());
+    assert!(supported_methods_for_path("/_matrix/client/v3/account/whoami/extra").is_empty());
+}
@@ -1,4 +1,5 @@
 use std::{
+    fmt, mem,
     sync::{Arc, Mutex},
     time::Duration,
 };
@@ -7,7 +8,11 @@ use assign::assign;
 use async_stream::try_stream;
 use futures_core::stream::Stream;
 use ruma_client_api::{
-    account::register::{self, RegistrationKind},
+    account::{
+        register::{self, RegistrationKind},
+        whoami,
+    },
+    authenticated_media, media,
     session::login::{self, v3::LoginInfo},
     sync::sync_events,
     uiaa::UserIdentifier,
@@ -15,23 +20,34 @@ use ruma_client_api::{
 use ruma_common::{
     api::{MatrixVersion, OutgoingRequest, SendAccessToken},
     presence::PresenceState,
-    DeviceId, UserId,
+    DeviceId, MxcUri, OwnedDeviceId, OwnedMxcUri, OwnedUserId, UserId,
 };
 
 use crate::{
-    add_user_id_to_query, send_customized_request, Error, HttpClient, ResponseError, ResponseResult,
+    add_user_id_to_query, send_customized_request, send_customized_request_with_parts, Error,
+    HttpClient, ResponseError, ResponseHook, ResponseResult,
 };
 
 mod builder;
+mod discovery;
+#[cfg(feature = "tokio")]
+mod sync;
 
-pub use self::builder::ClientBuilder;
+#[cfg(feature = "tokio")]
+pub use self::sync::{SyncHandle, SyncSettings, SyncToken};
+pub use self::{
+    builder::ClientBuilder,
+    discovery::{discover_homeserver, DiscoveryError},
+};
 
 /// A client for the Matrix client-server API.
 #[derive(Clone, Debug)]
 pub struct Client<C>(Arc<ClientData<C>>);
 
+/// A function called with the parts of every HTTP request sent by a [`Client`].
+type RequestHook = Box<dyn Fn(&mut http::request::Parts) + Send + Sync>;
+
 /// Data contained in Client's Rc
-#[derive(Debug)]
 struct ClientData<C> {
     /// The URL of the homeserver to connect to.
     homeserver_url: String,
@@ -42,8 +58,33 @@ struct ClientData<C> {
     /// The access token, if logged in.
     access_token: Mutex<Option<String>>,
 
+    /// The user ID of the current session, if known.
+    user_id: Mutex<Option<OwnedUserId>>,
+
+    /// The device ID of the current session, if known.
+    device_id: Mutex<Option<OwnedDeviceId>>,
+
     /// The (known) Matrix versions the homeserver supports.
     supported_matrix_versions: Vec<MatrixVersion>,
+
+    /// The functions called with every outgoing request.
+    request_hooks: Vec<RequestHook>,
+
+    /// The functions called with every incoming response.
+    response_hooks: Vec<ResponseHook>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for ClientData<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientData")
+            .field("homeserver_url", &self.homeserver_url)
+            .field("http_client", &self.http_client)
+            .field("access_token", &self.access_token)
+            .field("user_id", &self.user_id)
+            .field("device_id", &self.device_id)
+            .field("supported_matrix_versions", &self.supported_matrix_versions)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Client<()> {
@@ -60,6 +101,30 @@ impl<C> Client<C> {
     pub fn access_token(&self) -> Option<String> {
         self.0.access_token.lock().expect("session mutex was poisoned").clone()
     }
+
+    /// Get the user ID of the current session, if known.
+    ///
+    /// It is known after a successful call to [`log_in`][Client::log_in],
+    /// [`register_user`][Client::register_user], [`register_guest`][Client::register_guest] or
+    /// [`whoami`][Client::whoami].
+    pub fn user_id(&self) -> Option<OwnedUserId> {
+        self.0.user_id.lock().expect("session mutex was poisoned").clone()
+    }
+
+    /// Get the device ID of the current session, if known.
+    ///
+    /// It is known after a successful call to [`log_in`][Client::log_in], or to
+    /// [`register_user`][Client::register_user], [`register_guest`][Client::register_guest] or
+    /// [`whoami`][Client::whoami] if the server returned it.
+    pub fn device_id(&self) -> Option<OwnedDeviceId> {
+        self.0.device_id.lock().expect("session mutex was poisoned").clone()
+    }
+
+    /// Store the user ID and device ID of the current session.
+    fn set_session_ids(&self, user_id: OwnedUserId, device_id: Option<OwnedDeviceId>) {
+        *self.0.user_id.lock().expect("session mutex was poisoned") = Some(user_id);
+        *self.0.device_id.lock().expect("session mutex was poisoned") = device_id;
+    }
 }
 
 impl<C: HttpClient> Client<C> {
@@ -68,7 +133,32 @@ impl<C: HttpClient> Client<C> {
         self.send_customized_request(request, |_| Ok(())).await
     }
 
+    /// Makes a request to a Matrix API endpoint, failing with [`Error::Timeout`] if no response
+    /// was received within the given duration.
+    ///
+    /// The request is cancelled when the timeout elapses or when the returned future is dropped.
+    ///
+    /// This requires the `tokio` feature.
+    ///
+    /// # Panics
+    ///
+    /// This uses the timer of the tokio runtime, so it panics if it is not called from within a
+    /// tokio runtime with the time driver enabled.
+    #[cfg(feature = "tokio")]
+    pub async fn send_request_with_timeout<R: OutgoingRequest>(
+        &self,
+        request: R,
+        timeout: Duration,
+    ) -> ResponseResult<C, R> {
+        tokio::time::timeout(timeout, self.send_request(request))
+            .await
+            .unwrap_or(Err(Error::Timeout))
+    }
+
     /// Makes a request to a Matrix API endpoint including additional URL parameters.
+    ///
+    /// The hooks registered with [`ClientBuilder::request_hook()`] are called before
+    /// `customize`.
     pub async fn send_customized_request<R, F>(
         &self,
         request: R,
@@ -79,18 +169,18 @@ impl<C: HttpClient> Client<C> {
         F: FnOnce(&mut http::Request<C::RequestBody>) -> Result<(), ResponseError<C, R>>,
     {
         let access_token = self.access_token();
-        let send_access_token = match access_token.as_deref() {
-            Some(at) => SendAccessToken::IfRequired(at),
-            None => SendAccessToken::None,
-        };
 
         send_customized_request(
             &self.0.http_client,
             &self.0.homeserver_url,
-            send_access_token,
+            send_access_token(access_token.as_deref()),
             &self.0.supported_matrix_versions,
             request,
-            customize,
+            |http_request| {
+                call_request_hooks(&self.0.request_hooks, http_request);
+                customize(http_request)
+            },
+            &self.0.response_hooks,
         )
         .await
     }
@@ -107,10 +197,53 @@ impl<C: HttpClient> Client<C> {
         self.send_customized_request(request, add_user_id_to_query::<C, R>(user_id)).await
     }
 
+    /// Makes a request to a Matrix API endpoint, and returns the parts of the HTTP response in
+    /// addition to the deserialized response.
+    ///
+    /// This is useful to read the status code or headers of the response that are not part of the
+    /// response type, like `Retry-After`.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// # use ruma_client_api::discovery::get_supported_versions;
+    /// # let homeserver_url = "https://example.com".to_owned();
+    /// # async {
+    /// # let client = ruma_client::Client::builder()
+    /// #     .homeserver_url(homeserver_url)
+    /// #     .build::<ruma_client::http_client::Dummy>()
+    /// #     .await?;
+    /// let (response, parts) = client.send_raw_request(get_supported_versions::Request::new()).await?;
+    /// let server = parts.headers.get(http::header::SERVER);
+    /// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+    /// # };
+    /// ```
+    pub async fn send_raw_request<R: OutgoingRequest>(
+        &self,
+        request: R,
+    ) -> Result<(R::IncomingResponse, http::response::Parts), ResponseError<C, R>> {
+        let access_token = self.access_token();
+
+        send_customized_request_with_parts(
+            &self.0.http_client,
+            &self.0.homeserver_url,
+            send_access_token(access_token.as_deref()),
+            &self.0.supported_matrix_versions,
+            request,
+            |http_request| {
+                call_request_hooks(&self.0.request_hooks, http_request);
+                Ok(())
+            },
+            &self.0.response_hooks,
+        )
+        .await
+    }
+
     /// Log in with a username and password.
     ///
-    /// In contrast to [`send_request`][Self::send_request], this method stores the access token
-    /// returned by the endpoint in this client, in addition to returning it.
+    /// In contrast to [`send_request`][Self::send_request], this method stores the access token,
+    /// user ID and device ID returned by the endpoint in this client, in addition to returning
+    /// them.
     pub async fn log_in(
         &self,
         user: &str,
@@ -130,14 +263,16 @@ impl<C: HttpClient> Client<C> {
             .await?;
 
         *self.0.access_token.lock().unwrap() = Some(response.access_token.clone());
+        self.set_session_ids(response.user_id.clone(), Some(response.device_id.clone()));
 
         Ok(response)
     }
 
     /// Register as a guest.
     ///
-    /// In contrast to [`send_request`][Self::send_request], this method stores the access token
-    /// returned by the endpoint in this client, in addition to returning it.
+    /// In contrast to [`send_request`][Self::send_request], this method stores the access token,
+    /// user ID and device ID returned by the endpoint in this client, in addition to returning
+    /// them.
     pub async fn register_guest(
         &self,
     ) -> Result<register::v3::Response, Error<C::Error, ruma_client_api::uiaa::UiaaResponse>> {
@@ -146,14 +281,16 @@ impl<C: HttpClient> Client<C> {
             .await?;
 
         self.0.access_token.lock().unwrap().clone_from(&response.access_token);
+        self.set_session_ids(response.user_id.clone(), response.device_id.clone());
 
         Ok(response)
     }
 
     /// Register as a new user on this server.
     ///
-    /// In contrast to [`send_request`][Self::send_request], this method stores the access token
-    /// returned by the endpoint in this client, in addition to returning it.
+    /// In contrast to [`send_request`][Self::send_request], this method stores the access token,
+    /// user ID and device ID returned by the endpoint in this client, in addition to returning
+    /// them.
     ///
     /// The username is the local part of the returned user_id. If it is omitted from this request,
     /// the server will generate one.
@@ -170,10 +307,101 @@ impl<C: HttpClient> Client<C> {
             .await?;
 
         self.0.access_token.lock().unwrap().clone_from(&response.access_token);
+        self.set_session_ids(response.user_id.clone(), response.device_id.clone());
+
+        Ok(response)
+    }
+
+    /// Get information about the owner of the current access token.
+    ///
+    /// In contrast to [`send_request`][Self::send_request], this method stores the user ID and
+    /// device ID returned by the endpoint in this client, in addition to returning them. This is
+    /// useful to know them when restoring a session from a stored access token.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// # let homeserver_url = "https://example.com".to_owned();
+    /// # let access_token = "secret".to_owned();
+    /// # async {
+    /// let client = ruma_client::Client::builder()
+    ///     .homeserver_url(homeserver_url)
+    ///     .access_token(Some(access_token))
+    ///     .build::<ruma_client::http_client::Dummy>()
+    ///     .await?;
+    ///
+    /// client.whoami().await?;
+    /// let user_id = client.user_id().unwrap();
+    /// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+    /// # };
+    /// ```
+    pub async fn whoami(
+        &self,
+    ) -> Result<whoami::v3::Response, Error<C::Error, ruma_client_api::Error>> {
+        let response = self.send_request(whoami::v3::Request::new()).await?;
+
+        self.set_session_ids(response.user_id.clone(), response.device_id.clone());
 
         Ok(response)
     }
 
+    /// Upload the given data to the content repository of the homeserver.
+    ///
+    /// Returns the MXC URI of the uploaded content.
+    pub async fn upload_media(
+        &self,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<OwnedMxcUri, Error<C::Error, ruma_client_api::Error>> {
+        let response = self
+            .send_request(assign!(media::create_content::v3::Request::new(data), {
+                content_type: Some(content_type.to_owned()),
+            }))
+            .await?;
+
+        Ok(response.content_uri)
+    }
+
+    /// Download the content at the given MXC URI.
+    ///
+    /// Returns the content and its content type, if the server provided it.
+    ///
+    /// If the homeserver supports Matrix 1.11 or later, the authenticated media endpoint is used,
+    /// otherwise the deprecated unauthenticated endpoint is used.
+    pub async fn download_media(
+        &self,
+        mxc: &MxcUri,
+    ) -> Result<(Vec<u8>, Option<String>), Error<C::Error, ruma_client_api::Error>> {
+        let (server_name, media_id) = mxc.parts().map_err(Error::InvalidMxcUri)?;
+
+        let supports_authenticated_media = self
+            .0
+            .supported_matrix_versions
+            .iter()
+            .any(|version| version.is_superset_of(MatrixVersion::V1_11));
+
+        if supports_authenticated_media {
+            let response = self
+                .send_request(authenticated_media::get_content::v1::Request::new(
+                    media_id.to_owned(),
+                    server_name.to_owned(),
+                ))
+                .await?;
+
+            Ok((response.file, response.content_type))
+        } else {
+            #[allow(deprecated)]
+            let response = self
+                .send_request(media::get_content::v3::Request::new(
+                    media_id.to_owned(),
+                    server_name.to_owned(),
+                ))
+                .await?;
+
+            Ok((response.file, response.content_type))
+        }
+    }
+
     /// Convenience method that represents repeated calls to the sync_events endpoint as a stream.
     ///
     /// # Example:
@@ -227,3 +455,24 @@ impl<C: HttpClient> Client<C> {
         }
     }
 }
+
+/// Get the `SendAccessToken` to use with the given access token of a [`Client`].
+fn send_access_token(access_token: Option<&str>) -> SendAccessToken<'_> {
+    match access_token {
+        Some(at) => SendAccessToken::IfRequired(at),
+        None => SendAccessToken::None,
+    }
+}
+
+/// Call the given hooks with the parts of the given request, without its body.
+fn call_request_hooks<B: Default>(hooks: &[RequestHook], request: &mut http::Request<B>) {
+    if hooks.is_empty() {
+        return;
+    }
+
+    let (mut parts, body) = mem::take(request).into_parts();
+    for hook in hooks {
+        hook(&mut parts);
+    }
+    *request = http::Request::from_parts(parts, body);
+}
@@ -1,10 +1,16 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use ruma_client_api::discovery::get_supported_versions;
 use ruma_common::api::{MatrixVersion, SendAccessToken};
 
-use super::{Client, ClientData};
-use crate::{DefaultConstructibleHttpClient, Error, HttpClient, HttpClientExt};
+use super::{call_request_hooks, Client, ClientData, RequestHook};
+use crate::{
+    send_customized_request, DefaultConstructibleHttpClient, Error, HttpClient, HttpClientSettings,
+    ResponseHook,
+};
 
 /// A [`Client`] builder.
 ///
@@ -13,11 +19,21 @@ pub struct ClientBuilder {
     homeserver_url: Option<String>,
     access_token: Option<String>,
     supported_matrix_versions: Option<Vec<MatrixVersion>>,
+    request_hooks: Vec<RequestHook>,
+    response_hooks: Vec<ResponseHook>,
+    http_client_settings: HttpClientSettings,
 }
 
 impl ClientBuilder {
     pub(super) fn new() -> Self {
-        Self { homeserver_url: None, access_token: None, supported_matrix_versions: None }
+        Self {
+            homeserver_url: None,
+            access_token: None,
+            supported_matrix_versions: None,
+            request_hooks: Vec::new(),
+            response_hooks: Vec::new(),
+            http_client_settings: HttpClientSettings::new(),
+        }
     }
 
     /// Set the homeserver URL.
@@ -42,9 +58,88 @@ impl ClientBuilder {
         Self { supported_matrix_versions: Some(versions), ..self }
     }
 
+    /// Add a function that is called with every request sent by the [`Client`].
+    ///
+    /// It can be used to modify the method, URI or headers of all requests, e.g. to set a custom
+    /// user agent. The hooks are called in the order they were added, before the `customize`
+    /// closure of [`Client::send_customized_request()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::header::{HeaderValue, USER_AGENT};
+    /// use ruma_client::Client;
+    ///
+    /// let builder =
+    ///     Client::builder().homeserver_url("https://example.com".to_owned()).request_hook(|parts| {
+    ///         parts.headers.insert(USER_AGENT, HeaderValue::from_static("my-client/1.0"));
+    ///     });
+    /// ```
+    pub fn request_hook(
+        mut self,
+        hook: impl Fn(&mut http::request::Parts) + Send + Sync + 'static,
+    ) -> Self {
+        self.request_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Add a function that is called with every response received by the [`Client`], before it
+    /// is deserialized.
+    ///
+    /// It can be used to record metrics about the responses, like their status code. The hooks
+    /// are called in the order they were added.
+    pub fn response_hook(
+        mut self,
+        hook: impl Fn(&http::response::Parts) + Send + Sync + 'static,
+    ) -> Self {
+        self.response_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Set the maximum number of idle connections per host in the connection pool of the HTTP
+    /// client.
+    ///
+    /// Like the other HTTP client settings, it is only used by [`build()`][Self::build], and
+    /// only if the HTTP client supports it, which is the case for the built-in hyper and reqwest
+    /// clients.
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.http_client_settings.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Set the duration after which an idle connection in the connection pool of the HTTP client
+    /// is closed.
+    ///
+    /// Like the other HTTP client settings, it is only used by [`build()`][Self::build], and
+    /// only if the HTTP client supports it.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client_settings.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for establishing a connection with the HTTP client.
+    ///
+    /// Like the other HTTP client settings, it is only used by [`build()`][Self::build], and
+    /// only if the HTTP client supports it.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client_settings.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set whether the HTTP client should only use HTTP/2, without negotiating the HTTP version
+    /// with the server.
+    ///
+    /// Like the other HTTP client settings, it is only used by [`build()`][Self::build], and
+    /// only if the HTTP client supports it.
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http_client_settings.http2_prior_knowledge = enabled;
+        self
+    }
+
     /// Finish building the [`Client`].
     ///
-    /// Uses [`DefaultConstructibleHttpClient::default()`] to create an HTTP client instance.
+    /// Uses [`DefaultConstructibleHttpClient::with_settings()`] to create an HTTP client
+    /// instance, with the settings set on this builder.
     /// Unless the supported Matrix versions were manually set via
     /// [`supported_matrix_versions`][Self::supported_matrix_versions], this will do a
     /// [`get_supported_versions`] request to find out about the supported versions.
@@ -52,11 +147,14 @@ impl ClientBuilder {
     where
         C: DefaultConstructibleHttpClient,
     {
-        self.http_client(C::default()).await
+        let http_client = C::with_settings(&self.http_client_settings);
+        self.http_client(http_client).await
     }
 
     /// Set the HTTP client to finish building the [`Client`].
     ///
+    /// The HTTP client settings set on this builder are ignored.
+    ///
     /// Unless the supported Matrix versions were manually set via
     /// [`supported_matrix_versions`][Self::supported_matrix_versions], this will do a
     /// [`get_supported_versions`] request to find out about the supported versions.
@@ -73,23 +171,32 @@ impl ClientBuilder {
 
         let supported_matrix_versions = match self.supported_matrix_versions {
             Some(versions) => versions,
-            None => http_client
-                .send_matrix_request(
-                    &homeserver_url,
-                    SendAccessToken::None,
-                    &[MatrixVersion::V1_0],
-                    get_supported_versions::Request::new(),
-                )
-                .await?
-                .known_versions()
-                .collect(),
+            None => send_customized_request(
+                &http_client,
+                &homeserver_url,
+                SendAccessToken::None,
+                &[MatrixVersion::V1_0],
+                get_supported_versions::Request::new(),
+                |http_request| {
+                    call_request_hooks(&self.request_hooks, http_request);
+                    Ok(())
+                },
+                &self.response_hooks,
+            )
+            .await?
+            .known_versions()
+            .collect(),
         };
 
         Ok(Client(Arc::new(ClientData {
             homeserver_url,
             http_client,
             access_token: Mutex::new(self.access_token),
+            user_id: Mutex::new(None),
+            device_id: Mutex::new(None),
             supported_matrix_versions,
+            request_hooks: self.request_hooks,
+            response_hooks: self.response_hooks,
         })))
     }
 }
@@ -0,0 +1,95 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use ruma_client_api::discovery::discover_homeserver as well_known;
+use ruma_common::{
+    api::{error::FromHttpResponseError, MatrixVersion, SendAccessToken},
+    ServerName,
+};
+
+use crate::{Error, HttpClient, HttpClientExt};
+
+/// Discover the URL of the homeserver of the given server name.
+///
+/// This uses the [`.well-known/matrix/client`] endpoint of the server name. If the endpoint is not
+/// found, `https://{server_name}` is used as the homeserver URL.
+///
+/// The returned URL can be passed to [`ClientBuilder::homeserver_url()`]. Checking that it is a
+/// valid homeserver is done by [`ClientBuilder::build()`], which queries the versions supported by
+/// the homeserver.
+///
+/// ```no_run
+/// # async {
+/// # type HttpClient = ruma_client::http_client::Dummy;
+/// use ruma_client::DefaultConstructibleHttpClient;
+/// use ruma_common::server_name;
+///
+/// let http_client = HttpClient::default();
+/// let homeserver_url =
+///     match ruma_client::discover_homeserver(&http_client, server_name!("example.org")).await {
+///         Ok(url) => url,
+///         Err(error) => panic!("Could not discover homeserver: {error:?}"),
+///     };
+///
+/// let client = ruma_client::Client::builder()
+///     .homeserver_url(homeserver_url)
+///     .http_client(http_client)
+///     .await?;
+/// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+/// # };
+/// ```
+///
+/// [`.well-known/matrix/client`]: https://spec.matrix.org/latest/client-server-api/#well-known-uri
+/// [`ClientBuilder::homeserver_url()`]: crate::ClientBuilder::homeserver_url
+/// [`ClientBuilder::build()`]: crate::ClientBuilder::build
+pub async fn discover_homeserver<C: HttpClient>(
+    http_client: &C,
+    server_name: &ServerName,
+) -> Result<String, DiscoveryError<C::Error>> {
+    let server_url = format!("https://{server_name}");
+
+    let response = match http_client
+        .send_matrix_request(
+            &server_url,
+            SendAccessToken::None,
+            &[MatrixVersion::V1_0],
+            well_known::Request::new(),
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(Error::FromHttpResponse(FromHttpResponseError::Server(error)))
+            if error.status_code == http::StatusCode::NOT_FOUND =>
+        {
+            return Ok(server_url);
+        }
+        Err(error) => return Err(DiscoveryError::Request(error)),
+    };
+
+    if response.homeserver.validated_base_url().is_some() {
+        Ok(response.homeserver.base_url.trim_end_matches('/').to_owned())
+    } else {
+        Err(DiscoveryError::InvalidBaseUrl(response.homeserver.base_url))
+    }
+}
+
+/// An error that can occur when discovering the URL of a homeserver.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DiscoveryError<E> {
+    /// The request to the `.well-known/matrix/client` endpoint failed.
+    Request(Error<E, ruma_client_api::Error>),
+
+    /// The `m.homeserver.base_url` returned by the server is not a valid URL.
+    InvalidBaseUrl(String),
+}
+
+impl<E: Display> Display for DiscoveryError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(err) => write!(f, "Homeserver discovery request failed: {err}"),
+            Self::InvalidBaseUrl(url) => write!(f, "Invalid homeserver base URL: {url}"),
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for DiscoveryError<E> {}
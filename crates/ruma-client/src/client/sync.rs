@@ -0,0 +1,320 @@
+use std::{
+    future::{poll_fn, Future as _},
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::Poll,
+    time::Duration,
+};
+
+use assign::assign;
+use async_stream::stream;
+use futures_core::stream::Stream;
+use ruma_client_api::sync::sync_events;
+use ruma_common::presence::PresenceState;
+use tokio::sync::{futures::Notified, Notify};
+
+use super::Client;
+use crate::{Error, HttpClient};
+
+/// The delay before retrying a failed sync request for the first time.
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The maximum delay before retrying a failed sync request.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+impl<C: HttpClient> Client<C> {
+    /// Convenience method that represents repeated calls to the sync_events endpoint as a stream,
+    /// configured with the given [`SyncSettings`].
+    ///
+    /// The `since` token is advanced automatically with the `next_batch` token of each response.
+    /// It can be read or reset through the [`SyncToken`] returned by [`SyncSettings::token()`].
+    ///
+    /// The filter can be changed and the current request can be interrupted through the
+    /// [`SyncHandle`] returned by [`SyncSettings::handle()`].
+    ///
+    /// In contrast to [`sync`][Self::sync], errors don't terminate the stream: the failed request
+    /// is retried with the same token when the next item is polled, after a delay that starts at
+    /// one second and doubles with each consecutive failure, up to one minute. The stream only
+    /// stops when it is dropped.
+    ///
+    /// This requires the `tokio` feature.
+    ///
+    /// # Panics
+    ///
+    /// This uses the timer of the tokio runtime to wait between retries, so the stream panics if
+    /// it is not polled from within a tokio runtime with the time driver enabled.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// # use ruma_client::SyncSettings;
+    /// # use tokio_stream::{StreamExt as _};
+    /// # let homeserver_url = "https://example.com".to_owned();
+    /// # async {
+    /// # let client = ruma_client::Client::builder()
+    /// #     .homeserver_url(homeserver_url)
+    /// #     .build::<ruma_client::http_client::Dummy>()
+    /// #     .await?;
+    /// let settings = SyncSettings::new().timeout(Duration::from_secs(30));
+    /// let token = settings.token();
+    ///
+    /// let mut sync_stream = Box::pin(client.sync_stream(settings));
+    /// while let Some(result) = sync_stream.next().await {
+    ///     match result {
+    ///         Ok(response) => {
+    ///             // Do something with the data in the response...
+    ///         }
+    ///         Err(error) => {
+    ///             // Handle the error, and maybe start over with an initial sync...
+    ///             token.set(None);
+    ///         }
+    ///     }
+    /// }
+    /// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+    /// # };
+    /// ```
+    pub fn sync_stream(
+        &self,
+        settings: SyncSettings,
+    ) -> impl Stream<Item = Result<sync_events::v3::Response, Error<C::Error, ruma_client_api::Error>>>
+           + '_ {
+        let SyncSettings { mut filter, handle, since, set_presence, timeout } = settings;
+
+        stream! {
+            let mut retry_delay = None;
+
+            loop {
+                if let Some(delay) = retry_delay {
+                    tokio::time::sleep(delay).await;
+                }
+
+                // Register the waiter before reading the settings, so an interruption that happens
+                // while the request is built is not missed.
+                let mut interrupted = pin!(handle.notified());
+                interrupted.as_mut().enable();
+
+                if let Some(new_filter) = handle.take_new_filter() {
+                    filter = new_filter;
+                }
+
+                let timeout = if handle.take_interrupted() { Some(Duration::ZERO) } else { timeout };
+                let mut request = pin!(self.send_request(assign!(sync_events::v3::Request::new(), {
+                    filter: filter.clone(),
+                    since: since.get(),
+                    set_presence: set_presence.clone(),
+                    timeout,
+                })));
+
+                let result = poll_fn(|cx| {
+                    if interrupted.as_mut().poll(cx).is_ready() {
+                        return Poll::Ready(None);
+                    }
+
+                    request.as_mut().poll(cx).map(Some)
+                })
+                .await;
+
+                // The request was interrupted, send a new one.
+                let Some(result) = result else {
+                    continue;
+                };
+
+                match &result {
+                    Ok(response) => {
+                        since.set(Some(response.next_batch.clone()));
+                        retry_delay = None;
+                    }
+                    Err(_) => {
+                        retry_delay = Some(retry_delay.map_or(MIN_RETRY_DELAY, |delay: Duration| {
+                            (delay * 2).min(MAX_RETRY_DELAY)
+                        }));
+                    }
+                }
+
+                yield result;
+            }
+        }
+    }
+}
+
+/// Settings for [`Client::sync_stream()`](super::Client::sync_stream).
+///
+/// This type can be used to configure a sync stream through a few method calls.
+///
+/// Clones of this type have their own [`SyncHandle`] and [`SyncToken`], so they can be used to
+/// start independent sync streams.
+#[derive(Debug, Default)]
+pub struct SyncSettings {
+    pub(super) filter: Option<sync_events::v3::Filter>,
+    pub(super) handle: SyncHandle,
+    pub(super) since: SyncToken,
+    pub(super) set_presence: PresenceState,
+    pub(super) timeout: Option<Duration>,
+}
+
+impl SyncSettings {
+    /// Creates new default `SyncSettings`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the filter to apply to the sync responses.
+    ///
+    /// Once the stream is running, the filter can be changed with [`SyncHandle::set_filter()`].
+    pub fn filter(self, filter: sync_events::v3::Filter) -> Self {
+        Self { filter: Some(filter), ..self }
+    }
+
+    /// Set the token to start syncing from.
+    ///
+    /// If this is not set, the first request of the stream will be an initial sync.
+    pub fn since(self, since: String) -> Self {
+        self.since.set(Some(since));
+        self
+    }
+
+    /// Set the presence state to set on the user while syncing.
+    pub fn set_presence(self, set_presence: PresenceState) -> Self {
+        Self { set_presence, ..self }
+    }
+
+    /// Set the maximum time to wait for new events in each request.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout: Some(timeout), ..self }
+    }
+
+    /// Get a handle to the token the sync stream uses for its next request.
+    ///
+    /// The handle can be used to read or reset the token while the stream is running.
+    pub fn token(&self) -> SyncToken {
+        self.since.clone()
+    }
+
+    /// Get a handle to control the sync stream while it is running.
+    ///
+    /// The handle can be used to change the filter or to interrupt the current request of the
+    /// stream.
+    pub fn handle(&self) -> SyncHandle {
+        self.handle.clone()
+    }
+}
+
+impl Clone for SyncSettings {
+    fn clone(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            handle: SyncHandle::default(),
+            since: SyncToken(Arc::new(Mutex::new(self.since.get()))),
+            set_presence: self.set_presence.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// A handle to the `since` token of a sync stream.
+///
+/// Clones of this handle share the same token, but clones of the [`SyncSettings`] it was
+/// obtained from don't.
+#[derive(Clone, Debug, Default)]
+pub struct SyncToken(Arc<Mutex<Option<String>>>);
+
+impl SyncToken {
+    /// Get a copy of the current token, if any.
+    pub fn get(&self) -> Option<String> {
+        self.0.lock().expect("sync token mutex was poisoned").clone()
+    }
+
+    /// Replace the current token.
+    ///
+    /// Setting it to `None` makes the next request of the sync stream an initial sync.
+    pub fn set(&self, token: Option<String>) {
+        *self.0.lock().expect("sync token mutex was poisoned") = token;
+    }
+}
+
+/// A handle to control a running sync stream.
+///
+/// Clones of this handle control the same stream.
+///
+/// When the current request of the stream is interrupted, it is dropped and a new request is sent
+/// with the same `since` token. A response that was already received before the interruption is
+/// still returned by the stream, so one response may be delivered with the previous settings.
+///
+/// # Example:
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// # use ruma_client::SyncSettings;
+/// # use tokio_stream::{StreamExt as _};
+/// # let homeserver_url = "https://example.com".to_owned();
+/// # async {
+/// # let client = ruma_client::Client::builder()
+/// #     .homeserver_url(homeserver_url)
+/// #     .build::<ruma_client::http_client::Dummy>()
+/// #     .await?;
+/// # let new_filter = ruma_client_api::sync::sync_events::v3::Filter::FilterId("1".to_owned());
+/// let settings = SyncSettings::new().timeout(Duration::from_secs(30));
+/// let handle = settings.handle();
+///
+/// let mut sync_stream = Box::pin(client.sync_stream(settings));
+///
+/// // Later, from another task: restart the current long-poll with a new filter.
+/// handle.set_filter(Some(new_filter));
+///
+/// while let Some(result) = sync_stream.next().await {
+///     // Handle the response...
+/// }
+/// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+/// # };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SyncHandle(Arc<SyncHandleInner>);
+
+#[derive(Debug, Default)]
+struct SyncHandleInner {
+    /// The filter set with `set_filter()` that was not used by the stream yet.
+    new_filter: Mutex<Option<Option<sync_events::v3::Filter>>>,
+    interrupted: AtomicBool,
+    notify: Notify,
+}
+
+impl SyncHandle {
+    /// Replace the filter of the sync stream.
+    ///
+    /// The current request is interrupted, and the next one uses the new filter with the usual
+    /// timeout.
+    pub fn set_filter(&self, filter: Option<sync_events::v3::Filter>) {
+        *self.0.new_filter.lock().expect("sync filter mutex was poisoned") = Some(filter);
+        self.0.notify.notify_waiters();
+    }
+
+    /// Interrupt the current request of the sync stream.
+    ///
+    /// The next request uses a timeout of zero, so the server responds immediately. If no request
+    /// is in progress, this applies to the next request.
+    pub fn interrupt(&self) {
+        self.0.interrupted.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    /// Take the filter set with `set_filter()` since the last call to this method, if any.
+    pub(super) fn take_new_filter(&self) -> Option<Option<sync_events::v3::Filter>> {
+        self.0.new_filter.lock().expect("sync filter mutex was poisoned").take()
+    }
+
+    /// Whether `interrupt()` was called since the last call to this method.
+    pub(super) fn take_interrupted(&self) -> bool {
+        self.0.interrupted.swap(false, Ordering::SeqCst)
+    }
+
+    /// A future that resolves when the current request should be interrupted.
+    pub(super) fn notified(&self) -> Notified<'_> {
+        self.0.notify.notified()
+    }
+}
@@ -2,7 +2,12 @@
 
 use std::fmt::{self, Debug, Display, Formatter};
 
-use ruma_common::api::error::{FromHttpResponseError, IntoHttpError};
+#[cfg(feature = "client-api")]
+use as_variant::as_variant;
+use ruma_common::{
+    api::error::{FromHttpResponseError, IntoHttpError},
+    MxcUriError,
+};
 
 /// An error that can occur during client operations.
 #[derive(Debug)]
@@ -22,6 +27,12 @@ pub enum Error<E, F> {
 
     /// Converting the HTTP response to one of ruma's types failed.
     FromHttpResponse(FromHttpResponseError<F>),
+
+    /// No response was received before the timeout of the request elapsed.
+    Timeout,
+
+    /// The given MXC URI is invalid.
+    InvalidMxcUri(MxcUriError),
 }
 
 #[cfg(feature = "client-api")]
@@ -29,11 +40,38 @@ impl<E> Error<E, ruma_client_api::Error> {
     /// If `self` is a server error in the `errcode` + `error` format expected
     /// for client-server API endpoints, returns the error kind (`errcode`).
     pub fn error_kind(&self) -> Option<&ruma_client_api::error::ErrorKind> {
-        use as_variant::as_variant;
         use ruma_client_api::error::FromHttpResponseErrorExt as _;
 
         as_variant!(self, Self::FromHttpResponse)?.error_kind()
     }
+
+    /// Whether `self` is a server error indicating that the access token is unknown or expired.
+    ///
+    /// This is the case if the server responded with an `M_UNKNOWN_TOKEN` `errcode`, or with a
+    /// `401 Unauthorized` status but without a body in the standard format.
+    pub fn is_unknown_token(&self) -> bool {
+        use ruma_client_api::error::{ErrorBody, ErrorKind};
+
+        let Some(FromHttpResponseError::Server(error)) = as_variant!(self, Self::FromHttpResponse)
+        else {
+            return false;
+        };
+
+        match &error.body {
+            ErrorBody::Standard { kind, .. } => matches!(kind, ErrorKind::UnknownToken { .. }),
+            _ => error.status_code == http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    /// Whether `self` is an `M_UNKNOWN_TOKEN` error with `soft_logout` set to `true`.
+    ///
+    /// In this case, the client can acquire a new access token by logging in again with the same
+    /// device ID, without losing its encryption keys.
+    pub fn soft_logout(&self) -> bool {
+        use ruma_client_api::error::ErrorKind;
+
+        matches!(self.error_kind(), Some(ErrorKind::UnknownToken { soft_logout: true, .. }))
+    }
 }
 
 impl<E: Display, F: Display> Display for Error<E, F> {
@@ -46,6 +84,8 @@ impl<E: Display, F: Display> Display for Error<E, F> {
             Self::Url(err) => write!(f, "Invalid URL: {err}"),
             Self::Response(err) => write!(f, "Couldn't obtain a response: {err}"),
             Self::FromHttpResponse(err) => write!(f, "HTTP response conversion failed: {err}"),
+            Self::Timeout => write!(f, "The request timed out."),
+            Self::InvalidMxcUri(err) => write!(f, "Invalid MXC URI: {err}"),
         }
     }
 }
@@ -77,3 +117,67 @@ impl<E, F> From<FromHttpResponseError<F>> for Error<E, F> {
 }
 
 impl<E: Debug + Display, F: Debug + Display> std::error::Error for Error<E, F> {}
+
+#[cfg(all(test, feature = "client-api"))]
+mod tests {
+    use http::StatusCode;
+    use ruma_common::api::EndpointError;
+    use serde_json::{json, Value as JsonValue};
+
+    use super::{Error, FromHttpResponseError};
+
+    fn server_error(status: StatusCode, body: JsonValue) -> Error<(), ruma_client_api::Error> {
+        let response = http::Response::builder()
+            .status(status)
+            .body(serde_json::to_vec(&body).unwrap())
+            .unwrap();
+        Error::FromHttpResponse(FromHttpResponseError::Server(
+            ruma_client_api::Error::from_http_response(response),
+        ))
+    }
+
+    #[test]
+    fn unknown_token() {
+        let error = server_error(
+            StatusCode::UNAUTHORIZED,
+            json!({ "errcode": "M_UNKNOWN_TOKEN", "error": "Unknown token" }),
+        );
+        assert!(error.is_unknown_token());
+        assert!(!error.soft_logout());
+    }
+
+    #[test]
+    fn unknown_token_soft_logout() {
+        let error = server_error(
+            StatusCode::UNAUTHORIZED,
+            json!({ "errcode": "M_UNKNOWN_TOKEN", "error": "Soft logout", "soft_logout": true }),
+        );
+        assert!(error.is_unknown_token());
+        assert!(error.soft_logout());
+    }
+
+    #[test]
+    fn unauthorized_without_standard_body() {
+        let error = server_error(StatusCode::UNAUTHORIZED, json!({ "message": "Go away" }));
+        assert!(error.is_unknown_token());
+        assert!(!error.soft_logout());
+    }
+
+    #[test]
+    fn other_errors() {
+        let error = server_error(
+            StatusCode::UNAUTHORIZED,
+            json!({ "errcode": "M_MISSING_TOKEN", "error": "Missing token" }),
+        );
+        assert!(!error.is_unknown_token());
+        assert!(!error.soft_logout());
+
+        let error = server_error(StatusCode::FORBIDDEN, json!({ "message": "Forbidden" }));
+        assert!(!error.is_unknown_token());
+        assert!(!error.soft_logout());
+
+        let error = Error::<(), ruma_client_api::Error>::Timeout;
+        assert!(!error.is_unknown_token());
+        assert!(!error.soft_logout());
+    }
+}
@@ -1,13 +1,14 @@
 //! This module contains an abstraction for HTTP clients as well as friendly-named re-exports of
 //! client types that implement this trait.
 
-use std::{future::Future, pin::Pin};
+use std::{any::type_name, future::Future, pin::Pin, time::Duration};
 
 use bytes::BufMut;
 use ruma_common::{
     api::{MatrixVersion, OutgoingRequest, SendAccessToken},
     UserId,
 };
+use tracing::warn;
 
 use crate::{add_user_id_to_query, ResponseError, ResponseResult};
 
@@ -47,6 +48,51 @@ pub trait HttpClient: Sync {
 pub trait DefaultConstructibleHttpClient: HttpClient {
     /// Creates a new HTTP client with default configuration.
     fn default() -> Self;
+
+    /// Creates a new HTTP client with default configuration, tuned with the given settings.
+    ///
+    /// The default implementation falls back to [`default()`][Self::default], ignoring the
+    /// settings. A warning is logged if any of the settings differs from its default value.
+    fn with_settings(settings: &HttpClientSettings) -> Self
+    where
+        Self: Sized,
+    {
+        if *settings != HttpClientSettings::default() {
+            warn!(
+                http_client = type_name::<Self>(),
+                "The HTTP client doesn't support custom settings, they are ignored"
+            );
+        }
+
+        Self::default()
+    }
+}
+
+/// Settings for the connections of an HTTP client constructed with
+/// [`DefaultConstructibleHttpClient::with_settings()`].
+///
+/// The settings that are not set use the default value of the HTTP client.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HttpClientSettings {
+    /// The maximum number of idle connections per host in the connection pool.
+    pub pool_max_idle_per_host: Option<usize>,
+
+    /// The duration after which an idle connection in the connection pool is closed.
+    pub pool_idle_timeout: Option<Duration>,
+
+    /// The timeout for establishing a connection.
+    pub connect_timeout: Option<Duration>,
+
+    /// Whether to only use HTTP/2, without negotiating the HTTP version with the server.
+    pub http2_prior_knowledge: bool,
+}
+
+impl HttpClientSettings {
+    /// Creates a new `HttpClientSettings` with the default values of the HTTP client.
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 /// Convenience functionality on top of `HttpClient`.
@@ -94,6 +140,7 @@ pub trait HttpClientExt: HttpClient {
             for_versions,
             request,
             customize,
+            &[],
         ))
     }
 
@@ -5,7 +5,7 @@ use hyper_util::{
     rt::TokioExecutor,
 };
 
-use super::{DefaultConstructibleHttpClient, HttpClient};
+use super::{DefaultConstructibleHttpClient, HttpClient, HttpClientSettings};
 
 /// A hyper HTTP client.
 ///
@@ -51,6 +51,10 @@ impl DefaultConstructibleHttpClient for Hyper {
         hyper_util::client::legacy::Client::builder(TokioExecutor::new())
             .build(HttpConnector::new())
     }
+
+    fn with_settings(settings: &HttpClientSettings) -> Self {
+        client_builder(settings).build(http_connector(settings))
+    }
 }
 
 #[cfg(feature = "hyper-native-tls")]
@@ -59,4 +63,34 @@ impl DefaultConstructibleHttpClient for HyperNativeTls {
         hyper_util::client::legacy::Client::builder(TokioExecutor::new())
             .build(hyper_tls::HttpsConnector::new())
     }
+
+    fn with_settings(settings: &HttpClientSettings) -> Self {
+        let mut http_connector = http_connector(settings);
+        http_connector.enforce_http(false);
+
+        client_builder(settings)
+            .build(hyper_tls::HttpsConnector::new_with_connector(http_connector))
+    }
+}
+
+/// Creates a client builder with the given settings.
+fn client_builder(settings: &HttpClientSettings) -> hyper_util::client::legacy::Builder {
+    let mut builder = hyper_util::client::legacy::Client::builder(TokioExecutor::new());
+
+    if let Some(max_idle) = settings.pool_max_idle_per_host {
+        builder.pool_max_idle_per_host(max_idle);
+    }
+    if let Some(timeout) = settings.pool_idle_timeout {
+        builder.pool_idle_timeout(timeout);
+    }
+    builder.http2_only(settings.http2_prior_knowledge);
+
+    builder
+}
+
+/// Creates an HTTP connector with the given settings.
+fn http_connector(settings: &HttpClientSettings) -> HttpConnector {
+    let mut connector = HttpConnector::new();
+    connector.set_connect_timeout(settings.connect_timeout);
+    connector
 }
@@ -2,7 +2,7 @@ use std::mem;
 
 use bytes::{Bytes, BytesMut};
 
-use super::{DefaultConstructibleHttpClient, HttpClient};
+use super::{DefaultConstructibleHttpClient, HttpClient, HttpClientSettings};
 
 /// The `reqwest` crate's `Client`.
 pub type Reqwest = reqwest::Client;
@@ -34,4 +34,23 @@ impl DefaultConstructibleHttpClient for Reqwest {
     fn default() -> Self {
         reqwest::Client::new()
     }
+
+    fn with_settings(settings: &HttpClientSettings) -> Self {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(max_idle) = settings.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(timeout) = settings.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(timeout) = settings.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if settings.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        builder.build().expect("reqwest client with valid settings should build")
+    }
 }
@@ -80,6 +80,10 @@
 //!
 //! # Crate features
 //!
+//! The `tokio` feature activates the methods that need the timer of the tokio runtime, like
+//! `Client::send_request_with_timeout()` and `Client::sync_stream()`. They panic if they are not
+//! called from within a tokio runtime.
+//!
 //! The following features activate http client types in the [`http_client`] module:
 //!
 //! * `hyper`
@@ -113,10 +117,12 @@ mod error;
 pub mod http_client;
 
 #[cfg(feature = "client-api")]
-pub use self::client::{Client, ClientBuilder};
+pub use self::client::{discover_homeserver, Client, ClientBuilder, DiscoveryError};
+#[cfg(all(feature = "client-api", feature = "tokio"))]
+pub use self::client::{SyncHandle, SyncSettings, SyncToken};
 pub use self::{
     error::Error,
-    http_client::{DefaultConstructibleHttpClient, HttpClient, HttpClientExt},
+    http_client::{DefaultConstructibleHttpClient, HttpClient, HttpClientExt, HttpClientSettings},
 };
 
 /// The error type for sending the request `R` with the http client `C`.
@@ -127,6 +133,9 @@ pub type ResponseError<C, R> =
 pub type ResponseResult<C, R> =
     Result<<R as OutgoingRequest>::IncomingResponse, ResponseError<C, R>>;
 
+/// A function called with the parts of every HTTP response received by a [`Client`].
+type ResponseHook = Box<dyn Fn(&http::response::Parts) + Send + Sync>;
+
 fn send_customized_request<'a, C, R, F>(
     http_client: &'a C,
     homeserver_url: &str,
@@ -134,7 +143,79 @@ fn send_customized_request<'a, C, R, F>(
     for_versions: &[MatrixVersion],
     request: R,
     customize: F,
+    response_hooks: &'a [ResponseHook],
 ) -> impl Future<Output = ResponseResult<C, R>> + Send + 'a
+where
+    C: HttpClient + ?Sized,
+    R: OutgoingRequest,
+    F: FnOnce(&mut http::Request<C::RequestBody>) -> Result<(), ResponseError<C, R>>,
+{
+    let fut = send_customized_http_request(
+        http_client,
+        homeserver_url,
+        send_access_token,
+        for_versions,
+        request,
+        customize,
+        response_hooks,
+    );
+
+    async move { deserialize_response::<C, R>(fut.await?) }
+}
+
+/// Like [`send_customized_request`], but also returns the parts of the HTTP response.
+#[cfg(feature = "client-api")]
+fn send_customized_request_with_parts<'a, C, R, F>(
+    http_client: &'a C,
+    homeserver_url: &str,
+    send_access_token: SendAccessToken<'_>,
+    for_versions: &[MatrixVersion],
+    request: R,
+    customize: F,
+    response_hooks: &'a [ResponseHook],
+) -> impl Future<Output = Result<(R::IncomingResponse, http::response::Parts), ResponseError<C, R>>>
+       + Send
+       + 'a
+where
+    C: HttpClient + ?Sized,
+    R: OutgoingRequest,
+    F: FnOnce(&mut http::Request<C::RequestBody>) -> Result<(), ResponseError<C, R>>,
+{
+    let fut = send_customized_http_request(
+        http_client,
+        homeserver_url,
+        send_access_token,
+        for_versions,
+        request,
+        customize,
+        response_hooks,
+    );
+
+    async move {
+        let (parts, body) = fut.await?.into_parts();
+
+        // `Parts` can't be cloned because of its extensions, so only the data needed for
+        // deserialization is copied.
+        let mut http_res = http::Response::new(body);
+        *http_res.status_mut() = parts.status;
+        *http_res.version_mut() = parts.version;
+        http_res.headers_mut().clone_from(&parts.headers);
+
+        Ok((deserialize_response::<C, R>(http_res)?, parts))
+    }
+}
+
+/// Serialize, customize and send the given request, and call the response hooks with the HTTP
+/// response.
+fn send_customized_http_request<'a, C, R, F>(
+    http_client: &'a C,
+    homeserver_url: &str,
+    send_access_token: SendAccessToken<'_>,
+    for_versions: &[MatrixVersion],
+    request: R,
+    customize: F,
+    response_hooks: &'a [ResponseHook],
+) -> impl Future<Output = Result<http::Response<C::ResponseBody>, ResponseError<C, R>>> + Send + 'a
 where
     C: HttpClient + ?Sized,
     R: OutgoingRequest,
@@ -165,16 +246,30 @@ where
             .await
             .map_err(Error::Response)?;
 
-        let res =
-            info_span!("deserialize_response", response_type = type_name::<R::IncomingResponse>())
-                .in_scope(move || {
-                    ruma_common::api::IncomingResponse::try_from_http_response(http_res)
-                })?;
+        if response_hooks.is_empty() {
+            return Ok(http_res);
+        }
+
+        let (parts, body) = http_res.into_parts();
+        for hook in response_hooks {
+            hook(&parts);
+        }
 
-        Ok(res)
+        Ok(http::Response::from_parts(parts, body))
     }
 }
 
+/// Deserialize the given HTTP response for the request `R`.
+fn deserialize_response<C, R>(http_res: http::Response<C::ResponseBody>) -> ResponseResult<C, R>
+where
+    C: HttpClient + ?Sized,
+    R: OutgoingRequest,
+{
+    info_span!("deserialize_response", response_type = type_name::<R::IncomingResponse>())
+        .in_scope(move || ruma_common::api::IncomingResponse::try_from_http_response(http_res))
+        .map_err(Into::into)
+}
+
 fn add_user_id_to_query<C: HttpClient + ?Sized, R: OutgoingRequest>(
     user_id: &UserId,
 ) -> impl FnOnce(&mut http::Request<C::RequestBody>) -> Result<(), ResponseError<C, R>> + '_ {
@@ -0,0 +1,60 @@
+use http::{header::RETRY_AFTER, StatusCode};
+use ruma_client_api::account::whoami;
+use ruma_common::{api::MatrixVersion, device_id, user_id};
+use serde_json::json;
+
+use crate::mock::{MockClient, MockResponse};
+
+#[tokio::test]
+async fn send_raw_request_returns_response_headers() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    mock.push_response(MockResponse::Response(
+        http::Response::builder()
+            .status(StatusCode::OK)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(RETRY_AFTER, "120")
+            .body(br#"{ "user_id": "@alice:matrix.local" }"#.to_vec())
+            .unwrap(),
+    ));
+    let (response, parts) = client.send_raw_request(whoami::v3::Request::new()).await.unwrap();
+
+    assert_eq!(response.user_id, "@alice:matrix.local");
+    assert_eq!(parts.status, StatusCode::OK);
+    assert_eq!(parts.headers.get(RETRY_AFTER).unwrap(), "120");
+}
+
+#[tokio::test]
+async fn whoami_updates_session() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+    assert_eq!(client.user_id(), None);
+    assert_eq!(client.device_id(), None);
+
+    mock.push_response(MockResponse::ok(json!({
+        "user_id": "@alice:matrix.local",
+        "device_id": "ABCDEF",
+    })));
+    let response = client.whoami().await.unwrap();
+    assert_eq!(response.user_id, "@alice:matrix.local");
+
+    assert_eq!(client.user_id().as_deref(), Some(user_id!("@alice:matrix.local")));
+    assert_eq!(client.device_id().as_deref(), Some(device_id!("ABCDEF")));
+
+    // A response without device ID clears it.
+    mock.push_response(MockResponse::ok(json!({ "user_id": "@bob:matrix.local" })));
+    client.whoami().await.unwrap();
+
+    assert_eq!(client.user_id().as_deref(), Some(user_id!("@bob:matrix.local")));
+    assert_eq!(client.device_id(), None);
+
+    // The session is not changed by an error.
+    mock.push_response(MockResponse::json(
+        StatusCode::UNAUTHORIZED,
+        json!({ "errcode": "M_UNKNOWN_TOKEN", "error": "Unknown token" }),
+    ));
+    client.whoami().await.unwrap_err();
+
+    assert_eq!(client.user_id().as_deref(), Some(user_id!("@bob:matrix.local")));
+}
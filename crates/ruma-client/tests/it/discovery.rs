@@ -0,0 +1,59 @@
+use assert_matches2::assert_matches;
+use http::StatusCode;
+use ruma_client::{discover_homeserver, DiscoveryError};
+use ruma_common::server_name;
+use serde_json::json;
+
+use crate::mock::{MockClient, MockResponse};
+
+#[tokio::test]
+async fn discover_homeserver_from_well_known() {
+    let mock = MockClient::new();
+    mock.push_response(MockResponse::ok(json!({
+        "m.homeserver": { "base_url": "https://matrix.example.org/" },
+    })));
+
+    let url = discover_homeserver(&mock, server_name!("example.org")).await.unwrap();
+    // The trailing slash is trimmed.
+    assert_eq!(url, "https://matrix.example.org");
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].uri(), "https://example.org/.well-known/matrix/client");
+}
+
+#[tokio::test]
+async fn discover_homeserver_not_found() {
+    let mock = MockClient::new();
+    mock.push_response(MockResponse::Response(
+        http::Response::builder().status(StatusCode::NOT_FOUND).body(Vec::new()).unwrap(),
+    ));
+
+    // The server name is used as the homeserver.
+    let url = discover_homeserver(&mock, server_name!("example.org")).await.unwrap();
+    assert_eq!(url, "https://example.org");
+}
+
+#[tokio::test]
+async fn discover_homeserver_invalid_base_url() {
+    let mock = MockClient::new();
+    mock.push_response(MockResponse::ok(json!({
+        "m.homeserver": { "base_url": "matrix.example.org" },
+    })));
+
+    let error = discover_homeserver(&mock, server_name!("example.org")).await.unwrap_err();
+    assert_matches!(error, DiscoveryError::InvalidBaseUrl(base_url));
+    assert_eq!(base_url, "matrix.example.org");
+}
+
+#[tokio::test]
+async fn discover_homeserver_server_error() {
+    let mock = MockClient::new();
+    mock.push_response(MockResponse::json(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        json!({ "errcode": "M_UNKNOWN", "error": "Oops" }),
+    ));
+
+    let error = discover_homeserver(&mock, server_name!("example.org")).await.unwrap_err();
+    assert_matches!(error, DiscoveryError::Request(_));
+}
@@ -0,0 +1,68 @@
+use std::sync::{Arc, Mutex};
+
+use http::{
+    header::{HeaderValue, USER_AGENT},
+    StatusCode,
+};
+use ruma_client::Client;
+use ruma_client_api::account::whoami;
+use ruma_common::api::MatrixVersion;
+use serde_json::json;
+
+use crate::mock::{MockClient, MockResponse};
+
+#[tokio::test]
+async fn request_hook_sets_header() {
+    let mock = MockClient::new();
+    let client = Client::builder()
+        .homeserver_url("https://matrix.local".to_owned())
+        .access_token(Some("secret".to_owned()))
+        .supported_matrix_versions(vec![MatrixVersion::V1_0])
+        .request_hook(|parts| {
+            parts.headers.insert(USER_AGENT, HeaderValue::from_static("ruma-test/1.0"));
+        })
+        .http_client(mock.clone())
+        .await
+        .unwrap();
+
+    mock.push_response(MockResponse::ok(json!({ "user_id": "@alice:matrix.local" })));
+    client.send_request(whoami::v3::Request::new()).await.unwrap();
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.headers().get(USER_AGENT).unwrap(), "ruma-test/1.0");
+    // The rest of the request is untouched.
+    assert_eq!(request.uri().path(), "/_matrix/client/r0/account/whoami");
+    assert!(request.headers().contains_key(http::header::AUTHORIZATION));
+}
+
+#[tokio::test]
+async fn response_hook_sees_status() {
+    let statuses = Arc::new(Mutex::new(Vec::new()));
+
+    let mock = MockClient::new();
+    // The hook is also called for the response of the supported versions request.
+    mock.push_response(MockResponse::ok(json!({ "versions": ["v1.1"] })));
+    let client = Client::builder()
+        .homeserver_url("https://matrix.local".to_owned())
+        .access_token(Some("secret".to_owned()))
+        .response_hook({
+            let statuses = statuses.clone();
+            move |parts| statuses.lock().unwrap().push(parts.status)
+        })
+        .http_client(mock.clone())
+        .await
+        .unwrap();
+
+    mock.push_response(MockResponse::ok(json!({ "user_id": "@alice:matrix.local" })));
+    client.send_request(whoami::v3::Request::new()).await.unwrap();
+
+    mock.push_response(MockResponse::json(
+        StatusCode::FORBIDDEN,
+        json!({ "errcode": "M_FORBIDDEN", "error": "Nope" }),
+    ));
+    client.send_request(whoami::v3::Request::new()).await.unwrap_err();
+
+    assert_eq!(*statuses.lock().unwrap(), [StatusCode::OK, StatusCode::OK, StatusCode::FORBIDDEN]);
+}
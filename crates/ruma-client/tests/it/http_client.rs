@@ -0,0 +1,113 @@
+use std::{sync::Mutex, time::Duration};
+
+use ruma_client::{Client, DefaultConstructibleHttpClient, HttpClient, HttpClientSettings};
+use ruma_common::api::MatrixVersion;
+
+/// An HTTP client that only implements `default()`.
+#[derive(Debug)]
+struct DefaultOnlyClient {
+    from_default: bool,
+}
+
+impl HttpClient for DefaultOnlyClient {
+    type RequestBody = Vec<u8>;
+    type ResponseBody = Vec<u8>;
+    type Error = ();
+
+    async fn send_http_request(
+        &self,
+        _req: http::Request<Self::RequestBody>,
+    ) -> Result<http::Response<Self::ResponseBody>, Self::Error> {
+        Err(())
+    }
+}
+
+impl DefaultConstructibleHttpClient for DefaultOnlyClient {
+    fn default() -> Self {
+        Self { from_default: true }
+    }
+}
+
+/// An HTTP client that supports settings.
+#[derive(Debug)]
+struct SettingsClient;
+
+/// The settings that `SettingsClient` was constructed with.
+static SETTINGS: Mutex<Option<HttpClientSettings>> = Mutex::new(None);
+
+impl HttpClient for SettingsClient {
+    type RequestBody = Vec<u8>;
+    type ResponseBody = Vec<u8>;
+    type Error = ();
+
+    async fn send_http_request(
+        &self,
+        _req: http::Request<Self::RequestBody>,
+    ) -> Result<http::Response<Self::ResponseBody>, Self::Error> {
+        Err(())
+    }
+}
+
+impl DefaultConstructibleHttpClient for SettingsClient {
+    fn default() -> Self {
+        Self::with_settings(&HttpClientSettings::new())
+    }
+
+    fn with_settings(settings: &HttpClientSettings) -> Self {
+        *SETTINGS.lock().unwrap() = Some(settings.clone());
+        Self
+    }
+}
+
+#[test]
+fn with_settings_falls_back_to_default() {
+    let mut settings = HttpClientSettings::new();
+    settings.connect_timeout = Some(Duration::from_secs(5));
+    settings.http2_prior_knowledge = true;
+
+    assert!(DefaultOnlyClient::with_settings(&settings).from_default);
+    assert!(DefaultOnlyClient::with_settings(&HttpClientSettings::new()).from_default);
+}
+
+#[tokio::test]
+async fn builder_forwards_settings() {
+    Client::builder()
+        .homeserver_url("https://matrix.local".to_owned())
+        .supported_matrix_versions(vec![MatrixVersion::V1_0])
+        .pool_max_idle_per_host(2)
+        .pool_idle_timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(5))
+        .http2_prior_knowledge(true)
+        .build::<SettingsClient>()
+        .await
+        .unwrap();
+
+    let mut expected = HttpClientSettings::new();
+    expected.pool_max_idle_per_host = Some(2);
+    expected.pool_idle_timeout = Some(Duration::from_secs(30));
+    expected.connect_timeout = Some(Duration::from_secs(5));
+    expected.http2_prior_knowledge = true;
+    assert_eq!(SETTINGS.lock().unwrap().take(), Some(expected));
+}
+
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+fn custom_settings() -> HttpClientSettings {
+    let mut settings = HttpClientSettings::new();
+    settings.pool_max_idle_per_host = Some(2);
+    settings.pool_idle_timeout = Some(Duration::from_secs(30));
+    settings.connect_timeout = Some(Duration::from_secs(5));
+    settings.http2_prior_knowledge = true;
+    settings
+}
+
+#[cfg(feature = "hyper")]
+#[test]
+fn hyper_with_settings() {
+    <ruma_client::http_client::Hyper>::with_settings(&custom_settings());
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn reqwest_with_settings() {
+    ruma_client::http_client::Reqwest::with_settings(&custom_settings());
+}
@@ -0,0 +1,10 @@
+#![cfg(feature = "client-api")]
+
+mod client;
+mod discovery;
+mod hooks;
+mod http_client;
+mod media;
+mod mock;
+#[cfg(feature = "tokio")]
+mod sync;
@@ -0,0 +1,79 @@
+use assert_matches2::assert_matches;
+use http::header::CONTENT_TYPE;
+use ruma_client::Error;
+use ruma_common::{api::MatrixVersion, mxc_uri, MxcUri};
+use serde_json::json;
+
+use crate::mock::{MockClient, MockResponse};
+
+fn file_response() -> MockResponse {
+    MockResponse::Response(
+        http::Response::builder()
+            .header(CONTENT_TYPE, "text/plain")
+            .body(b"hello".to_vec())
+            .unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn upload_media() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_11]).await;
+
+    mock.push_response(MockResponse::ok(json!({ "content_uri": "mxc://matrix.local/abcd" })));
+    let mxc = client.upload_media("text/plain", b"hello".to_vec()).await.unwrap();
+    assert_eq!(mxc, "mxc://matrix.local/abcd");
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method(), http::Method::POST);
+    assert_eq!(request.uri().path(), "/_matrix/media/v3/upload");
+    assert_eq!(request.headers().get(CONTENT_TYPE).unwrap(), "text/plain");
+    assert_eq!(request.body(), b"hello");
+}
+
+#[tokio::test]
+async fn download_media_authenticated() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_10, MatrixVersion::V1_11]).await;
+
+    mock.push_response(file_response());
+    let (data, content_type) =
+        client.download_media(mxc_uri!("mxc://matrix.local/abcd")).await.unwrap();
+    assert_eq!(data, b"hello");
+    assert_eq!(content_type.as_deref(), Some("text/plain"));
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].uri().path(), "/_matrix/client/v1/media/download/matrix.local/abcd");
+}
+
+#[tokio::test]
+async fn download_media_legacy() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_9, MatrixVersion::V1_10]).await;
+
+    mock.push_response(file_response());
+    let (data, content_type) =
+        client.download_media(mxc_uri!("mxc://matrix.local/abcd")).await.unwrap();
+    assert_eq!(data, b"hello");
+    assert_eq!(content_type.as_deref(), Some("text/plain"));
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].uri().path(), "/_matrix/media/v3/download/matrix.local/abcd");
+}
+
+#[tokio::test]
+async fn download_media_invalid_mxc_uri() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_11]).await;
+
+    let error =
+        client.download_media(<&MxcUri>::from("https://matrix.local/abcd")).await.unwrap_err();
+    assert_matches!(error, Error::InvalidMxcUri(_));
+
+    // No request was sent.
+    assert!(mock.take_requests().is_empty());
+}
@@ -0,0 +1,115 @@
+//! An HTTP client that returns predefined responses, to test the `Client` without a homeserver.
+
+use std::{
+    collections::VecDeque,
+    future::{pending, Future},
+    sync::{Arc, Mutex},
+};
+
+use ruma_client::{Client, HttpClient};
+use ruma_common::api::MatrixVersion;
+use serde_json::Value as JsonValue;
+
+/// A response of the [`MockClient`].
+#[derive(Debug)]
+pub(crate) enum MockResponse {
+    /// Respond with the given HTTP response.
+    Response(http::Response<Vec<u8>>),
+
+    /// Fail with an error.
+    Error,
+
+    /// Never respond.
+    Pending,
+}
+
+impl MockResponse {
+    /// A response with the given status code and JSON body.
+    pub(crate) fn json(status: http::StatusCode, body: JsonValue) -> Self {
+        Self::Response(
+            http::Response::builder()
+                .status(status)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(serde_json::to_vec(&body).unwrap())
+                .unwrap(),
+        )
+    }
+
+    /// A `200 OK` response with the given JSON body.
+    pub(crate) fn ok(body: JsonValue) -> Self {
+        Self::json(http::StatusCode::OK, body)
+    }
+}
+
+/// An HTTP client that records the requests it sends, and responds with queued responses.
+///
+/// Clones of this client share the same requests and responses.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MockClient(Arc<Mutex<MockClientInner>>);
+
+#[derive(Debug, Default)]
+struct MockClientInner {
+    requests: Vec<http::Request<Vec<u8>>>,
+    responses: VecDeque<MockResponse>,
+}
+
+impl MockClient {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response to a future request.
+    pub(crate) fn push_response(&self, response: MockResponse) {
+        self.0.lock().unwrap().responses.push_back(response);
+    }
+
+    /// Take the requests that were sent until now.
+    pub(crate) fn take_requests(&self) -> Vec<http::Request<Vec<u8>>> {
+        std::mem::take(&mut self.0.lock().unwrap().requests)
+    }
+
+    /// Build a logged in `Client` using this HTTP client, that supports the given Matrix versions.
+    pub(crate) async fn client(&self, versions: Vec<MatrixVersion>) -> Client<Self> {
+        Client::builder()
+            .homeserver_url("https://matrix.local".to_owned())
+            .access_token(Some("secret".to_owned()))
+            .supported_matrix_versions(versions)
+            .http_client(self.clone())
+            .await
+            .unwrap()
+    }
+}
+
+impl HttpClient for MockClient {
+    type RequestBody = Vec<u8>;
+    type ResponseBody = Vec<u8>;
+    type Error = ();
+
+    fn send_http_request(
+        &self,
+        req: http::Request<Self::RequestBody>,
+    ) -> impl Future<Output = Result<http::Response<Self::ResponseBody>, Self::Error>> + Send {
+        let response = {
+            let mut inner = self.0.lock().unwrap();
+            inner.requests.push(req);
+            inner.responses.pop_front().expect("no response queued for the request")
+        };
+
+        async move {
+            match response {
+                MockResponse::Response(response) => Ok(response),
+                MockResponse::Error => Err(()),
+                MockResponse::Pending => pending().await,
+            }
+        }
+    }
+}
+
+/// Get the value of the query parameter with the given name in the URI of the given request.
+pub(crate) fn query_param(request: &http::Request<Vec<u8>>, name: &str) -> Option<String> {
+    let query = request.uri().query()?;
+    serde_html_form::from_str::<Vec<(String, String)>>(query)
+        .unwrap()
+        .into_iter()
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use ruma_client::SyncSettings;
+use ruma_client_api::sync::sync_events::v3::Filter;
+use ruma_common::api::MatrixVersion;
+use serde_json::json;
+use tokio::{task::yield_now, time::Instant};
+use tokio_stream::StreamExt as _;
+
+use crate::mock::{query_param, MockClient, MockResponse};
+
+#[tokio::test]
+async fn sync_stream_uses_filter_of_settings() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    let settings = SyncSettings::new().filter(Filter::FilterId("1".to_owned()));
+    // Setting the filter on a clone doesn't affect the original settings.
+    let _other_settings = settings.clone().filter(Filter::FilterId("2".to_owned()));
+
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s1" })));
+    let mut sync_stream = Box::pin(client.sync_stream(settings));
+    sync_stream.next().await.unwrap().unwrap();
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(query_param(&requests[0], "filter").as_deref(), Some("1"));
+}
+
+#[tokio::test]
+async fn sync_handle_set_filter() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    let settings = SyncSettings::new()
+        .filter(Filter::FilterId("1".to_owned()))
+        .timeout(Duration::from_secs(30));
+    let handle = settings.handle();
+
+    mock.push_response(MockResponse::Pending);
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s1" })));
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s2" })));
+    let mut sync_stream = Box::pin(client.sync_stream(settings));
+
+    // The pending request is interrupted and sent again with the new filter.
+    let (response, ()) = tokio::join!(sync_stream.next(), async {
+        yield_now().await;
+        handle.set_filter(Some(Filter::FilterId("2".to_owned())));
+    });
+    assert_eq!(response.unwrap().unwrap().next_batch, "s1");
+
+    // The new filter is kept for the next requests.
+    sync_stream.next().await.unwrap().unwrap();
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(query_param(&requests[0], "filter").as_deref(), Some("1"));
+    assert_eq!(query_param(&requests[1], "filter").as_deref(), Some("2"));
+    assert_eq!(query_param(&requests[1], "timeout").as_deref(), Some("30000"));
+    assert_eq!(query_param(&requests[2], "filter").as_deref(), Some("2"));
+    assert_eq!(query_param(&requests[2], "since").as_deref(), Some("s1"));
+}
+
+#[tokio::test]
+async fn sync_handle_interrupt() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    let settings = SyncSettings::new().since("s0".to_owned()).timeout(Duration::from_secs(30));
+    let handle = settings.handle();
+
+    mock.push_response(MockResponse::Pending);
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s1" })));
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s2" })));
+    let mut sync_stream = Box::pin(client.sync_stream(settings));
+
+    // The pending request is interrupted and sent again with a timeout of zero.
+    let (response, ()) = tokio::join!(sync_stream.next(), async {
+        yield_now().await;
+        handle.interrupt();
+    });
+    assert_eq!(response.unwrap().unwrap().next_batch, "s1");
+
+    // The following request uses the usual timeout again.
+    sync_stream.next().await.unwrap().unwrap();
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(query_param(&requests[0], "timeout").as_deref(), Some("30000"));
+    assert_eq!(query_param(&requests[1], "timeout").as_deref(), Some("0"));
+    assert_eq!(query_param(&requests[1], "since").as_deref(), Some("s0"));
+    assert_eq!(query_param(&requests[2], "timeout").as_deref(), Some("30000"));
+    assert_eq!(query_param(&requests[2], "since").as_deref(), Some("s1"));
+}
+
+#[tokio::test]
+async fn sync_handle_interrupt_before_request() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    let settings = SyncSettings::new().timeout(Duration::from_secs(30));
+    settings.handle().interrupt();
+
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s1" })));
+    let mut sync_stream = Box::pin(client.sync_stream(settings));
+    sync_stream.next().await.unwrap().unwrap();
+
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(query_param(&requests[0], "timeout").as_deref(), Some("0"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn sync_stream_retries_with_backoff() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    let settings = SyncSettings::new().since("s0".to_owned());
+    let token = settings.token();
+
+    mock.push_response(MockResponse::Error);
+    mock.push_response(MockResponse::Error);
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s1" })));
+    mock.push_response(MockResponse::Error);
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s2" })));
+    let mut sync_stream = Box::pin(client.sync_stream(settings));
+
+    let start = Instant::now();
+    sync_stream.next().await.unwrap().unwrap_err();
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    // The delay doubles with consecutive failures.
+    let start = Instant::now();
+    sync_stream.next().await.unwrap().unwrap_err();
+    assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+    let start = Instant::now();
+    sync_stream.next().await.unwrap().unwrap();
+    assert_eq!(start.elapsed(), Duration::from_secs(2));
+    assert_eq!(token.get().as_deref(), Some("s1"));
+
+    // A success resets the delay.
+    let start = Instant::now();
+    sync_stream.next().await.unwrap().unwrap_err();
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    let start = Instant::now();
+    sync_stream.next().await.unwrap().unwrap();
+    assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+    // Failed requests are retried with the same token.
+    let requests = mock.take_requests();
+    assert_eq!(requests.len(), 5);
+    assert_eq!(query_param(&requests[0], "since").as_deref(), Some("s0"));
+    assert_eq!(query_param(&requests[1], "since").as_deref(), Some("s0"));
+    assert_eq!(query_param(&requests[2], "since").as_deref(), Some("s0"));
+    assert_eq!(query_param(&requests[3], "since").as_deref(), Some("s1"));
+    assert_eq!(query_param(&requests[4], "since").as_deref(), Some("s1"));
+}
+
+#[tokio::test]
+async fn sync_settings_clones_have_own_token() {
+    let mock = MockClient::new();
+    let client = mock.client(vec![MatrixVersion::V1_0]).await;
+
+    let settings = SyncSettings::new().since("s0".to_owned());
+    let token = settings.token();
+    let other_settings = settings.clone();
+    let other_token = other_settings.token();
+
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "s1" })));
+    mock.push_response(MockResponse::ok(json!({ "next_batch": "t1" })));
+    let mut sync_stream = Box::pin(client.sync_stream(settings));
+    let mut other_sync_stream = Box::pin(client.sync_stream(other_settings));
+
+    sync_stream.next().await.unwrap().unwrap();
+    other_sync_stream.next().await.unwrap().unwrap();
+    assert_eq!(token.get().as_deref(), Some("s1"));
+    assert_eq!(other_token.get().as_deref(), Some("t1"));
+
+    let requests = mock.take_requests();
+    assert_eq!(query_param(&requests[0], "since").as_deref(), Some("s0"));
+    assert_eq!(query_param(&requests[1], "since").as_deref(), Some("s0"));
+}
@@ -114,6 +114,11 @@ macro_rules! metadata {
 /// `.cargo/config.toml` (under `[build]` -> `rustflags = ["..."]`). When that setting is
 /// activated, the attribute is not applied so the type is exhaustive.
 ///
+/// The `Request` type also gets a `path_spec()` const function, that returns the
+/// [`PathSegment`]s of the canonical path of the endpoint, as returned by
+/// [`VersionHistory::canonical_path()`]. It can be used by server frameworks to build matching
+/// routes.
+///
 /// ## Attributes
 ///
 /// To declare which part of the request a field belongs to:
@@ -129,12 +134,15 @@ macro_rules! metadata {
 ///   component of the request URL. If there are multiple of these fields, the order in which
 ///   they are declared must match the order in which they occur in the request path.
 /// * `#[ruma_api(query)]`: Fields with this attribute will be inserting into the URL's query
-///   string.
+///   string. Like for body fields, serde attributes such as `#[serde(rename = "...")]` are
+///   honored, so the name of the query parameter can differ from the name of the field.
 /// * `#[ruma_api(query_all)]`: Instead of individual query fields, one query_all field, of any
 ///   type that can be (de)serialized by [serde_html_form], can be used for cases where
 ///   multiple endpoints should share a query fields type, the query fields are better
 ///   expressed as an `enum` rather than a `struct`, or the endpoint supports arbitrary query
-///   parameters.
+///   parameters. For repeated parameters, a sequence of key / value pairs like `Vec<(String,
+///   OwnedRoomId)>` can be used, where the values are of any type that can be (de)serialized
+///   by [serde_html_form].
 /// * No attribute: Fields without an attribute are part of the body. They can use `#[serde]`
 ///   attributes to customize (de)serialization.
 /// * `#[ruma_api(body)]`: Use this if multiple endpoints should share a request body type, or
@@ -329,7 +337,11 @@ pub use ruma_macros::response;
 pub mod error;
 mod metadata;
 
-pub use self::metadata::{MatrixVersion, Metadata, VersionHistory, VersioningDecision};
+pub use self::metadata::{
+    MatrixVersion, Metadata, PathSegment, VersionHistory, VersioningDecision,
+};
+#[doc(hidden)]
+pub use self::metadata::{_path_segment_count, _path_segments};
 
 /// An enum to control whether an access token should be added to outgoing requests
 #[derive(Clone, Copy, Debug)]
@@ -405,12 +417,47 @@ pub trait OutgoingRequest: Sized + Clone {
     /// The endpoints path will be appended to the given `base_url`, for example
     /// `https://matrix.org`. Since all paths begin with a slash, it is not necessary for the
     /// `base_url` to have a trailing slash. If it has one however, it will be ignored.
+    ///
+    /// The `base_url` can include a path prefix, for homeservers that are served under a
+    /// subpath behind a reverse proxy. For example, with `https://example.org/matrix` the
+    /// request will be sent to `https://example.org/matrix/_matrix/…`. The `base_url` must not
+    /// include a query string or a fragment.
     fn try_into_http_request<T: Default + BufMut>(
         self,
         base_url: &str,
         access_token: SendAccessToken<'_>,
         considering_versions: &'_ [MatrixVersion],
     ) -> Result<http::Request<T>, IntoHttpError>;
+
+    /// Tries to convert this request into an `http::Request`, failing if the query string of a
+    /// `GET` request is longer than `max_query_length` bytes.
+    ///
+    /// Servers and proxies usually reject URLs that are too long, so this allows to fail early
+    /// with a [`QueryTooLong`](error::IntoHttpError::QueryTooLong) error, for example when a
+    /// request contains a long list of query parameters.
+    ///
+    /// Apart from this check, this works like
+    /// [`try_into_http_request()`](Self::try_into_http_request).
+    fn try_into_http_request_with_max_query_length<T: Default + BufMut>(
+        self,
+        base_url: &str,
+        access_token: SendAccessToken<'_>,
+        considering_versions: &'_ [MatrixVersion],
+        max_query_length: usize,
+    ) -> Result<http::Request<T>, IntoHttpError> {
+        let http_request =
+            self.try_into_http_request(base_url, access_token, considering_versions)?;
+
+        if Self::METADATA.method == http::Method::GET {
+            let length = http_request.uri().query().map_or(0, str::len);
+
+            if length > max_query_length {
+                return Err(IntoHttpError::QueryTooLong { length, max_length: max_query_length });
+            }
+        }
+
+        Ok(http_request)
+    }
 }
 
 /// A response type for a Matrix API endpoint, used for receiving responses.
@@ -459,6 +506,30 @@ pub trait OutgoingRequestAppserviceExt: OutgoingRequest {
 
         Ok(http_request)
     }
+
+    /// Tries to convert this request into an `http::Request` sent by an appservice, masquerading
+    /// as the given virtual `user_id`.
+    ///
+    /// The `as_token` of the appservice is always sent as the access token, regardless of the
+    /// authentication scheme of the endpoint, and the `user_id` is appended to the query string
+    /// to [assert Appservice identity][id_assert], like
+    /// [`try_into_http_request_with_user_id`][Self::try_into_http_request_with_user_id].
+    ///
+    /// [id_assert]: https://spec.matrix.org/latest/application-service-api/#identity-assertion
+    fn try_into_http_request_as_user<T: Default + BufMut>(
+        self,
+        base_url: &str,
+        as_token: &str,
+        user_id: &UserId,
+        considering_versions: &'_ [MatrixVersion],
+    ) -> Result<http::Request<T>, IntoHttpError> {
+        self.try_into_http_request_with_user_id(
+            base_url,
+            SendAccessToken::Always(as_token),
+            user_id,
+            considering_versions,
+        )
+    }
 }
 
 impl<T: OutgoingRequest> OutgoingRequestAppserviceExt for T {}
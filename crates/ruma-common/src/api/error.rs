@@ -5,10 +5,12 @@
 use std::{error::Error as StdError, fmt, num::ParseIntError, sync::Arc};
 
 use bytes::{BufMut, Bytes};
+use serde::{Deserialize, Serialize};
 use serde_json::{from_slice as from_json_slice, Value as JsonValue};
 use thiserror::Error;
 
 use super::{EndpointError, MatrixVersion, OutgoingResponse};
+use crate::{serde::StringEnum, PrivOwnedStr};
 
 /// A general-purpose Matrix error type consisting of an HTTP status code and a JSON body.
 ///
@@ -33,6 +35,15 @@ impl fmt::Display for MatrixError {
     }
 }
 
+impl MatrixError {
+    /// Tries to deserialize the body of this error as a [`StandardErrorBody`].
+    ///
+    /// Returns `None` if the body is not JSON or doesn't have the expected shape.
+    pub fn standard_body(&self) -> Option<StandardErrorBody> {
+        self.body.standard()
+    }
+}
+
 impl StdError for MatrixError {}
 
 impl OutgoingResponse for MatrixError {
@@ -90,6 +101,97 @@ impl MatrixErrorBody {
             },
         }
     }
+
+    /// Tries to deserialize this body as a [`StandardErrorBody`].
+    ///
+    /// Returns `None` if the body is not JSON or doesn't have the expected shape.
+    pub fn standard(&self) -> Option<StandardErrorBody> {
+        match self {
+            MatrixErrorBody::Json(json) => StandardErrorBody::deserialize(json).ok(),
+            MatrixErrorBody::NotJson { .. } => None,
+        }
+    }
+}
+
+/// The standard JSON body of an error response, shared by all the Matrix APIs.
+///
+/// Any other field of the body is ignored during deserialization.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[allow(clippy::exhaustive_structs)]
+pub struct StandardErrorBody {
+    /// The error code.
+    #[serde(rename = "errcode")]
+    pub code: ErrorCode,
+
+    /// A human-readable error message, usually a sentence explaining what went wrong.
+    #[serde(rename = "error")]
+    pub message: String,
+}
+
+impl StandardErrorBody {
+    /// Creates a new `StandardErrorBody` with the given error code and message.
+    pub fn new(code: ErrorCode, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+/// The [common error codes] of the Matrix APIs.
+///
+/// Individual `ruma-*-api` crates may provide more specific error codes.
+///
+/// [common error codes]: https://spec.matrix.org/latest/client-server-api/#common-error-codes
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, PartialEq, Eq, StringEnum)]
+#[non_exhaustive]
+#[ruma_enum(rename_all = "M_MATRIX_ERROR_CASE")]
+pub enum ErrorCode {
+    /// M_FORBIDDEN
+    Forbidden,
+
+    /// M_UNKNOWN_TOKEN
+    UnknownToken,
+
+    /// M_MISSING_TOKEN
+    MissingToken,
+
+    /// M_USER_LOCKED
+    UserLocked,
+
+    /// M_USER_SUSPENDED
+    UserSuspended,
+
+    /// M_BAD_JSON
+    BadJson,
+
+    /// M_NOT_JSON
+    NotJson,
+
+    /// M_NOT_FOUND
+    NotFound,
+
+    /// M_LIMIT_EXCEEDED
+    LimitExceeded,
+
+    /// M_UNRECOGNIZED
+    Unrecognized,
+
+    /// M_UNKNOWN
+    Unknown,
+
+    /// M_UNAUTHORIZED
+    Unauthorized,
+
+    /// M_MISSING_PARAM
+    MissingParam,
+
+    /// M_INVALID_PARAM
+    InvalidParam,
+
+    /// M_TOO_LARGE
+    TooLarge,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
 }
 
 /// An error when converting one of ruma's endpoint-specific request or response
@@ -131,6 +233,16 @@ pub enum IntoHttpError {
     /// HTTP request construction failed.
     #[error("HTTP request construction failed: {0}")]
     Http(#[from] http::Error),
+
+    /// The query string of a `GET` request is longer than the maximum allowed length.
+    #[error("query string is {length} bytes long, but the maximum is {max_length} bytes")]
+    QueryTooLong {
+        /// The length of the query string, in bytes.
+        length: usize,
+
+        /// The maximum allowed length of the query string, in bytes.
+        max_length: usize,
+    },
 }
 
 impl From<http::header::InvalidHeaderValue> for IntoHttpError {
@@ -383,3 +495,48 @@ pub enum HeaderSerializationError {
     #[error("invalid HTTP date")]
     InvalidHttpDate,
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use serde_json::json;
+
+    use super::{ErrorCode, MatrixError, MatrixErrorBody};
+
+    #[test]
+    fn deserialize_standard_body() {
+        let error = MatrixError {
+            status_code: http::StatusCode::UNAUTHORIZED,
+            body: MatrixErrorBody::Json(json!({
+                "errcode": "M_UNKNOWN_TOKEN",
+                "error": "Invalid access token",
+                "soft_logout": true,
+            })),
+        };
+
+        let body = error.standard_body().unwrap();
+        assert_eq!(body.code, ErrorCode::UnknownToken);
+        assert_eq!(body.message, "Invalid access token");
+    }
+
+    #[test]
+    fn deserialize_standard_body_custom_code() {
+        let body = MatrixErrorBody::Json(json!({
+            "errcode": "M_INVALID_PEPPER",
+            "error": "Unknown or invalid pepper",
+        }));
+
+        let body = body.standard().unwrap();
+        assert_eq!(body.code.as_str(), "M_INVALID_PEPPER");
+        assert_eq!(body.message, "Unknown or invalid pepper");
+    }
+
+    #[test]
+    fn deserialize_non_standard_body() {
+        let body = MatrixErrorBody::Json(json!({ "message": "Not found" }));
+        assert_matches!(body.standard(), None);
+
+        let body = MatrixErrorBody::from_bytes(b"<html>Not found</html>");
+        assert_matches!(body.standard(), None);
+    }
+}
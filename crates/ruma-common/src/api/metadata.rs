@@ -413,6 +413,42 @@ impl VersionHistory {
         self.unstable_paths().chain(self.stable_paths().map(|(_, path)| path))
     }
 
+    /// Whether the given request path matches one of the path variants of this endpoint.
+    ///
+    /// Path parameters, like `:room_id`, match any segment, including an empty one, since some
+    /// parameters like state keys can be empty. The path must not contain a query string.
+    pub fn matches_path(&self, path: &str) -> bool {
+        self.all_paths().any(|canon| {
+            let mut canon_segments = canon.split('/');
+            let mut segments = path.split('/');
+
+            loop {
+                match (canon_segments.next(), segments.next()) {
+                    (None, None) => return true,
+                    (Some(canon_segment), Some(segment)) => {
+                        if !canon_segment.starts_with(':') && canon_segment != segment {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+        })
+    }
+
+    /// Returns the path that best represents this endpoint, in canon form.
+    ///
+    /// This is the latest stable path if there is one, or the latest unstable path otherwise.
+    pub const fn canonical_path(&self) -> &'static str {
+        match self.stable_paths {
+            [.., (_, path)] => path,
+            [] => match self.unstable_paths {
+                [.., path] => path,
+                [] => panic!("No paths supplied"),
+            },
+        }
+    }
+
     /// Returns all unstable path variants in canon form.
     pub fn unstable_paths(&self) -> impl Iterator<Item = &'static str> {
         self.unstable_paths.iter().copied()
@@ -447,6 +483,59 @@ impl VersionHistory {
     }
 }
 
+/// A segment of an endpoint path, as described by its canon form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[allow(clippy::exhaustive_enums)]
+pub enum PathSegment {
+    /// A segment that must match exactly, like `_matrix` or `client`.
+    Literal(&'static str),
+
+    /// A segment that is replaced by the value of a path parameter, with the name of that
+    /// parameter, like `room_id` for `:room_id`.
+    Parameter(&'static str),
+}
+
+// Used by the `request` macro to generate `path_spec()`.
+#[doc(hidden)]
+pub const fn _path_segment_count(path: &'static str) -> usize {
+    let bytes = path.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'/' {
+            count += 1;
+        }
+        i += 1;
+    }
+
+    count
+}
+
+// Used by the `request` macro to generate `path_spec()`.
+#[doc(hidden)]
+pub const fn _path_segments<const N: usize>(path: &'static str) -> [PathSegment; N] {
+    use konst::{iter, string};
+
+    let path = match string::strip_prefix(path, "/") {
+        Some(path) => path,
+        None => panic!("endpoint paths must start with '/'"),
+    };
+
+    let mut segments = [PathSegment::Literal(""); N];
+    let mut i = 0;
+
+    iter::for_each!(segment in string::split(path, "/") => {
+        segments[i] = match string::strip_prefix(segment, ":") {
+            Some(name) => PathSegment::Parameter(name),
+            None => PathSegment::Literal(segment),
+        };
+        i += 1;
+    });
+
+    segments
+}
+
 /// A versioning "decision" derived from a set of Matrix versions.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[allow(clippy::exhaustive_enums)]
@@ -846,6 +935,16 @@ mod tests {
         assert_matches!(hist.select_path(&[V1_0, V1_1]), Ok("/s"));
     }
 
+    #[test]
+    fn select_stable_path_at_or_below_version() {
+        let hist =
+            VersionHistory { stable_paths: &[(V1_0, "/r"), (V1_1, "/s"), (V1_3, "/t")], ..EMPTY };
+        assert_matches!(hist.select_path(&[V1_0]), Ok("/r"));
+        assert_matches!(hist.select_path(&[V1_1]), Ok("/s"));
+        assert_matches!(hist.select_path(&[V1_2]), Ok("/s"));
+        assert_matches!(hist.select_path(&[V1_1, V1_3]), Ok("/t"));
+    }
+
     #[test]
     fn select_unstable() {
         let hist = VersionHistory { unstable_paths: &["/u"], ..EMPTY };
@@ -886,6 +985,22 @@ mod tests {
         assert_matches!(hist.select_path(&[V1_0]), Err(IntoHttpError::NoUnstablePath));
     }
 
+    #[test]
+    fn matches_path() {
+        let hist = VersionHistory {
+            unstable_paths: &["/_matrix/client/unstable/org.example/rooms/:room_id/thing"],
+            stable_paths: &[(V1_1, "/_matrix/client/v3/rooms/:room_id/thing")],
+            ..EMPTY
+        };
+
+        assert!(hist.matches_path("/_matrix/client/v3/rooms/!room:example.org/thing"));
+        assert!(hist.matches_path("/_matrix/client/unstable/org.example/rooms/!room/thing"));
+        assert!(hist.matches_path("/_matrix/client/v3/rooms//thing"));
+        assert!(!hist.matches_path("/_matrix/client/v3/rooms/!room:example.org"));
+        assert!(!hist.matches_path("/_matrix/client/v3/rooms/!room:example.org/thing/other"));
+        assert!(!hist.matches_path("/_matrix/client/r0/rooms/!room:example.org/thing"));
+    }
+
     #[test]
     fn version_literal() {
         const LIT: MatrixVersion = MatrixVersion::from_lit("1.0");
@@ -16,6 +16,11 @@ use crate::{serde::Raw, RoomVersionId};
 #[allow(clippy::exhaustive_enums)]
 pub enum CanonicalJsonError {
     /// The numeric value failed conversion to js_int::Int.
+    ///
+    /// This happens when the number is not an integer, or when it is outside of the range
+    /// `[-(2**53)+1, (2**53)-1]` required by the [canonical JSON] format.
+    ///
+    /// [canonical JSON]: https://spec.matrix.org/latest/appendices/#canonical-json
     IntConvert,
 
     /// An error occurred while serializing/deserializing.
@@ -109,12 +114,56 @@ pub fn try_from_json_map(
 }
 
 /// Fallible conversion from any value that impl's `Serialize` to a `CanonicalJsonValue`.
+///
+/// As required by the [canonical JSON] format of the Matrix specification, this fails with
+/// [`CanonicalJsonError::IntConvert`] if the value contains a number that is not an integer or
+/// that is outside of the range `[-(2**53)+1, (2**53)-1]`.
+///
+/// [canonical JSON]: https://spec.matrix.org/latest/appendices/#canonical-json
 pub fn to_canonical_value<T: Serialize>(
     value: T,
 ) -> Result<CanonicalJsonValue, CanonicalJsonError> {
     serde_json::to_value(value).map_err(CanonicalJsonError::SerDe)?.try_into()
 }
 
+/// Serialize the given value to a canonical JSON string.
+///
+/// `Serialize` implementations emit the fields of structs in declaration order and may emit
+/// whitespace or floating-point numbers, which don't match the [canonical JSON] format. This
+/// serializes the value and converts it to canonical JSON, with sorted object keys and no
+/// whitespace, so the result can be signed or hashed.
+///
+/// This is the correct way to get the bytes to sign from a typed value.
+///
+/// Fails if the value is not valid canonical JSON, e.g. if it contains a floating-point number
+/// or an integer outside of the range of `js_int::Int`.
+///
+/// # Example
+///
+/// ```
+/// use ruma_common::canonical_json::to_canonical_json_string;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Key {
+///     verify_key: String,
+///     algorithm: String,
+/// }
+///
+/// let key = Key { verify_key: "abc".to_owned(), algorithm: "ed25519".to_owned() };
+/// assert_eq!(
+///     to_canonical_json_string(&key).unwrap(),
+///     r#"{"algorithm":"ed25519","verify_key":"abc"}"#
+/// );
+/// ```
+///
+/// [canonical JSON]: https://spec.matrix.org/latest/appendices/#canonical-json
+pub fn to_canonical_json_string<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<String, CanonicalJsonError> {
+    Ok(to_canonical_value(value)?.to_string())
+}
+
 /// The value to put in `unsigned.redacted_because`.
 #[derive(Clone, Debug)]
 pub struct RedactedBecause(CanonicalJsonObject);
@@ -226,6 +275,41 @@ pub fn redact_content_in_place(
     object_retain_keys(object, allowed_content_keys_for(event_type.as_ref(), version))
 }
 
+/// Returns the ID of the event redacted by the given `m.room.redaction` event, according to the
+/// given room version.
+///
+/// Before room version 11, the ID is in the `redacts` field at the top level of the event. Since
+/// room version 11, it is in the `redacts` field of the content of the event.
+///
+/// Returns `None` if the field at the proper location for the room version is missing or is not a
+/// string. This doesn't check the type of the event.
+pub fn redaction_target<'a>(
+    object: &'a CanonicalJsonObject,
+    version: &RoomVersionId,
+) -> Option<&'a str> {
+    let redacts = match version {
+        RoomVersionId::V1
+        | RoomVersionId::V2
+        | RoomVersionId::V3
+        | RoomVersionId::V4
+        | RoomVersionId::V5
+        | RoomVersionId::V6
+        | RoomVersionId::V7
+        | RoomVersionId::V8
+        | RoomVersionId::V9
+        | RoomVersionId::V10 => object.get("redacts"),
+        _ => match object.get("content") {
+            Some(CanonicalJsonValue::Object(content)) => content.get("redacts"),
+            _ => None,
+        },
+    };
+
+    match redacts {
+        Some(CanonicalJsonValue::String(redacts)) => Some(redacts),
+        _ => None,
+    }
+}
+
 fn object_retain_keys(
     object: &mut CanonicalJsonObject,
     allowed_keys: &AllowedKeys,
@@ -508,10 +592,67 @@ mod tests {
     };
 
     use super::{
-        redact_in_place, to_canonical_value, try_from_json_map, value::CanonicalJsonValue,
+        redact_in_place, redaction_target, to_canonical_json_string, to_canonical_value,
+        try_from_json_map, value::CanonicalJsonValue, CanonicalJsonError,
     };
     use crate::RoomVersionId;
 
+    #[test]
+    fn canonical_value_integer_range() {
+        const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+        assert_matches!(to_canonical_value(MAX_SAFE_INTEGER), Ok(CanonicalJsonValue::Integer(int)));
+        assert_eq!(i64::from(int), MAX_SAFE_INTEGER);
+        assert_matches!(
+            to_canonical_value(-MAX_SAFE_INTEGER),
+            Ok(CanonicalJsonValue::Integer(int))
+        );
+        assert_eq!(i64::from(int), -MAX_SAFE_INTEGER);
+
+        assert_matches!(to_canonical_value(1_i64 << 53), Err(CanonicalJsonError::IntConvert));
+        assert_matches!(to_canonical_value(-(1_i64 << 53)), Err(CanonicalJsonError::IntConvert));
+        assert_matches!(to_canonical_value(u64::MAX), Err(CanonicalJsonError::IntConvert));
+        assert_matches!(
+            to_canonical_value(json!({ "a": [1_i64 << 53] })),
+            Err(CanonicalJsonError::IntConvert)
+        );
+        assert_matches!(
+            CanonicalJsonValue::try_from(json!(-(1_i64 << 53))),
+            Err(CanonicalJsonError::IntConvert)
+        );
+    }
+
+    #[test]
+    fn canonical_json_string() {
+        #[derive(serde::Serialize)]
+        struct Inner {
+            z: u8,
+            a: &'static str,
+        }
+
+        #[derive(serde::Serialize)]
+        struct Outer {
+            unicode: &'static str,
+            inner: Inner,
+            array: Vec<u8>,
+        }
+
+        let value = Outer {
+            unicode: "日本語",
+            inner: Inner { z: 1, a: "\u{1F600}" },
+            array: vec![3, 2, 1],
+        };
+        assert_eq!(
+            to_canonical_json_string(&value).unwrap(),
+            r#"{"array":[3,2,1],"inner":{"a":"😀","z":1},"unicode":"日本語"}"#
+        );
+
+        assert_matches!(
+            to_canonical_json_string(&json!({ "a": 1.5 })),
+            Err(CanonicalJsonError::IntConvert)
+        );
+    }
+
     #[test]
     fn serialize_canon() {
         let json: CanonicalJsonValue = json!({
@@ -765,4 +906,50 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn redaction_target_before_v11() {
+        let event = try_from_json_map(
+            json!({
+                "content": {
+                    "reason": "Spam",
+                },
+                "event_id": "$h29iv0s8:example.com",
+                "redacts": "$fukweghifu23:localhost",
+                "room_id": "!jEsUZKDJdhlrceRyVU:example.org",
+                "sender": "@example:example.org",
+                "type": "m.room.redaction",
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        )
+        .unwrap();
+
+        assert_eq!(redaction_target(&event, &RoomVersionId::V10), Some("$fukweghifu23:localhost"));
+        assert_eq!(redaction_target(&event, &RoomVersionId::V11), None);
+    }
+
+    #[test]
+    fn redaction_target_since_v11() {
+        let event = try_from_json_map(
+            json!({
+                "content": {
+                    "reason": "Spam",
+                    "redacts": "$fukweghifu23:localhost",
+                },
+                "event_id": "$h29iv0s8:example.com",
+                "room_id": "!jEsUZKDJdhlrceRyVU:example.org",
+                "sender": "@example:example.org",
+                "type": "m.room.redaction",
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        )
+        .unwrap();
+
+        assert_eq!(redaction_target(&event, &RoomVersionId::V11), Some("$fukweghifu23:localhost"));
+        assert_eq!(redaction_target(&event, &RoomVersionId::V10), None);
+    }
 }
@@ -2,7 +2,11 @@ use std::{collections::BTreeMap, fmt};
 
 use as_variant::as_variant;
 use js_int::{Int, UInt};
-use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use serde::{
+    de::{self, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
 use serde_json::{to_string as to_json_string, Value as JsonValue};
 
 use super::CanonicalJsonError;
@@ -283,16 +287,107 @@ impl<'de> Deserialize<'de> for CanonicalJsonValue {
     where
         D: Deserializer<'de>,
     {
-        let val = JsonValue::deserialize(deserializer)?;
-        val.try_into().map_err(serde::de::Error::custom)
+        // Deserialize directly instead of going through `serde_json::Value`, which avoids an
+        // intermediate allocation for every value.
+        deserializer.deserialize_any(CanonicalJsonValueVisitor)
+    }
+}
+
+/// The key of the map that represents a number with the `arbitrary_precision` feature of
+/// serde_json.
+const SERDE_JSON_NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+struct CanonicalJsonValueVisitor;
+
+impl<'de> Visitor<'de> for CanonicalJsonValueVisitor {
+    type Value = CanonicalJsonValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a canonical JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(CanonicalJsonValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        let int = Int::try_from(v).map_err(|_| E::custom(CanonicalJsonError::IntConvert))?;
+        Ok(CanonicalJsonValue::Integer(int))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        let int = Int::try_from(v).map_err(|_| E::custom(CanonicalJsonError::IntConvert))?;
+        Ok(CanonicalJsonValue::Integer(int))
+    }
+
+    fn visit_f64<E: de::Error>(self, _v: f64) -> Result<Self::Value, E> {
+        Err(E::custom(CanonicalJsonError::IntConvert))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CanonicalJsonValue::String(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CanonicalJsonValue::String(v))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(CanonicalJsonValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(CanonicalJsonValue::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(value) = seq.next_element()? {
+            vec.push(value);
+        }
+
+        Ok(CanonicalJsonValue::Array(vec))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut object = CanonicalJsonObject::new();
+
+        let Some(first_key) = map.next_key::<String>()? else {
+            return Ok(CanonicalJsonValue::Object(object));
+        };
+
+        // With the `arbitrary_precision` feature of serde_json, which can be enabled by any crate
+        // in the dependency graph, numbers are deserialized as a map with a single entry
+        // whose value is the number as a string.
+        if first_key == SERDE_JSON_NUMBER_TOKEN {
+            let number = map.next_value::<String>()?;
+            let int = number
+                .parse::<Int>()
+                .map_err(|_| de::Error::custom(CanonicalJsonError::IntConvert))?;
+            return Ok(CanonicalJsonValue::Integer(int));
+        }
+
+        object.insert(first_key, map.next_value()?);
+
+        while let Some((key, value)) = map.next_entry()? {
+            object.insert(key, value);
+        }
+
+        Ok(CanonicalJsonValue::Object(object))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use serde_json::json;
+    use js_int::{int, Int};
+    use serde_json::{from_str as from_json_str, json};
 
-    use super::CanonicalJsonValue;
+    use super::{CanonicalJsonObject, CanonicalJsonValue};
 
     #[test]
     fn to_string() {
@@ -304,4 +399,70 @@ mod tests {
         assert_eq!(format!("{json}"), CANONICAL_STR);
         assert_eq!(format!("{json:#}"), CANONICAL_STR);
     }
+
+    #[test]
+    fn deserialize() {
+        let json = from_json_str::<CanonicalJsonValue>(
+            r#"{"b":[1,-2,true,null],"a":{"string":"日本","int":9007199254740991}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            json,
+            CanonicalJsonValue::Object(CanonicalJsonObject::from([
+                (
+                    "a".to_owned(),
+                    CanonicalJsonValue::Object(CanonicalJsonObject::from([
+                        ("int".to_owned(), CanonicalJsonValue::Integer(Int::MAX)),
+                        ("string".to_owned(), CanonicalJsonValue::String("日本".to_owned())),
+                    ]))
+                ),
+                (
+                    "b".to_owned(),
+                    CanonicalJsonValue::Array(vec![
+                        CanonicalJsonValue::Integer(int!(1)),
+                        CanonicalJsonValue::Integer(int!(-2)),
+                        CanonicalJsonValue::Bool(true),
+                        CanonicalJsonValue::Null,
+                    ])
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_numbers() {
+        from_json_str::<CanonicalJsonValue>("1.5").unwrap_err();
+        from_json_str::<CanonicalJsonValue>("1.0").unwrap_err();
+        from_json_str::<CanonicalJsonValue>("1e3").unwrap_err();
+        from_json_str::<CanonicalJsonValue>("9007199254740992").unwrap_err();
+        from_json_str::<CanonicalJsonValue>("-9007199254740992").unwrap_err();
+        from_json_str::<CanonicalJsonValue>(r#"{"a":[0.5]}"#).unwrap_err();
+    }
+
+    #[test]
+    fn deserialize_arbitrary_precision_numbers() {
+        // This is how serde_json represents numbers with its `arbitrary_precision` feature.
+        let json =
+            from_json_str::<CanonicalJsonValue>(r#"{"a":{"$serde_json::private::Number":"-42"}}"#)
+                .unwrap();
+        assert_eq!(
+            json,
+            CanonicalJsonValue::Object(CanonicalJsonObject::from([(
+                "a".to_owned(),
+                CanonicalJsonValue::Integer(int!(-42))
+            )]))
+        );
+
+        from_json_str::<CanonicalJsonValue>(r#"{"a":{"$serde_json::private::Number":"1.5"}}"#)
+            .unwrap_err();
+        from_json_str::<CanonicalJsonValue>(
+            r#"{"a":{"$serde_json::private::Number":"100000000000000000000000"}}"#,
+        )
+        .unwrap_err();
+        from_json_str::<CanonicalJsonValue>(
+            r#"{"a":{"$serde_json::private::Number":"9007199254740992"}}"#,
+        )
+        .unwrap_err();
+    }
 }
@@ -209,7 +209,8 @@ mod tests {
     use assert_matches2::assert_matches;
     use ruma_identifiers_validation::Error;
 
-    use super::DeviceKeyId;
+    use super::{CrossSigningKeyId, DeviceKeyId, ServerSigningKeyId};
+    use crate::SigningKeyAlgorithm;
 
     #[test]
     fn algorithm_and_key_name_are_correctly_extracted() {
@@ -218,6 +219,27 @@ mod tests {
         assert_eq!(key_id.key_name(), "MYDEVICE");
     }
 
+    #[test]
+    fn cross_signing_key_id_parts() {
+        let key_id =
+            CrossSigningKeyId::parse("ed25519:nqOvzeuGWT/sRx3h7+MHoInYj3Uk2LD/unI9kDYcHwk")
+                .expect("Should parse correctly");
+        assert_eq!(key_id.algorithm(), SigningKeyAlgorithm::Ed25519);
+        assert_eq!(key_id.key_name().as_str(), "nqOvzeuGWT/sRx3h7+MHoInYj3Uk2LD/unI9kDYcHwk");
+    }
+
+    #[test]
+    fn server_signing_key_id_parts() {
+        let key_id = ServerSigningKeyId::parse("ed25519:abc_123").expect("Should parse correctly");
+        assert_eq!(key_id.algorithm(), SigningKeyAlgorithm::Ed25519);
+        assert_eq!(key_id.key_name().as_str(), "abc_123");
+
+        let key_id =
+            ServerSigningKeyId::parse("org.example.custom:0").expect("Should parse correctly");
+        assert_eq!(key_id.algorithm().as_str(), "org.example.custom");
+        assert_eq!(key_id.key_name().as_str(), "0");
+    }
+
     #[test]
     fn empty_key_name_is_correctly_extracted() {
         let key_id = DeviceKeyId::parse("ed25519:").expect("Should parse correctly");
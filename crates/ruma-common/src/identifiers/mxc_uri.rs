@@ -2,7 +2,7 @@
 //!
 //! [MXC URI]: https://spec.matrix.org/latest/client-server-api/#matrix-content-mxc-uris
 
-use std::num::NonZeroU8;
+use std::num::NonZeroUsize;
 
 use ruma_identifiers_validation::{error::MxcUriError, mxc_uri::validate};
 use ruma_macros::IdZst;
@@ -14,6 +14,11 @@ type Result<T, E = MxcUriError> = std::result::Result<T, E>;
 /// A URI that should be a Matrix-spec compliant [MXC URI].
 ///
 /// [MXC URI]: https://spec.matrix.org/latest/client-server-api/#matrix-content-mxc-uris
+///
+/// Contrary to other identifiers, an `MxcUri` is not validated when it is constructed, so
+/// that an invalid URI in a received event doesn't make the whole event fail to deserialize.
+/// Use [`MxcUri::validate()`] or the accessors that return a `Result`, like
+/// [`MxcUri::parts()`], before relying on its contents.
 #[repr(transparent)]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, IdZst)]
 pub struct MxcUri(str);
@@ -34,8 +39,8 @@ impl MxcUri {
     pub fn parts(&self) -> Result<(&ServerName, &str)> {
         self.extract_slash_idx().map(|idx| {
             (
-                ServerName::from_borrowed(&self.as_str()[6..idx.get() as usize]),
-                &self.as_str()[idx.get() as usize + 1..],
+                ServerName::from_borrowed(&self.as_str()[6..idx.get()]),
+                &self.as_str()[idx.get() + 1..],
             )
         })
     }
@@ -53,7 +58,7 @@ impl MxcUri {
 
     // convenience method for calling validate(self)
     #[inline(always)]
-    fn extract_slash_idx(&self) -> Result<NonZeroU8> {
+    fn extract_slash_idx(&self) -> Result<NonZeroUsize> {
         validate(self.as_str())
     }
 }
@@ -83,6 +88,43 @@ mod tests {
         assert_eq!(mxc.parts(), Err(MxcUriError::MissingSlash));
     }
 
+    #[test]
+    fn parse_mxc_uri_accessors() {
+        let mxc = <&MxcUri>::from("mxc://example.com:8448/AbC-12_3");
+        assert_eq!(mxc.server_name().unwrap(), "example.com:8448");
+        assert_eq!(mxc.media_id(), Ok("AbC-12_3"));
+    }
+
+    #[test]
+    fn parse_mxc_uri_with_empty_media_id() {
+        let mxc = <&MxcUri>::from("mxc://127.0.0.1/");
+        assert!(!mxc.is_valid());
+        assert_eq!(mxc.media_id(), Err(MxcUriError::MediaIdMalformed));
+    }
+
+    #[test]
+    fn parse_mxc_uri_with_invalid_media_id() {
+        let mxc = <&MxcUri>::from("mxc://127.0.0.1/../secret");
+        assert!(!mxc.is_valid());
+        assert_eq!(mxc.media_id(), Err(MxcUriError::MediaIdMalformed));
+    }
+
+    #[test]
+    fn parse_mxc_uri_with_invalid_server_name() {
+        let mxc = <&MxcUri>::from("mxc://in valid/media");
+        assert!(!mxc.is_valid());
+        assert_eq!(mxc.server_name(), Err(MxcUriError::ServerNameMalformed));
+    }
+
+    #[test]
+    fn parse_mxc_uri_with_long_server_name() {
+        let server_name = "a".repeat(300);
+        let mxc = OwnedMxcUri::from(format!("mxc://{server_name}/media"));
+        assert!(mxc.is_valid());
+        assert_eq!(mxc.server_name().unwrap(), server_name.as_str());
+        assert_eq!(mxc.media_id(), Ok("media"));
+    }
+
     #[test]
     fn parse_mxc_uri_without_protocol() {
         assert!(!Box::<MxcUri>::from("127.0.0.1/asd32asdfasdsd").is_valid());
@@ -34,6 +34,14 @@ impl RoomId {
         Self::from_borrowed(&format!("!{}:{server_name}", super::generate_localpart(18))).to_owned()
     }
 
+    /// Returns the localpart of the room ID.
+    ///
+    /// This is the part after the leading `!` and before the server name, if any.
+    pub fn localpart(&self) -> &str {
+        let localpart = &self.as_str()[1..];
+        localpart.split_once(':').map_or(localpart, |(localpart, _)| localpart)
+    }
+
     /// Returns the server name of the room ID.
     pub fn server_name(&self) -> Option<&ServerName> {
         <&RoomOrAliasId>::from(self).server_name()
@@ -205,17 +213,33 @@ mod tests {
     use super::{OwnedRoomId, RoomId};
     use crate::{server_name, IdParseError};
 
+    #[test]
+    fn parse_bytes() {
+        let room_id =
+            RoomId::parse_bytes(b"!29fhd83h92h0:example.com").expect("Failed to create RoomId.");
+        assert_eq!(room_id.as_str(), "!29fhd83h92h0:example.com");
+
+        assert_eq!(RoomId::parse_bytes(b"!29fhd83h92h0:\xc3"), Err(IdParseError::InvalidUtf8));
+        assert_eq!(
+            RoomId::parse_bytes(b"29fhd83h92h0:example.com"),
+            Err(IdParseError::MissingLeadingSigil)
+        );
+    }
+
     #[test]
     fn valid_room_id() {
         let room_id =
             <&RoomId>::try_from("!29fhd83h92h0:example.com").expect("Failed to create RoomId.");
         assert_eq!(room_id, "!29fhd83h92h0:example.com");
+        assert_eq!(room_id.localpart(), "29fhd83h92h0");
+        assert_eq!(room_id.server_name(), Some(server_name!("example.com")));
     }
 
     #[test]
     fn empty_localpart() {
         let room_id = <&RoomId>::try_from("!:example.com").expect("Failed to create RoomId.");
         assert_eq!(room_id, "!:example.com");
+        assert_eq!(room_id.localpart(), "");
         assert_eq!(room_id.server_name(), Some(server_name!("example.com")));
     }
 
@@ -278,6 +302,17 @@ mod tests {
     fn missing_server_name() {
         let room_id = <&RoomId>::try_from("!29fhd83h92h0").expect("Failed to create RoomId.");
         assert_eq!(room_id, "!29fhd83h92h0");
+        assert_eq!(room_id.localpart(), "29fhd83h92h0");
+        assert_eq!(room_id.server_name(), None);
+    }
+
+    #[test]
+    fn room_id_without_server_name() {
+        // Since room version 12, the room ID is derived from the reference hash of the create
+        // event.
+        let room_id = <&RoomId>::try_from("!31hneApxJ_1o-63DmFrpeqnkFfWppnzWso1JvH3ogLM")
+            .expect("Failed to create RoomId.");
+        assert_eq!(room_id.localpart(), "31hneApxJ_1o-63DmFrpeqnkFfWppnzWso1JvH3ogLM");
         assert_eq!(room_id.server_name(), None);
     }
 
@@ -59,6 +59,9 @@ pub enum RoomVersionId {
     /// A version 11 room.
     V11,
 
+    /// A version 12 room.
+    V12,
+
     #[doc(hidden)]
     _Custom(CustomRoomVersion),
 }
@@ -80,6 +83,7 @@ impl RoomVersionId {
             Self::V9 => "9",
             Self::V10 => "10",
             Self::V11 => "11",
+            Self::V12 => "12",
             Self::_Custom(version) => version.as_str(),
         }
     }
@@ -88,6 +92,33 @@ impl RoomVersionId {
     pub fn as_bytes(&self) -> &[u8] {
         self.as_str().as_bytes()
     }
+
+    /// Get the number of this room version, if it is a version of the Matrix specification.
+    ///
+    /// Returns `None` for custom room versions.
+    ///
+    /// ```
+    /// # use ruma_common::RoomVersionId;
+    /// assert_eq!(RoomVersionId::V10.as_number(), Some(10));
+    /// assert_eq!(RoomVersionId::try_from("org.example.1").unwrap().as_number(), None);
+    /// ```
+    pub fn as_number(&self) -> Option<u8> {
+        match self {
+            Self::V1 => Some(1),
+            Self::V2 => Some(2),
+            Self::V3 => Some(3),
+            Self::V4 => Some(4),
+            Self::V5 => Some(5),
+            Self::V6 => Some(6),
+            Self::V7 => Some(7),
+            Self::V8 => Some(8),
+            Self::V9 => Some(9),
+            Self::V10 => Some(10),
+            Self::V11 => Some(11),
+            Self::V12 => Some(12),
+            Self::_Custom(_) => None,
+        }
+    }
 }
 
 impl From<RoomVersionId> for String {
@@ -104,6 +135,7 @@ impl From<RoomVersionId> for String {
             RoomVersionId::V9 => "9".to_owned(),
             RoomVersionId::V10 => "10".to_owned(),
             RoomVersionId::V11 => "11".to_owned(),
+            RoomVersionId::V12 => "12".to_owned(),
             RoomVersionId::_Custom(version) => version.into(),
         }
     }
@@ -122,24 +154,33 @@ impl AsRef<[u8]> for RoomVersionId {
 }
 
 impl PartialOrd for RoomVersionId {
-    /// Compare the two given room version IDs by comparing their string representations.
+    /// Compare the two given room version IDs.
     ///
-    /// Please be aware that room version IDs don't have a defined ordering in the Matrix
-    /// specification. This implementation only exists to be able to use `RoomVersionId`s or
-    /// types containing `RoomVersionId`s as `BTreeMap` keys.
+    /// See the [`Ord`] implementation for details.
     fn partial_cmp(&self, other: &RoomVersionId) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for RoomVersionId {
-    /// Compare the two given room version IDs by comparing their string representations.
+    /// Compare the two given room version IDs.
     ///
-    /// Please be aware that room version IDs don't have a defined ordering in the Matrix
-    /// specification. This implementation only exists to be able to use `RoomVersionId`s or
-    /// types containing `RoomVersionId`s as `BTreeMap` keys.
+    /// The versions of the Matrix specification are ordered by their [number], so for example
+    /// `V9 < V10`. Custom room versions are considered higher than the versions of the
+    /// specification, and are compared by their string representations.
+    ///
+    /// Please be aware that custom room versions don't have a defined ordering in the Matrix
+    /// specification, so a higher custom room version doesn't mean that it is more recent or that
+    /// it has more features.
+    ///
+    /// [number]: Self::as_number
     fn cmp(&self, other: &Self) -> Ordering {
-        self.as_str().cmp(other.as_str())
+        match (self.as_number(), other.as_number()) {
+            (Some(number), Some(other_number)) => number.cmp(&other_number),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.as_str().cmp(other.as_str()),
+        }
     }
 }
 
@@ -178,6 +219,7 @@ where
         "9" => RoomVersionId::V9,
         "10" => RoomVersionId::V10,
         "11" => RoomVersionId::V11,
+        "12" => RoomVersionId::V12,
         custom => {
             ruma_identifiers_validation::room_version_id::validate(custom)?;
             RoomVersionId::_Custom(CustomRoomVersion(room_version_id.into()))
@@ -315,6 +357,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn valid_version_12_room_version_id() {
+        let version = RoomVersionId::try_from("12").expect("Failed to create RoomVersionId.");
+        assert_eq!(version, RoomVersionId::V12);
+        assert_eq!(version.as_str(), "12");
+        assert_eq!(String::from(version), "12");
+    }
+
     #[test]
     fn valid_custom_room_version_id() {
         assert_eq!(
@@ -394,4 +444,41 @@ mod tests {
             IdParseError::InvalidCharacters
         );
     }
+
+    #[test]
+    fn ordering() {
+        assert!(RoomVersionId::V1 < RoomVersionId::V2);
+        assert!(RoomVersionId::V9 < RoomVersionId::V10);
+        assert!(RoomVersionId::V10 >= RoomVersionId::V10);
+        assert!(RoomVersionId::V12 < RoomVersionId::try_from("13").unwrap());
+        assert!(RoomVersionId::V12 < RoomVersionId::try_from("io.ruma.1").unwrap());
+        assert!(
+            RoomVersionId::try_from("io.ruma.1").unwrap()
+                < RoomVersionId::try_from("io.ruma.2").unwrap()
+        );
+
+        let mut versions = vec![
+            RoomVersionId::try_from("io.ruma.1").unwrap(),
+            RoomVersionId::V10,
+            RoomVersionId::V2,
+            RoomVersionId::V1,
+        ];
+        versions.sort();
+        assert_eq!(
+            versions,
+            [
+                RoomVersionId::V1,
+                RoomVersionId::V2,
+                RoomVersionId::V10,
+                RoomVersionId::try_from("io.ruma.1").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn as_number() {
+        assert_eq!(RoomVersionId::V1.as_number(), Some(1));
+        assert_eq!(RoomVersionId::V12.as_number(), Some(12));
+        assert_eq!(RoomVersionId::try_from("13").unwrap().as_number(), None);
+    }
 }
@@ -51,6 +51,29 @@ impl<E: Ord, K: KeyName + ?Sized> Signatures<E, K> {
     ) -> Option<String> {
         self.0.entry(entity).or_default().insert(key_identifier, value)
     }
+
+    /// Add the signatures from `other` to this map.
+    ///
+    /// Existing signatures are kept, so if both maps have a signature for the same entity and key
+    /// identifier, the one from `other` is ignored.
+    pub fn merge(&mut self, other: Self) {
+        for (entity, signatures) in other.0 {
+            let entity_signatures = self.0.entry(entity).or_default();
+
+            for (key_identifier, value) in signatures {
+                entity_signatures.entry(key_identifier).or_insert(value);
+            }
+        }
+    }
+
+    /// Iterate over all the signatures, as `(entity, key_identifier, signature)` tuples.
+    pub fn iter_signatures(&self) -> impl Iterator<Item = (&E, &OwnedSigningKeyId<K>, &str)> + '_ {
+        self.0.iter().flat_map(|(entity, signatures)| {
+            signatures
+                .iter()
+                .map(move |(key_identifier, value)| (entity, key_identifier, value.as_str()))
+        })
+    }
 }
 
 /// Map of server signatures, grouped by server.
@@ -118,3 +141,48 @@ impl<E: Ord, K: KeyName + ?Sized> Extend<(E, OwnedSigningKeyId<K>, String)> for
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ServerSignatures;
+    use crate::{
+        owned_server_name, server_signing_key_version, ServerSigningKeyId, SigningKeyAlgorithm,
+    };
+
+    #[test]
+    fn merge_and_iter_signatures() {
+        let key_1 = ServerSigningKeyId::from_parts(
+            SigningKeyAlgorithm::Ed25519,
+            server_signing_key_version!("1"),
+        );
+        let key_2 = ServerSigningKeyId::from_parts(
+            SigningKeyAlgorithm::Ed25519,
+            server_signing_key_version!("2"),
+        );
+
+        let mut signatures = ServerSignatures::from([
+            (owned_server_name!("example.org"), key_1.clone(), "local".to_owned()),
+            (owned_server_name!("example.org"), key_2.clone(), "local_2".to_owned()),
+        ]);
+        let received = ServerSignatures::from([
+            (owned_server_name!("example.org"), key_1.clone(), "received".to_owned()),
+            (owned_server_name!("matrix.org"), key_1.clone(), "remote".to_owned()),
+        ]);
+
+        signatures.merge(received);
+
+        let all = signatures
+            .iter_signatures()
+            .map(|(entity, key_id, value)| (entity.as_str(), key_id.as_str(), value))
+            .collect::<Vec<_>>();
+        // The existing signature for the same key is not replaced.
+        assert_eq!(
+            all,
+            [
+                ("example.org", "ed25519:1", "local"),
+                ("example.org", "ed25519:2", "local_2"),
+                ("matrix.org", "ed25519:1", "remote"),
+            ]
+        );
+    }
+}
@@ -107,6 +107,19 @@ impl UserId {
         !localpart_is_fully_conforming(self.localpart()).unwrap()
     }
 
+    /// Validate this user ID against the latest specification of the user ID grammar.
+    ///
+    /// Parsing a user ID also accepts historical user IDs. This method returns an error if the
+    /// localpart contains characters other than `a-z`, `0-9`, `-`, `.`, `=`, `_`, `/` and `+`,
+    /// which can be used to warn users when creating a new account with a historical user ID.
+    pub fn validate_strict(&self) -> Result<(), IdParseError> {
+        if self.is_historical() {
+            Err(IdParseError::InvalidCharacters)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Create a `matrix.to` URI for this user ID.
     ///
     /// # Example
@@ -177,6 +190,18 @@ mod tests {
         assert!(!user_id.is_historical());
     }
 
+    #[test]
+    fn parse_bytes() {
+        let user_id = UserId::parse_bytes(b"@carl:example.com").expect("Failed to create UserId.");
+        assert_eq!(user_id.as_str(), "@carl:example.com");
+
+        assert_eq!(UserId::parse_bytes(b"@carl:\xff"), Err(IdParseError::InvalidUtf8));
+        assert_eq!(
+            UserId::parse_bytes(b"carl:example.com"),
+            Err(IdParseError::MissingLeadingSigil)
+        );
+    }
+
     #[test]
     fn parse_valid_user_id_parts() {
         let server_name = server_name!("example.com");
@@ -219,6 +244,18 @@ mod tests {
         assert_eq!(user_id.localpart(), "a%b[irc]");
         assert_eq!(user_id.server_name(), "example.com");
         assert!(user_id.is_historical());
+        assert_eq!(user_id.validate_strict(), Err(IdParseError::InvalidCharacters));
+    }
+
+    #[test]
+    fn validate_strict_user_id() {
+        let user_id = <&UserId>::try_from("@carl_1.2=3/4+5-6:example.com").unwrap();
+        assert!(!user_id.is_historical());
+        assert_eq!(user_id.validate_strict(), Ok(()));
+
+        let user_id = <&UserId>::try_from("@Carl:example.com").unwrap();
+        assert!(user_id.is_historical());
+        assert_eq!(user_id.validate_strict(), Err(IdParseError::InvalidCharacters));
     }
 
     #[test]
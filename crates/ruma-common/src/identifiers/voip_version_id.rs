@@ -1,6 +1,6 @@
 //! Matrix VoIP version identifier.
 
-use std::fmt;
+use std::{cmp::Ordering, fmt};
 
 use js_int::UInt;
 use ruma_macros::DisplayAsRefStr;
@@ -26,7 +26,8 @@ use crate::{IdParseError, PrivOwnedStr};
 /// ```
 ///
 /// For simplicity, version 0 has a string representation, but trying to construct a `VoipVersionId`
-/// from a `"0"` string will not result in the `V0` variant.
+/// from a `"0"` string will not result in the `V0` variant. [`VoipVersionId::negotiate_version()`]
+/// considers such a version to be the same as `V0`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, DisplayAsRefStr)]
 #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
 pub enum VoipVersionId {
@@ -54,6 +55,48 @@ impl VoipVersionId {
     pub fn as_bytes(&self) -> &[u8] {
         self.as_str().as_bytes()
     }
+
+    /// Get the highest version that is both offered by the other party and supported locally.
+    ///
+    /// `offered` would usually be the `version` of an `m.call.invite` event, and `supported` the
+    /// versions supported by the local client. The versions are compared with the [`Ord`]
+    /// implementation of `VoipVersionId`.
+    ///
+    /// Some clients send version 0 as the `"0"` string instead of an integer. It is considered to
+    /// be the same as [`VoipVersionId::V0`], which is returned in that case.
+    ///
+    /// Returns `None` if there is no version in common.
+    ///
+    /// ```
+    /// # use ruma_common::VoipVersionId;
+    /// let offered = [VoipVersionId::from("0"), VoipVersionId::V1];
+    ///
+    /// assert_eq!(
+    ///     VoipVersionId::negotiate_version(&offered, &[VoipVersionId::V0, VoipVersionId::V1]),
+    ///     Some(VoipVersionId::V1)
+    /// );
+    /// assert_eq!(
+    ///     VoipVersionId::negotiate_version(&offered, &[VoipVersionId::V0]),
+    ///     Some(VoipVersionId::V0)
+    /// );
+    /// assert_eq!(VoipVersionId::negotiate_version(&offered, &[]), None);
+    /// ```
+    pub fn negotiate_version(offered: &[Self], supported: &[Self]) -> Option<Self> {
+        offered
+            .iter()
+            .map(Self::normalize)
+            .filter(|version| supported.iter().any(|s| s.normalize() == *version))
+            .max()
+            .cloned()
+    }
+
+    /// Convert the legacy `"0"` string version to [`VoipVersionId::V0`].
+    fn normalize(&self) -> &Self {
+        match self {
+            Self::_Custom(PrivOwnedStr(s)) if &**s == "0" => &Self::V0,
+            _ => self,
+        }
+    }
 }
 
 impl From<VoipVersionId> for String {
@@ -71,6 +114,36 @@ impl AsRef<str> for VoipVersionId {
     }
 }
 
+impl PartialOrd for VoipVersionId {
+    /// Compare the two given VoIP version IDs.
+    ///
+    /// See the [`Ord`] implementation for details.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VoipVersionId {
+    /// Compare the two given VoIP version IDs.
+    ///
+    /// Versions of the specification are ordered by their number, `V0` being lower than `V1`.
+    /// Custom versions are considered higher than the versions of the specification, and are
+    /// compared by their string representations.
+    ///
+    /// Please be aware that custom VoIP versions don't have a defined ordering in the Matrix
+    /// specification.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::V0, Self::V0) | (Self::V1, Self::V1) => Ordering::Equal,
+            (Self::V0, _) => Ordering::Less,
+            (_, Self::V0) => Ordering::Greater,
+            (Self::V1, _) => Ordering::Less,
+            (_, Self::V1) => Ordering::Greater,
+            (Self::_Custom(a), Self::_Custom(b)) => a.cmp(b),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for VoipVersionId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -201,6 +274,53 @@ mod tests {
         assert_eq!(from_json_value::<VoipVersionId>(json!("1")).unwrap(), VoipVersionId::V1);
     }
 
+    #[test]
+    fn ordering() {
+        assert!(VoipVersionId::V0 < VoipVersionId::V1);
+        assert!(VoipVersionId::V1 < VoipVersionId::from("io.ruma.1"));
+        assert!(VoipVersionId::from("io.ruma.1") < VoipVersionId::from("io.ruma.2"));
+        assert_eq!(
+            [VoipVersionId::V1, VoipVersionId::from("io.ruma.1"), VoipVersionId::V0]
+                .into_iter()
+                .max(),
+            Some(VoipVersionId::from("io.ruma.1"))
+        );
+    }
+
+    #[test]
+    fn negotiate_version() {
+        let supported = [VoipVersionId::V0, VoipVersionId::V1];
+
+        assert_eq!(
+            VoipVersionId::negotiate_version(&[VoipVersionId::V0, VoipVersionId::V1], &supported),
+            Some(VoipVersionId::V1)
+        );
+        assert_eq!(
+            VoipVersionId::negotiate_version(&[VoipVersionId::V0], &supported),
+            Some(VoipVersionId::V0)
+        );
+        assert_eq!(
+            VoipVersionId::negotiate_version(&[VoipVersionId::from("io.ruma.2")], &supported),
+            None
+        );
+        assert_eq!(VoipVersionId::negotiate_version(&[], &supported), None);
+    }
+
+    #[test]
+    fn negotiate_legacy_string_version_0() {
+        let legacy_v0 = from_json_value::<VoipVersionId>(json!("0")).unwrap();
+        assert_ne!(legacy_v0, VoipVersionId::V0);
+
+        assert_eq!(
+            VoipVersionId::negotiate_version(&[legacy_v0.clone()], &[VoipVersionId::V0]),
+            Some(VoipVersionId::V0)
+        );
+        assert_eq!(
+            VoipVersionId::negotiate_version(&[VoipVersionId::V0], &[legacy_v0]),
+            Some(VoipVersionId::V0)
+        );
+    }
+
     #[test]
     fn serialize_custom_string() {
         let version = VoipVersionId::from("io.ruma.1");
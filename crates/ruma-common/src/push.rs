@@ -982,12 +982,14 @@ mod tests {
         condition::{
             PushCondition, PushConditionPowerLevelsCtx, PushConditionRoomCtx, RoomMemberCountIs,
         },
-        AnyPushRule, ConditionalPushRule, PatternedPushRule, Ruleset, SimplePushRule,
+        AnyPushRule, ConditionalPushRule, InsertPushRuleError, NewConditionalPushRule, NewPushRule,
+        NewSimplePushRule, PatternedPushRule, RemovePushRuleError, RuleKind, Ruleset,
+        SimplePushRule,
     };
     use crate::{
         owned_room_id, owned_user_id,
         power_levels::NotificationPowerLevels,
-        push::{PredefinedContentRuleId, PredefinedOverrideRuleId},
+        push::{PredefinedContentRuleId, PredefinedOverrideRuleId, PredefinedUnderrideRuleId},
         serde::Raw,
         user_id,
     };
@@ -1855,4 +1857,165 @@ mod tests {
             PredefinedOverrideRuleId::InviteForMe.as_ref()
         );
     }
+
+    #[test]
+    fn insert_user_rule() {
+        let mut set = Ruleset::server_default(user_id!("@jolly_jumper:server.name"));
+
+        set.insert(
+            NewPushRule::Override(NewConditionalPushRule::new(
+                "my.rule".to_owned(),
+                vec![PushCondition::EventMatch {
+                    key: "type".into(),
+                    pattern: "m.call.invite".into(),
+                }],
+                vec![Action::Notify],
+            )),
+            None,
+            None,
+        )
+        .unwrap();
+
+        // The rule is inserted after `.m.rule.master`.
+        let mut override_ids = set.override_.iter().map(|rule| rule.rule_id.as_str());
+        assert_eq!(override_ids.next(), Some(PredefinedOverrideRuleId::Master.as_ref()));
+        assert_eq!(override_ids.next(), Some("my.rule"));
+
+        // Replacing the rule keeps whether it is enabled.
+        set.set_enabled(RuleKind::Override, "my.rule", false).unwrap();
+        set.insert(
+            NewPushRule::Override(NewConditionalPushRule::new(
+                "my.rule".to_owned(),
+                vec![],
+                vec![],
+            )),
+            None,
+            None,
+        )
+        .unwrap();
+        let rule = set.get(RuleKind::Override, "my.rule").unwrap();
+        assert!(!rule.enabled());
+        assert!(rule.actions().is_empty());
+    }
+
+    #[test]
+    fn insert_invalid_rule() {
+        let mut set = Ruleset::server_default(user_id!("@jolly_jumper:server.name"));
+
+        assert_matches!(
+            set.insert(
+                NewPushRule::Room(NewSimplePushRule::new(
+                    owned_room_id!("!far_west:server.name"),
+                    vec![Action::Notify],
+                )),
+                None,
+                None,
+            ),
+            Ok(())
+        );
+
+        assert_matches!(
+            set.insert(
+                NewPushRule::Override(NewConditionalPushRule::new(
+                    ".m.rule.my_rule".to_owned(),
+                    vec![],
+                    vec![],
+                )),
+                None,
+                None,
+            ),
+            Err(InsertPushRuleError::ServerDefaultRuleId)
+        );
+        assert_matches!(
+            set.insert(
+                NewPushRule::Override(NewConditionalPushRule::new(
+                    "my/rule".to_owned(),
+                    vec![],
+                    vec![],
+                )),
+                None,
+                None,
+            ),
+            Err(InsertPushRuleError::InvalidRuleId)
+        );
+        assert_matches!(
+            set.insert(
+                NewPushRule::Override(NewConditionalPushRule::new(
+                    "my.rule".to_owned(),
+                    vec![],
+                    vec![],
+                )),
+                Some(PredefinedOverrideRuleId::Master.as_ref()),
+                None,
+            ),
+            Err(InsertPushRuleError::RelativeToServerDefaultRule)
+        );
+        assert_matches!(
+            set.insert(
+                NewPushRule::Override(NewConditionalPushRule::new(
+                    "my.rule".to_owned(),
+                    vec![],
+                    vec![],
+                )),
+                Some("unknown.rule"),
+                None,
+            ),
+            Err(InsertPushRuleError::UnknownRuleId)
+        );
+    }
+
+    #[test]
+    fn update_server_default_rule() {
+        let mut set = Ruleset::server_default(user_id!("@jolly_jumper:server.name"));
+        let master_rule_id = PredefinedOverrideRuleId::Master.as_ref();
+
+        // Server-default rules can be enabled or disabled, and their actions can be changed.
+        set.set_enabled(RuleKind::Override, master_rule_id, true).unwrap();
+        set.set_actions(RuleKind::Override, master_rule_id, vec![Action::Notify]).unwrap();
+
+        let rule = set.get(RuleKind::Override, master_rule_id).unwrap();
+        assert!(rule.enabled());
+        assert_matches!(rule.actions(), [Action::Notify]);
+        assert!(rule.is_server_default());
+
+        // Unknown rules can't be updated.
+        set.set_enabled(RuleKind::Underride, master_rule_id, true).unwrap_err();
+        set.set_actions(RuleKind::Override, "unknown.rule", vec![]).unwrap_err();
+    }
+
+    #[test]
+    fn remove_rule() {
+        let mut set = Ruleset::server_default(user_id!("@jolly_jumper:server.name"));
+        set.insert(
+            NewPushRule::Override(NewConditionalPushRule::new(
+                "my.rule".to_owned(),
+                vec![],
+                vec![Action::Notify],
+            )),
+            None,
+            None,
+        )
+        .unwrap();
+
+        // Server-default rules can't be removed.
+        assert_matches!(
+            set.remove(RuleKind::Override, PredefinedOverrideRuleId::Master),
+            Err(RemovePushRuleError::ServerDefault)
+        );
+        assert_matches!(
+            set.remove(RuleKind::Underride, PredefinedUnderrideRuleId::Message),
+            Err(RemovePushRuleError::ServerDefault)
+        );
+        assert!(set.get(RuleKind::Override, PredefinedOverrideRuleId::Master).is_some());
+
+        // Unknown rules can't be removed.
+        assert_matches!(
+            set.remove(RuleKind::Underride, "my.rule"),
+            Err(RemovePushRuleError::NotFound)
+        );
+
+        // User-defined rules can be removed.
+        set.remove(RuleKind::Override, "my.rule").unwrap();
+        assert!(set.get(RuleKind::Override, "my.rule").is_none());
+    }
 }
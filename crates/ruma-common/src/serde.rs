@@ -19,12 +19,14 @@ pub mod single_element_seq;
 mod strings;
 pub mod test;
 
+#[cfg(feature = "serde-path-to-error")]
+pub use self::raw::PathDeserializationError;
 pub use self::{
-    base64::{Base64, Base64DecodeError},
+    base64::{Base64, Base64DecodeError, Base64Writer},
     buf::{json_to_buf, slice_to_buf},
     can_be_empty::{is_empty, CanBeEmpty},
     cow::deserialize_cow_str,
-    raw::Raw,
+    raw::{deserialize_all, Raw},
     strings::{
         btreemap_deserialize_v1_powerlevel_values, deserialize_as_number_or_string,
         deserialize_as_optional_number_or_string, deserialize_v1_powerlevel, empty_string_as_none,
@@ -1,9 +1,14 @@
 //! Transparent base64 encoding / decoding as part of (de)serialization.
 
-use std::{fmt, marker::PhantomData};
+use std::{
+    fmt,
+    io::{self, Write},
+    marker::PhantomData,
+};
 
 use base64::{
     engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    write::EncoderWriter,
     Engine,
 };
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
@@ -97,6 +102,59 @@ impl<C: Base64Config> Base64<C> {
     pub fn parse(encoded: impl AsRef<[u8]>) -> Result<Self, Base64DecodeError> {
         Self::ENGINE.decode(encoded).map(Self::new).map_err(Base64DecodeError)
     }
+
+    /// Decode some base64-encoded data and append the result to the given buffer.
+    ///
+    /// This allows to reuse the same buffer to decode several inputs. If decoding fails, the
+    /// buffer is left unchanged.
+    pub fn decode_into(
+        encoded: impl AsRef<[u8]>,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Base64DecodeError> {
+        let len = out.len();
+
+        Self::ENGINE.decode_vec(encoded, out).map_err(|error| {
+            out.truncate(len);
+            Base64DecodeError(error)
+        })
+    }
+}
+
+/// A writer that base64-encodes the bytes written to it incrementally.
+///
+/// The encoded data is written to the inner writer as unpadded base64, using the character set of
+/// the config `C`. [`Base64Writer::finish()`] must be called to write the last bytes.
+pub struct Base64Writer<C: Base64Config, W: Write> {
+    inner: EncoderWriter<'static, GeneralPurpose, W>,
+    _phantom_conf: PhantomData<fn(C) -> C>,
+}
+
+impl<C: Base64Config, W: Write> Base64Writer<C, W> {
+    /// Create a `Base64Writer` that writes the encoded data to the given writer.
+    pub fn new(writer: W) -> Self {
+        Self { inner: EncoderWriter::new(writer, &Base64::<C>::ENGINE), _phantom_conf: PhantomData }
+    }
+
+    /// Encode and write the remaining bytes, and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.finish()
+    }
+}
+
+impl<C: Base64Config, W: Write> Write for Base64Writer<C, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<C: Base64Config, W: Write> fmt::Debug for Base64Writer<C, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Base64Writer").finish_non_exhaustive()
+    }
 }
 
 impl<C: Base64Config, B: AsRef<[u8]>> fmt::Debug for Base64<C, B> {
@@ -150,7 +208,9 @@ impl std::error::Error for Base64DecodeError {}
 
 #[cfg(test)]
 mod tests {
-    use super::{Base64, Standard};
+    use std::io::Write;
+
+    use super::{Base64, Base64Writer, Standard, UrlSafe};
 
     #[test]
     fn slightly_malformed_base64() {
@@ -162,4 +222,32 @@ mod tests {
         Base64::<Standard>::parse(INPUT_WITH_PADDING)
             .expect("We should be able to decode padded Base64");
     }
+
+    #[test]
+    fn decode_into_buffer() {
+        let mut buf = b"prefix".to_vec();
+        Base64::<Standard>::decode_into("aGVsbG8", &mut buf).unwrap();
+        assert_eq!(buf, b"prefixhello");
+
+        Base64::<Standard>::decode_into("not base64!", &mut buf).unwrap_err();
+        assert_eq!(buf, b"prefixhello");
+    }
+
+    #[test]
+    fn streamed_encoding() {
+        let data: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = Base64Writer::<Standard, _>::new(Vec::new());
+        // Use a chunk size that is not a multiple of 3 to test the internal buffering.
+        for chunk in data.chunks(1000) {
+            writer.write_all(chunk).unwrap();
+        }
+        let encoded = writer.finish().unwrap();
+        assert_eq!(String::from_utf8(encoded).unwrap(), Base64::<Standard, _>::new(&data).encode());
+
+        let mut writer = Base64Writer::<UrlSafe, _>::new(Vec::new());
+        writer.write_all(&data).unwrap();
+        let encoded = writer.finish().unwrap();
+        assert_eq!(String::from_utf8(encoded).unwrap(), Base64::<UrlSafe, _>::new(&data).encode());
+    }
 }
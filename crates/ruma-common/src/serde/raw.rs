@@ -83,8 +83,8 @@ impl<T> Raw<T> {
 
     /// Try to access a given field inside this `Raw`, assuming it contains an object.
     ///
-    /// Returns `Err(_)` when the contained value is not an object, or the field exists but is fails
-    /// to deserialize to the expected type.
+    /// Returns `Err(_)` when the contained value is not an object, or the field exists but fails to
+    /// deserialize to the expected type.
     ///
     /// Returns `Ok(None)` when the field doesn't exist or is `null`.
     ///
@@ -153,7 +153,7 @@ impl<T> Raw<T> {
             type Value = Option<T>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str("a string")
+                formatter.write_str("an object")
             }
 
             fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
@@ -163,7 +163,7 @@ impl<T> Raw<T> {
                 let mut res = None;
                 while let Some(is_right_field) = map.next_key_seed(Field(self.field_name))? {
                     if is_right_field {
-                        res = Some(map.next_value()?);
+                        res = map.next_value()?;
                     } else {
                         map.next_value::<IgnoredAny>()?;
                     }
@@ -193,6 +193,100 @@ impl<T> Raw<T> {
         serde_json::from_str(self.json.get())
     }
 
+    /// Try to deserialize the JSON as the expected type, and get the path to the value that failed
+    /// to deserialize on error.
+    ///
+    /// The path is only tracked through the types whose `Deserialize` implementation forwards to
+    /// the deserializer. Types that first deserialize the JSON to a buffer, like most event types
+    /// and event content types with a `msgtype` or similar discriminator in `ruma-events`, report
+    /// the path up to that type, and the fields they deserialize from the buffer are not part of
+    /// the path. The message of the inner error still describes the failure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ruma_common::serde::Raw;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Content {
+    ///     relates_to: RelatesTo,
+    /// }
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct RelatesTo {
+    ///     event_id: String,
+    /// }
+    ///
+    /// let raw =
+    ///     Raw::<Content>::from_json_string(r#"{ "relates_to": { "event_id": 42 } }"#.to_owned())
+    ///         .unwrap();
+    /// let error = raw.deserialize_with_path().unwrap_err();
+    /// assert_eq!(error.path(), "relates_to.event_id");
+    /// ```
+    #[cfg(feature = "serde-path-to-error")]
+    pub fn deserialize_with_path<'a>(&'a self) -> Result<T, PathDeserializationError>
+    where
+        T: Deserialize<'a>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_str(self.json.get());
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|error| {
+            PathDeserializationError { path: error.path().to_string(), inner: error.into_inner() }
+        })
+    }
+
+    /// Get the JSON of this `Raw` as an indented string, for debugging.
+    ///
+    /// The order of the fields of objects is preserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ruma_common::serde::Raw;
+    ///
+    /// let raw = Raw::<()>::from_json_string(r#"{"type":"m.tag","content":{}}"#.to_owned())?;
+    /// assert_eq!(raw.json_pretty(), "{\n  \"type\": \"m.tag\",\n  \"content\": {}\n}");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn json_pretty(&self) -> String {
+        let mut pretty = String::with_capacity(self.json.get().len());
+        let mut indent = 0;
+        let mut tokens = JsonTokens(self.json.get()).peekable();
+
+        let push_newline = |pretty: &mut String, indent: usize| {
+            pretty.push('\n');
+            pretty.extend(std::iter::repeat("  ").take(indent));
+        };
+
+        while let Some(token) = tokens.next() {
+            match token {
+                "{" | "[" => {
+                    pretty.push_str(token);
+
+                    if tokens.next_if(|next| matches!(*next, "}" | "]")).is_some() {
+                        pretty.push_str(if token == "{" { "}" } else { "]" });
+                    } else {
+                        indent += 1;
+                        push_newline(&mut pretty, indent);
+                    }
+                }
+                "}" | "]" => {
+                    indent -= 1;
+                    push_newline(&mut pretty, indent);
+                    pretty.push_str(token);
+                }
+                "," => {
+                    pretty.push(',');
+                    push_newline(&mut pretty, indent);
+                }
+                ":" => pretty.push_str(": "),
+                _ => pretty.push_str(token),
+            }
+        }
+
+        pretty
+    }
+
     /// Turns `Raw<T>` into `Raw<U>` without changing the underlying JSON.
     ///
     /// This is useful for turning raw specific event types into raw event enum types.
@@ -208,16 +302,161 @@ impl<T> Raw<T> {
     }
 }
 
+/// Try to deserialize all the given JSON values as the expected type.
+///
+/// Contrary to collecting the results of [`Raw::deserialize()`] into a `Result<Vec<T>, _>`, the
+/// values that fail to deserialize don't prevent the others from being returned.
+///
+/// Returns the successfully deserialized values, in order, and the index in `raws` and the error
+/// of each value that failed to deserialize.
+///
+/// # Example
+///
+/// ```
+/// use ruma_common::serde::{deserialize_all, Raw};
+///
+/// let raws = vec![
+///     Raw::<u8>::from_json_string("1".to_owned()).unwrap(),
+///     Raw::from_json_string(r#""two""#.to_owned()).unwrap(),
+///     Raw::from_json_string("3".to_owned()).unwrap(),
+/// ];
+///
+/// let (values, errors) = deserialize_all(&raws);
+/// assert_eq!(values, [1, 3]);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0, 1);
+/// ```
+pub fn deserialize_all<'a, T>(raws: &'a [Raw<T>]) -> (Vec<T>, Vec<(usize, serde_json::Error)>)
+where
+    T: Deserialize<'a>,
+{
+    let mut values = Vec::with_capacity(raws.len());
+    let mut errors = Vec::new();
+
+    for (index, raw) in raws.iter().enumerate() {
+        match raw.deserialize() {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push((index, error)),
+        }
+    }
+
+    (values, errors)
+}
+
 impl<T> Clone for Raw<T> {
     fn clone(&self) -> Self {
         Self::from_json(self.json.clone())
     }
 }
 
+/// The `Debug` implementation of `Raw` shows the JSON without whitespace, truncated after 1000
+/// bytes.
+///
+/// The values of fields that are likely to contain secrets, like `access_token` or `password`, are
+/// replaced by `"<redacted>"`.
 impl<T> Debug for Raw<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use std::any::type_name;
-        f.debug_struct(&format!("Raw::<{}>", type_name::<T>())).field("json", &self.json).finish()
+        f.debug_struct(&format!("Raw::<{}>", type_name::<T>()))
+            .field("json", &DebugJson(self.json.get()))
+            .finish()
+    }
+}
+
+/// The maximum length of the JSON in the `Debug` output of `Raw`.
+const MAX_DEBUG_JSON_LEN: usize = 1000;
+
+/// The fields whose string values are redacted in the `Debug` output of `Raw`.
+const SECRET_FIELDS: &[&str] = &["access_token", "refresh_token", "password", "token"];
+
+/// Helper to show JSON in the `Debug` output of `Raw`.
+struct DebugJson<'a>(&'a str);
+
+impl Debug for DebugJson<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut compact = String::with_capacity(self.0.len().min(MAX_DEBUG_JSON_LEN));
+        let mut tokens = JsonTokens(self.0).peekable();
+        let mut redact_value = false;
+
+        while let Some(token) = tokens.next() {
+            if compact.len() > MAX_DEBUG_JSON_LEN {
+                break;
+            }
+
+            if token.starts_with('"') {
+                if tokens.peek() == Some(&":") {
+                    redact_value = SECRET_FIELDS.contains(&&token[1..token.len() - 1]);
+                    compact.push_str(token);
+                    continue;
+                }
+
+                if redact_value {
+                    compact.push_str("\"<redacted>\"");
+                } else {
+                    compact.push_str(token);
+                }
+            } else {
+                compact.push_str(token);
+            }
+
+            if token != ":" {
+                redact_value = false;
+            }
+        }
+
+        if compact.len() > MAX_DEBUG_JSON_LEN {
+            let mut end = MAX_DEBUG_JSON_LEN;
+            while !compact.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            f.write_str(&compact[..end])?;
+            f.write_str("…")
+        } else {
+            f.write_str(&compact)
+        }
+    }
+}
+
+/// Iterator over the tokens of a valid JSON string, without the whitespace between them.
+///
+/// Strings are returned with their quotes, punctuation characters are returned one by one, and
+/// other scalar values are returned whole.
+struct JsonTokens<'a>(&'a str);
+
+impl<'a> Iterator for JsonTokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let json = self.0.trim_start_matches([' ', '\t', '\n', '\r']);
+        let bytes = json.as_bytes();
+
+        let len = match bytes.first()? {
+            b'{' | b'}' | b'[' | b']' | b',' | b':' => 1,
+            b'"' => {
+                let mut escaped = false;
+                let end = bytes[1..].iter().position(|&byte| {
+                    let is_end = byte == b'"' && !escaped;
+                    escaped = byte == b'\\' && !escaped;
+                    is_end
+                });
+
+                end.map_or(bytes.len(), |end| end + 2)
+            }
+            _ => bytes
+                .iter()
+                .position(|byte| {
+                    matches!(
+                        byte,
+                        b' ' | b'\t' | b'\n' | b'\r' | b'{' | b'}' | b'[' | b']' | b',' | b':'
+                    )
+                })
+                .unwrap_or(bytes.len()),
+        };
+
+        let (token, rest) = json.split_at(len);
+        self.0 = rest;
+        Some(token)
     }
 }
 
@@ -239,12 +478,60 @@ impl<T> Serialize for Raw<T> {
     }
 }
 
+/// An error that occurred while deserializing a [`Raw`] with [`Raw::deserialize_with_path()`].
+#[cfg(feature = "serde-path-to-error")]
+#[derive(Debug)]
+pub struct PathDeserializationError {
+    path: String,
+    inner: serde_json::Error,
+}
+
+#[cfg(feature = "serde-path-to-error")]
+impl PathDeserializationError {
+    /// The path to the value that failed to deserialize.
+    ///
+    /// The path uses a `.` to access fields and `[index]` to access the items of arrays, like
+    /// `content.list[1].value`. It is `.` if the error occurred at the root of the JSON, or inside
+    /// a type that doesn't track the path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The error that occurred.
+    pub fn inner(&self) -> &serde_json::Error {
+        &self.inner
+    }
+
+    /// Consume this error and get the error that occurred.
+    pub fn into_inner(self) -> serde_json::Error {
+        self.inner
+    }
+}
+
+#[cfg(feature = "serde-path-to-error")]
+impl fmt::Display for PathDeserializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path == "." {
+            fmt::Display::fmt(&self.inner, f)
+        } else {
+            write!(f, "{}: {}", self.path, self.inner)
+        }
+    }
+}
+
+#[cfg(feature = "serde-path-to-error")]
+impl std::error::Error for PathDeserializationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::Deserialize;
     use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
 
-    use super::Raw;
+    use super::{deserialize_all, Raw};
 
     #[test]
     fn get_field() -> serde_json::Result<()> {
@@ -266,4 +553,118 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "serde-path-to-error")]
+    #[test]
+    fn deserialize_with_path() {
+        #[derive(Debug, Deserialize)]
+        struct A {
+            #[allow(dead_code)]
+            list: Vec<B>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct B {
+            #[allow(dead_code)]
+            value: u8,
+        }
+
+        let raw: Raw<A> =
+            from_json_str(r#"{ "list": [{ "value": 1 }, { "value": "2" }] }"#).unwrap();
+        let error = raw.deserialize_with_path().unwrap_err();
+        assert_eq!(error.path(), "list[1].value");
+        assert!(error.inner().is_data());
+        assert!(error.to_string().starts_with("list[1].value: invalid type: string \"2\""));
+
+        let raw: Raw<A> = from_json_str(r#"{ "list": [{ "value": 1 }] }"#).unwrap();
+        assert_eq!(raw.deserialize_with_path().unwrap().list.len(), 1);
+    }
+
+    #[test]
+    fn get_field_null_or_not_object() -> serde_json::Result<()> {
+        #[derive(Deserialize)]
+        struct Event {}
+
+        let raw: Raw<Event> = from_json_str(r#"{ "room_id": null, "event_id": "$ev" }"#)?;
+        assert_eq!(raw.get_field::<String>("room_id")?, None);
+        assert_eq!(raw.get_field::<String>("event_id")?.as_deref(), Some("$ev"));
+
+        let raw: Raw<Event> = from_json_str("[1, 2]")?;
+        raw.get_field::<String>("event_id").unwrap_err();
+
+        Ok(())
+    }
+
+    #[test]
+    fn debug() {
+        let raw: Raw<()> = from_json_str(
+            r#"{
+                "type": "m.room.message",
+                "content": { "body": "a \" b\\", "list": [1, true, null] }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{raw:?}"),
+            r#"Raw::<()> { json: {"type":"m.room.message","content":{"body":"a \" b\\","list":[1,true,null]}} }"#
+        );
+
+        let raw: Raw<()> = from_json_str(
+            r#"{ "user": "@alice:localhost", "access_token": "secret", "auth": { "password": "secret", "token": 5 } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{raw:?}"),
+            r#"Raw::<()> { json: {"user":"@alice:localhost","access_token":"<redacted>","auth":{"password":"<redacted>","token":5}} }"#
+        );
+
+        let raw =
+            Raw::<()>::from_json_string(format!(r#"{{"body":"{}"}}"#, "é".repeat(1000))).unwrap();
+        let debug = format!("{raw:?}");
+        assert!(debug.ends_with("é… }"));
+        assert!(debug.len() < 1100);
+    }
+
+    #[test]
+    fn json_pretty() {
+        let raw: Raw<()> =
+            from_json_str(r#"{ "b": [1, [], {}, { "c": "d , e" }], "a": null }"#).unwrap();
+        assert_eq!(
+            raw.json_pretty(),
+            r#"{
+  "b": [
+    1,
+    [],
+    {},
+    {
+      "c": "d , e"
+    }
+  ],
+  "a": null
+}"#
+        );
+
+        let raw: Raw<()> = from_json_str(" 5 ").unwrap();
+        assert_eq!(raw.json_pretty(), "5");
+    }
+
+    #[test]
+    fn deserialize_all_partial_success() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct A {
+            b: u8,
+        }
+
+        let raws: Vec<Raw<A>> =
+            from_json_str(r#"[{ "b": 1 }, { "b": "2" }, { "c": 3 }, { "b": 4 }]"#).unwrap();
+
+        let (values, errors) = deserialize_all(&raws);
+        assert_eq!(values, [A { b: 1 }, A { b: 4 }]);
+        assert_eq!(errors.iter().map(|(index, _)| *index).collect::<Vec<_>>(), [1, 2]);
+        assert!(errors[1].1.to_string().contains("missing field `b`"));
+
+        let (values, errors) = deserialize_all::<A>(&[]);
+        assert!(values.is_empty());
+        assert!(errors.is_empty());
+    }
 }
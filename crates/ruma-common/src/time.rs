@@ -72,6 +72,18 @@ impl fmt::Debug for MilliSecondsSinceUnixEpoch {
     }
 }
 
+impl TryFrom<SecondsSinceUnixEpoch> for MilliSecondsSinceUnixEpoch {
+    type Error = js_int::TryFromIntError;
+
+    /// Converts the timestamp to milliseconds.
+    ///
+    /// Fails if the timestamp is too large to be represented in milliseconds.
+    fn try_from(value: SecondsSinceUnixEpoch) -> Result<Self, Self::Error> {
+        // `UInt::MAX * 1000` fits in a `u64`.
+        UInt::try_from(u64::from(value.0) * 1000).map(Self)
+    }
+}
+
 /// A timestamp represented as the number of seconds since the unix epoch.
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 #[allow(clippy::exhaustive_structs)]
@@ -79,7 +91,7 @@ impl fmt::Debug for MilliSecondsSinceUnixEpoch {
 pub struct SecondsSinceUnixEpoch(pub UInt);
 
 impl SecondsSinceUnixEpoch {
-    /// Creates a new `MilliSecondsSinceUnixEpoch` from the given `SystemTime`, if it is not before
+    /// Creates a new `SecondsSinceUnixEpoch` from the given `SystemTime`, if it is not before
     /// the unix epoch, or too large to be represented.
     pub fn from_system_time(time: SystemTime) -> Option<Self> {
         let duration = time.duration_since(UNIX_EPOCH).ok()?;
@@ -107,6 +119,13 @@ impl SecondsSinceUnixEpoch {
     }
 }
 
+impl From<MilliSecondsSinceUnixEpoch> for SecondsSinceUnixEpoch {
+    /// Converts the timestamp to seconds, truncating the milliseconds.
+    fn from(value: MilliSecondsSinceUnixEpoch) -> Self {
+        Self(value.as_secs())
+    }
+}
+
 impl fmt::Debug for SecondsSinceUnixEpoch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match OffsetDateTime::from_unix_timestamp(i64::from(self.0)) {
@@ -143,7 +162,7 @@ fn f64_to_uint(val: f64) -> UInt {
 mod tests {
     use std::time::{Duration, UNIX_EPOCH};
 
-    use js_int::uint;
+    use js_int::{uint, UInt};
     use serde::{Deserialize, Serialize};
     use serde_json::json;
 
@@ -175,6 +194,56 @@ mod tests {
         assert_eq!(serde_json::to_value(request).unwrap(), json!({ "millis": 2000, "secs": 0 }));
     }
 
+    #[test]
+    fn convert_between_granularities() {
+        let millis = MilliSecondsSinceUnixEpoch(uint!(1_500_999));
+        let secs = SecondsSinceUnixEpoch::from(millis);
+        assert_eq!(secs, SecondsSinceUnixEpoch(uint!(1_500)));
+
+        let millis = MilliSecondsSinceUnixEpoch::try_from(secs).unwrap();
+        assert_eq!(millis, MilliSecondsSinceUnixEpoch(uint!(1_500_000)));
+        assert_eq!(millis.to_system_time(), secs.to_system_time());
+        assert_eq!(millis.to_system_time(), Some(UNIX_EPOCH + Duration::from_secs(1_500)));
+
+        MilliSecondsSinceUnixEpoch::try_from(SecondsSinceUnixEpoch(UInt::MAX)).unwrap_err();
+    }
+
+    #[test]
+    fn system_time_roundtrip() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_500_999);
+
+        let millis = MilliSecondsSinceUnixEpoch::from_system_time(time).unwrap();
+        assert_eq!(millis.to_system_time(), Some(time));
+
+        let secs = SecondsSinceUnixEpoch::from_system_time(time).unwrap();
+        assert_eq!(secs.to_system_time(), Some(UNIX_EPOCH + Duration::from_secs(1_500)));
+
+        assert_eq!(
+            MilliSecondsSinceUnixEpoch::from_system_time(UNIX_EPOCH - Duration::from_secs(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn system_time_out_of_range() {
+        let before_epoch = UNIX_EPOCH - Duration::from_millis(1);
+        assert_eq!(MilliSecondsSinceUnixEpoch::from_system_time(before_epoch), None);
+        assert_eq!(SecondsSinceUnixEpoch::from_system_time(before_epoch), None);
+
+        // `UInt::MAX` milliseconds is more than 285,000 years after the unix epoch, which is still
+        // representable by `SystemTime`.
+        let max_millis = MilliSecondsSinceUnixEpoch(UInt::MAX);
+        let max_time = max_millis.to_system_time().unwrap();
+        assert_eq!(MilliSecondsSinceUnixEpoch::from_system_time(max_time), Some(max_millis));
+
+        let after_max_time = max_time + Duration::from_millis(1);
+        assert_eq!(MilliSecondsSinceUnixEpoch::from_system_time(after_max_time), None);
+        assert_eq!(
+            SecondsSinceUnixEpoch::from_system_time(after_max_time),
+            Some(SecondsSinceUnixEpoch(UInt::MAX / uint!(1000)))
+        );
+    }
+
     #[test]
     fn debug_s() {
         let seconds = SecondsSinceUnixEpoch(uint!(0));
@@ -7,6 +7,7 @@ mod header_override;
 mod manual_endpoint_impl;
 mod no_fields;
 mod optional_headers;
+mod raw_body;
 mod required_headers;
 mod ruma_api;
 mod ruma_api_macros;
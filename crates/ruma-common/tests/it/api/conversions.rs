@@ -1,10 +1,10 @@
 #![allow(clippy::exhaustive_structs)]
 
-use http::header::CONTENT_TYPE;
+use http::header::{AUTHORIZATION, CONTENT_TYPE};
 use ruma_common::{
     api::{
         request, response, IncomingRequest as _, MatrixVersion, Metadata, OutgoingRequest as _,
-        OutgoingRequestAppserviceExt, SendAccessToken,
+        OutgoingRequestAppserviceExt, PathSegment, SendAccessToken,
     },
     metadata, owned_user_id, user_id, OwnedUserId,
 };
@@ -80,6 +80,101 @@ fn request_serde() {
     assert_eq!(req.user, req2.user);
 }
 
+#[test]
+fn request_max_query_length_ignores_post() {
+    let req = Request {
+        hello: "hi".to_owned(),
+        world: "test".to_owned(),
+        q1: "query_param_special_chars %/&@!".to_owned(),
+        q2: 55,
+        bar: "barVal".to_owned(),
+        user: owned_user_id!("@bazme:ruma.io"),
+    };
+
+    let http_req = req
+        .try_into_http_request_with_max_query_length::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+            0,
+        )
+        .unwrap();
+    assert!(http_req.uri().query().is_some());
+}
+
+#[test]
+fn request_from_parts() {
+    let mut headers = http::HeaderMap::new();
+    headers.insert(CONTENT_TYPE, http::HeaderValue::from_static("test"));
+
+    let req = Request::try_from_parts(
+        &["barVal", "@bazme:ruma.io"],
+        "q1=query_param_special_chars+%25%2F%26%40%21&q2=55",
+        &headers,
+        br#"{"hello":"hi"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(req.hello, "hi");
+    assert_eq!(req.world, "test");
+    assert_eq!(req.q1, "query_param_special_chars %/&@!");
+    assert_eq!(req.q2, 55);
+    assert_eq!(req.bar, "barVal");
+    assert_eq!(req.user, "@bazme:ruma.io");
+
+    // Missing header.
+    Request::try_from_parts(
+        &["barVal", "@bazme:ruma.io"],
+        "q1=foo&q2=55",
+        &http::HeaderMap::new(),
+        br#"{"hello":"hi"}"#,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn request_path_spec() {
+    assert_eq!(
+        Request::path_spec(),
+        [
+            PathSegment::Literal("_matrix"),
+            PathSegment::Literal("foo"),
+            PathSegment::Parameter("bar"),
+            PathSegment::Parameter("user"),
+        ]
+    );
+}
+
+#[test]
+fn request_with_base_url_path_prefix() {
+    let req = Request {
+        hello: "hi".to_owned(),
+        world: "test".to_owned(),
+        q1: "query_param_special_chars %/&@!".to_owned(),
+        q2: 55,
+        bar: "barVal".to_owned(),
+        user: owned_user_id!("@bazme:ruma.io"),
+    };
+
+    for base_url in ["https://homeserver.tld/matrix", "https://homeserver.tld/matrix/"] {
+        let http_req = req
+            .clone()
+            .try_into_http_request::<Vec<u8>>(
+                base_url,
+                SendAccessToken::None,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+        assert_eq!(http_req.uri().host(), Some("homeserver.tld"));
+        assert_eq!(http_req.uri().path(), "/matrix/_matrix/foo/barVal/@bazme:ruma.io");
+        assert_eq!(
+            http_req.uri().query(),
+            Some("q1=query_param_special_chars+%25%2F%26%40%21&q2=55")
+        );
+    }
+}
+
 #[test]
 fn invalid_uri_should_not_panic() {
     let req = Request {
@@ -128,8 +223,38 @@ fn request_with_user_id_serde() {
     );
 }
 
+#[test]
+fn request_as_user_serde() {
+    let req = Request {
+        hello: "hi".to_owned(),
+        world: "test".to_owned(),
+        q1: "query_param_special_chars %/&@!".to_owned(),
+        q2: 55,
+        bar: "barVal".to_owned(),
+        user: owned_user_id!("@bazme:ruma.io"),
+    };
+
+    let user_id = user_id!("@_virtual_:ruma.io");
+    let http_req = req
+        .try_into_http_request_as_user::<Vec<u8>>(
+            "https://homeserver.tld",
+            "as_token",
+            user_id,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+
+    let query = http_req.uri().query().unwrap();
+
+    assert_eq!(
+        query,
+        "q1=query_param_special_chars+%25%2F%26%40%21&q2=55&user_id=%40_virtual_%3Aruma.io"
+    );
+    assert_eq!(http_req.headers().get(AUTHORIZATION).unwrap(), "Bearer as_token");
+}
+
 mod without_query {
-    use http::header::CONTENT_TYPE;
+    use http::header::{AUTHORIZATION, CONTENT_TYPE};
     use ruma_common::{
         api::{
             request, response, MatrixVersion, Metadata, OutgoingRequestAppserviceExt,
@@ -197,4 +322,256 @@ mod without_query {
 
         assert_eq!(query, "user_id=%40_virtual_%3Aruma.io");
     }
+
+    #[test]
+    fn request_without_query_as_user_serde() {
+        let req = Request {
+            hello: "hi".to_owned(),
+            world: "test".to_owned(),
+            bar: "barVal".to_owned(),
+            user: owned_user_id!("@bazme:ruma.io"),
+        };
+
+        let user_id = user_id!("@_virtual_:ruma.io");
+        let http_req = req
+            .try_into_http_request_as_user::<Vec<u8>>(
+                "https://homeserver.tld",
+                "as_token",
+                user_id,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+        let query = http_req.uri().query().unwrap();
+
+        assert_eq!(query, "user_id=%40_virtual_%3Aruma.io");
+        assert_eq!(http_req.headers().get(AUTHORIZATION).unwrap(), "Bearer as_token");
+    }
+}
+
+mod query_all_typed_pairs {
+    use assert_matches2::assert_matches;
+    use ruma_common::{
+        api::{
+            error::IntoHttpError, request, response, IncomingRequest as _, MatrixVersion, Metadata,
+            OutgoingRequest as _, SendAccessToken,
+        },
+        metadata, owned_room_id, OwnedRoomId,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/foo",
+        }
+    };
+
+    /// Request type for the `my_endpoint` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(query_all)]
+        pub rooms: Vec<(String, OwnedRoomId)>,
+    }
+
+    /// Response type for the `my_endpoint` endpoint.
+    #[response]
+    pub struct Response {}
+
+    #[test]
+    fn request_serde() {
+        let req = Request {
+            rooms: vec![
+                ("room_id".to_owned(), owned_room_id!("!first:ruma.io")),
+                ("room_id".to_owned(), owned_room_id!("!second:ruma.io")),
+            ],
+        };
+
+        let http_req = req
+            .clone()
+            .try_into_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::None,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+        assert_eq!(
+            http_req.uri().query().unwrap(),
+            "room_id=%21first%3Aruma.io&room_id=%21second%3Aruma.io"
+        );
+
+        let req2 = Request::try_from_http_request::<_, &str>(http_req, &[]).unwrap();
+        assert_eq!(req2.rooms, req.rooms);
+    }
+
+    #[test]
+    fn request_max_query_length() {
+        let req = Request {
+            rooms: vec![
+                ("room_id".to_owned(), owned_room_id!("!first:ruma.io")),
+                ("room_id".to_owned(), owned_room_id!("!second:ruma.io")),
+            ],
+        };
+
+        let http_req = req
+            .clone()
+            .try_into_http_request_with_max_query_length::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::None,
+                &[MatrixVersion::V1_1],
+                54,
+            )
+            .unwrap();
+        assert_eq!(http_req.uri().query().unwrap().len(), 54);
+
+        assert_matches!(
+            req.try_into_http_request_with_max_query_length::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::None,
+                &[MatrixVersion::V1_1],
+                53,
+            ),
+            Err(IntoHttpError::QueryTooLong { length: 54, max_length: 53 })
+        );
+    }
+}
+
+mod renamed_query {
+    use ruma_common::{
+        api::{
+            request, response, IncomingRequest as _, MatrixVersion, Metadata, OutgoingRequest as _,
+            SendAccessToken,
+        },
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/foo",
+        }
+    };
+
+    /// Request type for the `my_endpoint` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(query)]
+        #[serde(rename = "from")]
+        pub from_token: String,
+
+        #[ruma_api(query)]
+        #[serde(rename = "dir", skip_serializing_if = "Option::is_none")]
+        pub direction: Option<String>,
+    }
+
+    /// Response type for the `my_endpoint` endpoint.
+    #[response]
+    pub struct Response {}
+
+    #[test]
+    fn request_serde() {
+        let req = Request { from_token: "s72594_4483_1934".to_owned(), direction: None };
+
+        let http_req = req
+            .clone()
+            .try_into_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::None,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+        assert_eq!(http_req.uri().query().unwrap(), "from=s72594_4483_1934");
+
+        let req2 = Request::try_from_http_request::<_, &str>(http_req, &[]).unwrap();
+        assert_eq!(req2.from_token, req.from_token);
+        assert_eq!(req2.direction, None);
+
+        let http_req = http::Request::get("https://homeserver.tld/_matrix/foo?from=abc&dir=b")
+            .body(Vec::<u8>::new())
+            .unwrap();
+        let req = Request::try_from_http_request::<_, &str>(http_req, &[]).unwrap();
+        assert_eq!(req.from_token, "abc");
+        assert_eq!(req.direction.as_deref(), Some("b"));
+    }
+}
+
+mod newtype_array_body {
+    use ruma_common::{
+        api::{
+            request, response, IncomingRequest as _, MatrixVersion, Metadata, OutgoingRequest as _,
+            SendAccessToken,
+        },
+        metadata,
+    };
+    use serde::{Deserialize, Serialize};
+
+    const METADATA: Metadata = metadata! {
+        method: POST,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/foo/:bar",
+        }
+    };
+
+    #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+    pub struct Foo {
+        pub key: String,
+    }
+
+    /// Request type for the `my_endpoint` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(path)]
+        pub bar: String,
+
+        #[ruma_api(query)]
+        pub q: String,
+
+        #[ruma_api(body)]
+        pub items: Vec<Foo>,
+    }
+
+    /// Response type for the `my_endpoint` endpoint.
+    #[response]
+    pub struct Response {}
+
+    #[test]
+    fn request_serde() {
+        let req = Request {
+            bar: "barVal".to_owned(),
+            q: "qVal".to_owned(),
+            items: vec![Foo { key: "a".to_owned() }, Foo { key: "b".to_owned() }],
+        };
+
+        let http_req = req
+            .clone()
+            .try_into_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::None,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+        assert_eq!(http_req.body(), br#"[{"key":"a"},{"key":"b"}]"#);
+
+        let req2 = Request::try_from_http_request(http_req, &["barVal"]).unwrap();
+        assert_eq!(req2.bar, req.bar);
+        assert_eq!(req2.q, req.q);
+        assert_eq!(req2.items, req.items);
+    }
+
+    #[test]
+    fn request_empty_body() {
+        let http_req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("https://homeserver.tld/_matrix/foo/barVal?q=qVal")
+            .body(Vec::<u8>::new())
+            .unwrap();
+
+        let req = Request::try_from_http_request(http_req, &["barVal"]).unwrap();
+        assert_eq!(req.items, []);
+    }
 }
@@ -1,5 +1,7 @@
+use http::header::CONTENT_TYPE;
 use ruma_common::api::{
-    MatrixVersion, OutgoingRequest as _, OutgoingResponse as _, SendAccessToken,
+    IncomingResponse as _, MatrixVersion, OutgoingRequest as _, OutgoingResponse as _,
+    SendAccessToken,
 };
 
 mod get {
@@ -96,3 +98,19 @@ fn empty_get_response_http_repr() {
     // ... even for GET requests.
     assert_eq!(http_res.body(), b"{}");
 }
+
+#[test]
+fn empty_response_from_empty_body() {
+    let http_res = http::Response::new(Vec::<u8>::new());
+
+    get::Response::try_from_http_response(http_res).unwrap();
+}
+
+#[test]
+fn empty_response_from_non_json_body() {
+    let http_res =
+        http::Response::builder().header(CONTENT_TYPE, "text/plain").body(b"OK".to_vec()).unwrap();
+
+    // The body is ignored when the response has no body fields.
+    post::Response::try_from_http_response(http_res).unwrap();
+}
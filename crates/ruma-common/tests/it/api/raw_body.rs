@@ -0,0 +1,74 @@
+use http::header::CONTENT_TYPE;
+use ruma_common::{
+    api::{request, response, IncomingResponse, Metadata, OutgoingResponse},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/my/media",
+    }
+};
+
+/// Request type for the `raw_body` endpoint.
+#[request]
+pub struct Request {}
+
+/// Response type for the `raw_body` endpoint.
+#[response]
+pub struct Response {
+    #[ruma_api(header = CONTENT_TYPE)]
+    pub content_type: Option<String>,
+
+    #[ruma_api(raw_body)]
+    pub file: Vec<u8>,
+}
+
+// The start of a PNG file, which is not valid UTF-8.
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+#[test]
+fn image_response() {
+    let http_res =
+        http::Response::builder().header(CONTENT_TYPE, "image/png").body(PNG.to_vec()).unwrap();
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.content_type.as_deref(), Some("image/png"));
+    assert_eq!(res.file, PNG);
+}
+
+#[test]
+fn text_response() {
+    let http_res = http::Response::builder()
+        .header(CONTENT_TYPE, "text/plain")
+        .body(b"Hello, world!".to_vec())
+        .unwrap();
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.content_type.as_deref(), Some("text/plain"));
+    assert_eq!(res.file, b"Hello, world!");
+}
+
+#[test]
+fn empty_response() {
+    let http_res = http::Response::new(Vec::<u8>::new());
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.content_type, None);
+    assert!(res.file.is_empty());
+}
+
+#[test]
+fn response_roundtrip() {
+    let res = Response { content_type: Some("image/png".to_owned()), file: PNG.to_vec() };
+
+    let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+    assert_eq!(http_res.headers().get(CONTENT_TYPE).unwrap(), "image/png");
+    assert_eq!(http_res.body(), PNG);
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.file, PNG);
+}
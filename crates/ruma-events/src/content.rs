@@ -93,6 +93,28 @@ pub trait PossiblyRedactedStateEventContent: EventContent<EventType = StateEvent
 pub trait ToDeviceEventContent: EventContent<EventType = ToDeviceEventType> {}
 
 /// Event content that can be deserialized with its event type.
+///
+/// This is implemented for all the event content enums, like [`AnyMessageLikeEventContent`] and
+/// [`AnyStateEventContent`], which allows to deserialize the content of an event whose type is only
+/// known at runtime:
+///
+/// ```
+/// use ruma_events::{AnyStateEventContent, EventContentFromType, StateEventType};
+/// use serde_json::value::to_raw_value as to_raw_json_value;
+///
+/// let event_type = StateEventType::RoomTopic;
+/// let json = to_raw_json_value(&serde_json::json!({ "topic": "Ruma" }))?;
+///
+/// let content = AnyStateEventContent::from_parts(&event_type.to_string(), &json)?;
+/// assert!(matches!(content, AnyStateEventContent::RoomTopic(c) if c.topic == "Ruma"));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// When the content is available as a [`Raw`], [`RawExt::deserialize_with_type()`] can be used
+/// instead.
+///
+/// [`AnyMessageLikeEventContent`]: crate::AnyMessageLikeEventContent
+/// [`AnyStateEventContent`]: crate::AnyStateEventContent
 pub trait EventContentFromType: EventContent {
     /// Constructs this event content from the given event type and JSON.
     fn from_parts(event_type: &str, content: &RawJsonValue) -> serde_json::Result<Self>;
@@ -1,5 +1,7 @@
 #![allow(clippy::exhaustive_structs)]
 
+use std::time::Duration;
+
 use as_variant::as_variant;
 use ruma_common::{
     serde::{from_raw_json_value, Raw},
@@ -15,8 +17,8 @@ use super::{
     EventContentFromType, GlobalAccountDataEventContent, MessageLikeEventContent,
     MessageLikeEventType, MessageLikeUnsigned, PossiblyRedactedStateEventContent, RedactContent,
     RedactedMessageLikeEventContent, RedactedStateEventContent, RedactedUnsigned,
-    RedactionDeHelper, RoomAccountDataEventContent, StateEventType, StaticStateEventContent,
-    ToDeviceEventContent,
+    RedactionDeHelper, RoomAccountDataEventContent, StateEventType, StateUnsigned,
+    StaticStateEventContent, ToDeviceEventContent,
 };
 
 /// A global account data event.
@@ -124,6 +126,16 @@ pub struct OriginalMessageLikeEvent<C: MessageLikeEventContent> {
     pub unsigned: MessageLikeUnsigned<C>,
 }
 
+impl<C: MessageLikeEventContent> OriginalMessageLikeEvent<C> {
+    /// Computes the age of this event at the given point in time, from its `origin_server_ts`.
+    ///
+    /// Unlike `unsigned.age`, this doesn't rely on the homeserver. If the event's timestamp is in
+    /// the future relative to `now`, this returns a zero duration.
+    pub fn local_age(&self, now: MilliSecondsSinceUnixEpoch) -> Duration {
+        local_age(self.origin_server_ts, now)
+    }
+}
+
 /// An unredacted message-like event without a `room_id`.
 ///
 /// `OriginalSyncMessageLikeEvent` implements the comparison traits using only the `event_id` field,
@@ -146,6 +158,16 @@ pub struct OriginalSyncMessageLikeEvent<C: MessageLikeEventContent> {
     pub unsigned: MessageLikeUnsigned<C>,
 }
 
+impl<C: MessageLikeEventContent> OriginalSyncMessageLikeEvent<C> {
+    /// Computes the age of this event at the given point in time, from its `origin_server_ts`.
+    ///
+    /// Unlike `unsigned.age`, this doesn't rely on the homeserver. If the event's timestamp is in
+    /// the future relative to `now`, this returns a zero duration.
+    pub fn local_age(&self, now: MilliSecondsSinceUnixEpoch) -> Duration {
+        local_age(self.origin_server_ts, now)
+    }
+}
+
 impl<C: MessageLikeEventContent + RedactContent> OriginalSyncMessageLikeEvent<C>
 where
     C::Redacted: RedactedMessageLikeEventContent,
@@ -267,6 +289,22 @@ pub struct OriginalStateEvent<C: StaticStateEventContent> {
     pub unsigned: C::Unsigned,
 }
 
+impl<C> OriginalStateEvent<C>
+where
+    C: StaticStateEventContent<
+        Unsigned = StateUnsigned<<C as StaticStateEventContent>::PossiblyRedacted>,
+    >,
+{
+    /// Get the previous and the current content of this event, if the previous content is present
+    /// in `unsigned.prev_content`.
+    ///
+    /// This is useful to describe how the state changed, e.g. the previous and new topic of a
+    /// room.
+    pub fn content_change(&self) -> Option<(&C::PossiblyRedacted, &C)> {
+        self.unsigned.prev_content.as_ref().map(|prev_content| (prev_content, &self.content))
+    }
+}
+
 /// An unredacted state event without a `room_id`.
 ///
 /// `OriginalSyncStateEvent` implements the comparison traits using only the `event_id` field, a
@@ -295,6 +333,22 @@ pub struct OriginalSyncStateEvent<C: StaticStateEventContent> {
     pub unsigned: C::Unsigned,
 }
 
+impl<C> OriginalSyncStateEvent<C>
+where
+    C: StaticStateEventContent<
+        Unsigned = StateUnsigned<<C as StaticStateEventContent>::PossiblyRedacted>,
+    >,
+{
+    /// Get the previous and the current content of this event, if the previous content is present
+    /// in `unsigned.prev_content`.
+    ///
+    /// This is useful to describe how the state changed, e.g. the previous and new topic of a
+    /// room.
+    pub fn content_change(&self) -> Option<(&C::PossiblyRedacted, &C)> {
+        self.unsigned.prev_content.as_ref().map(|prev_content| (prev_content, &self.content))
+    }
+}
+
 /// A stripped-down state event, used for previews of rooms the user has been invited to.
 #[derive(Clone, Debug, Event)]
 pub struct StrippedStateEvent<C: PossiblyRedactedStateEventContent> {
@@ -773,3 +827,10 @@ impl_sync_from_full!(
     StaticStateEventContent,
     RedactedStateEventContent
 );
+
+fn local_age(
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    now: MilliSecondsSinceUnixEpoch,
+) -> Duration {
+    Duration::from_millis(now.get().saturating_sub(origin_server_ts.get()).into())
+}
@@ -284,10 +284,100 @@ impl Mentions {
         Self { room: true, ..Default::default() }
     }
 
-    fn add(&mut self, mentions: Self) {
+    /// Create a `Mentions` from the given HTML, like the formatted body of a message.
+    ///
+    /// The mentioned users are the targets of the links with a `matrix:` or `https://matrix.to`
+    /// URI pointing to a user ID. The whole room is mentioned if the text contains `@room` as a
+    /// separate word, outside of those links.
+    ///
+    /// The content of the `mx-reply` element, the fallback of a rich reply, is ignored.
+    ///
+    /// ```
+    /// # use ruma_common::user_id;
+    /// # use ruma_events::Mentions;
+    /// let mentions = Mentions::from_html(
+    ///     "@room: <a href=\"https://matrix.to/#/@alice:example.org\">Alice</a> is here!",
+    /// );
+    ///
+    /// assert!(mentions.user_ids.contains(user_id!("@alice:example.org")));
+    /// assert!(mentions.room);
+    /// ```
+    #[cfg(feature = "html")]
+    pub fn from_html(html: &str) -> Self {
+        fn add_node_mentions(mentions: &mut Mentions, node: &ruma_html::NodeRef) {
+            use ruma_common::matrix_uri::MatrixId;
+            use ruma_html::matrix::{AnchorUri, MatrixElement};
+
+            if let Some(text) = node.as_text() {
+                mentions.room |= contains_room_mention(&text.borrow());
+                return;
+            }
+
+            if let Some(element) = node.as_element() {
+                match element.to_matrix().element {
+                    // Don't look for mentions in the quoted message of a rich reply.
+                    MatrixElement::MatrixReply => return,
+                    MatrixElement::A(anchor) => {
+                        let matrix_id = match &anchor.href {
+                            Some(AnchorUri::Matrix(uri)) => Some(uri.id()),
+                            Some(AnchorUri::MatrixTo(uri)) => Some(uri.id()),
+                            _ => None,
+                        };
+
+                        if let Some(MatrixId::User(user_id)) = matrix_id {
+                            // Don't look for a room mention in the name of the user.
+                            mentions.user_ids.insert(user_id.clone());
+                            return;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            for child in node.children() {
+                add_node_mentions(mentions, &child);
+            }
+        }
+
+        /// Whether the given text contains `@room`, not surrounded by other word characters.
+        fn contains_room_mention(text: &str) -> bool {
+            let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+            text.match_indices("@room").any(|(index, room)| {
+                let before = text[..index].chars().next_back();
+                let after = text[index + room.len()..].chars().next();
+                !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+            })
+        }
+
+        let mut mentions = Self::new();
+
+        for node in ruma_html::Html::parse(html).children() {
+            add_node_mentions(&mut mentions, &node);
+        }
+
+        mentions
+    }
+
+    /// Add the given mentions to this one.
+    pub fn merge(&mut self, mentions: Self) {
         self.user_ids.extend(mentions.user_ids);
         self.room |= mentions.room;
     }
+
+    /// Get the mentions that are in this one but not in the `previous` one.
+    ///
+    /// This is useful when editing a message, to only mention the users that were not mentioned
+    /// in the previous version of the message, as recommended by the spec for [mentions] in
+    /// replacement events.
+    ///
+    /// [mentions]: https://spec.matrix.org/latest/client-server-api/#user-and-room-mentions
+    pub fn difference(&self, previous: &Mentions) -> Self {
+        Self {
+            user_ids: self.user_ids.difference(&previous.user_ids).cloned().collect(),
+            room: self.room && !previous.room,
+        }
+    }
 }
 
 // Wrapper around `Box<str>` that cannot be used in a meaningful way outside of
@@ -8,6 +8,11 @@
 use std::collections::BTreeMap;
 
 use js_int::UInt;
+#[cfg(feature = "canonical-json")]
+use ruma_common::{
+    canonical_json::{to_canonical_value, CanonicalJsonError},
+    CanonicalJsonObject, CanonicalJsonValue,
+};
 use ruma_common::{
     MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedRoomId, OwnedUserId, ServerSignatures,
 };
@@ -18,6 +23,8 @@ use serde::{
 use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
 
 use super::TimelineEventType;
+#[cfg(feature = "canonical-json")]
+use super::{MessageLikeEventContent, StateEventContent};
 
 /// Enum for PDU schemas
 #[derive(Clone, Debug, Serialize)]
@@ -157,6 +164,138 @@ impl EventHash {
     }
 }
 
+/// A builder for the JSON object of an outgoing PDU, for room versions 3 and above.
+///
+/// The resulting object uses the schema of [`RoomV3Pdu`], without the `hashes` and `signatures`
+/// fields. It is meant to be passed to `ruma_signatures::hash_and_sign_event()`, which adds them.
+#[cfg(feature = "canonical-json")]
+#[derive(Clone, Debug)]
+pub struct PduBuilder {
+    room_id: OwnedRoomId,
+    sender: OwnedUserId,
+    event_type: TimelineEventType,
+    content: CanonicalJsonValue,
+    state_key: Option<String>,
+    prev_events: Vec<OwnedEventId>,
+    auth_events: Vec<OwnedEventId>,
+    depth: UInt,
+    redacts: Option<OwnedEventId>,
+    origin_server_ts: Option<MilliSecondsSinceUnixEpoch>,
+}
+
+#[cfg(feature = "canonical-json")]
+impl PduBuilder {
+    /// Creates a new `PduBuilder` for a message-like event with the given content.
+    ///
+    /// Fails if the content cannot be serialized to canonical JSON.
+    pub fn message_like<C: MessageLikeEventContent>(
+        room_id: OwnedRoomId,
+        sender: OwnedUserId,
+        content: &C,
+    ) -> Result<Self, CanonicalJsonError> {
+        Ok(Self::new(room_id, sender, content.event_type().into(), to_canonical_value(content)?))
+    }
+
+    /// Creates a new `PduBuilder` for a state event with the given content and state key.
+    ///
+    /// Fails if the content cannot be serialized to canonical JSON.
+    pub fn state<C: StateEventContent>(
+        room_id: OwnedRoomId,
+        sender: OwnedUserId,
+        state_key: &C::StateKey,
+        content: &C,
+    ) -> Result<Self, CanonicalJsonError> {
+        let mut builder =
+            Self::new(room_id, sender, content.event_type().into(), to_canonical_value(content)?);
+        builder.state_key = Some(state_key.as_ref().to_owned());
+        Ok(builder)
+    }
+
+    fn new(
+        room_id: OwnedRoomId,
+        sender: OwnedUserId,
+        event_type: TimelineEventType,
+        content: CanonicalJsonValue,
+    ) -> Self {
+        Self {
+            room_id,
+            sender,
+            event_type,
+            content,
+            state_key: None,
+            prev_events: Vec::new(),
+            auth_events: Vec::new(),
+            depth: UInt::MIN,
+            redacts: None,
+            origin_server_ts: None,
+        }
+    }
+
+    /// Sets the IDs of the most recent events in the room that the homeserver was aware of.
+    pub fn with_prev_events(mut self, prev_events: Vec<OwnedEventId>) -> Self {
+        self.prev_events = prev_events;
+        self
+    }
+
+    /// Sets the IDs of the authorization events that allow this event to be in the room.
+    pub fn with_auth_events(mut self, auth_events: Vec<OwnedEventId>) -> Self {
+        self.auth_events = auth_events;
+        self
+    }
+
+    /// Sets the depth of the event, which should be the maximum depth of the `prev_events`, plus
+    /// one.
+    ///
+    /// Defaults to `0`.
+    pub fn with_depth(mut self, depth: UInt) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the ID of the event being redacted, for redaction events in room versions that use
+    /// the top-level `redacts` field.
+    pub fn with_redacts(mut self, redacts: OwnedEventId) -> Self {
+        self.redacts = Some(redacts);
+        self
+    }
+
+    /// Sets the timestamp of the event.
+    ///
+    /// Defaults to [`MilliSecondsSinceUnixEpoch::now()`] when the PDU is built.
+    pub fn with_origin_server_ts(mut self, origin_server_ts: MilliSecondsSinceUnixEpoch) -> Self {
+        self.origin_server_ts = Some(origin_server_ts);
+        self
+    }
+
+    /// Builds the JSON object of the PDU.
+    pub fn build(self) -> CanonicalJsonObject {
+        let origin_server_ts =
+            self.origin_server_ts.unwrap_or_else(MilliSecondsSinceUnixEpoch::now);
+        let event_ids =
+            |ids: Vec<OwnedEventId>| ids.into_iter().map(|id| id.as_str().into()).collect();
+
+        let mut object = CanonicalJsonObject::from([
+            ("room_id".to_owned(), self.room_id.as_str().into()),
+            ("sender".to_owned(), self.sender.as_str().into()),
+            ("origin_server_ts".to_owned(), CanonicalJsonValue::Integer(origin_server_ts.0.into())),
+            ("type".to_owned(), self.event_type.to_string().into()),
+            ("content".to_owned(), self.content),
+            ("prev_events".to_owned(), CanonicalJsonValue::Array(event_ids(self.prev_events))),
+            ("auth_events".to_owned(), CanonicalJsonValue::Array(event_ids(self.auth_events))),
+            ("depth".to_owned(), CanonicalJsonValue::Integer(self.depth.into())),
+        ]);
+
+        if let Some(state_key) = self.state_key {
+            object.insert("state_key".to_owned(), state_key.into());
+        }
+        if let Some(redacts) = self.redacts {
+            object.insert("redacts".to_owned(), redacts.as_str().into());
+        }
+
+        object
+    }
+}
+
 impl<'de> Deserialize<'de> for Pdu {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
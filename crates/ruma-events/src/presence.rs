@@ -3,7 +3,7 @@
 //! The only content valid for this event is `PresenceEventContent`.
 
 use js_int::UInt;
-use ruma_common::{presence::PresenceState, OwnedMxcUri, OwnedUserId};
+use ruma_common::{presence::PresenceState, MilliSecondsSinceUnixEpoch, OwnedMxcUri, OwnedUserId};
 use ruma_macros::{Event, EventContent};
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
@@ -83,12 +83,34 @@ impl PresenceEventContent {
             status_msg: None,
         }
     }
+
+    /// Sets the status message that accompanies the presence.
+    pub fn with_status_msg(mut self, status_msg: String) -> Self {
+        self.status_msg = Some(status_msg);
+        self
+    }
+
+    /// Sets whether the user is currently active.
+    pub fn with_currently_active(mut self, currently_active: bool) -> Self {
+        self.currently_active = Some(currently_active);
+        self
+    }
+
+    /// Sets `last_active_ago` to the time elapsed since the given timestamp of the last action of
+    /// the user.
+    ///
+    /// If the timestamp is in the future, `last_active_ago` is set to `0`.
+    pub fn with_last_active_ts(mut self, last_active_ts: MilliSecondsSinceUnixEpoch) -> Self {
+        let now = MilliSecondsSinceUnixEpoch::now();
+        self.last_active_ago = Some(now.get().saturating_sub(last_active_ts.get()));
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use js_int::uint;
-    use ruma_common::{mxc_uri, presence::PresenceState};
+    use ruma_common::{mxc_uri, presence::PresenceState, MilliSecondsSinceUnixEpoch};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{PresenceEvent, PresenceEventContent};
@@ -115,6 +137,39 @@ mod tests {
         assert_eq!(to_json_value(&content).unwrap(), json);
     }
 
+    #[test]
+    fn builder() {
+        let content = PresenceEventContent::new(PresenceState::Unavailable)
+            .with_status_msg("Out for lunch".to_owned())
+            .with_currently_active(false);
+
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({
+                "currently_active": false,
+                "presence": "unavailable",
+                "status_msg": "Out for lunch",
+            })
+        );
+    }
+
+    #[test]
+    fn last_active_ts() {
+        let now = MilliSecondsSinceUnixEpoch::now();
+
+        let last_active_ts = MilliSecondsSinceUnixEpoch(now.get() - uint!(60_000));
+        let content =
+            PresenceEventContent::new(PresenceState::Online).with_last_active_ts(last_active_ts);
+        let last_active_ago = content.last_active_ago.unwrap();
+        assert!(last_active_ago >= uint!(60_000));
+        assert!(last_active_ago < uint!(120_000));
+
+        let last_active_ts = MilliSecondsSinceUnixEpoch(now.get() + uint!(60_000));
+        let content =
+            PresenceEventContent::new(PresenceState::Online).with_last_active_ts(last_active_ts);
+        assert_eq!(content.last_active_ago, Some(uint!(0)));
+    }
+
     #[test]
     fn deserialization() {
         let json = json!({
@@ -249,6 +249,22 @@ impl<E> BundledMessageLikeRelations<E> {
         self.replace.is_none() && self.thread.is_none() && self.reference.is_none()
     }
 
+    /// Merge the given relations into these ones.
+    ///
+    /// The relations that are present in `other` replace the ones in `self`.
+    pub(crate) fn merge(&mut self, other: Self) {
+        if other.has_replacement() {
+            self.replace = other.replace;
+            self.has_invalid_replacement = other.has_invalid_replacement;
+        }
+        if other.thread.is_some() {
+            self.thread = other.thread;
+        }
+        if other.reference.is_some() {
+            self.reference = other.reference;
+        }
+    }
+
     /// Transform `BundledMessageLikeRelations<E>` to `BundledMessageLikeRelations<T>` using the
     /// given closure to convert the `replace` field if it is `Some(_)`.
     pub(crate) fn map_replace<T>(self, f: impl FnOnce(E) -> T) -> BundledMessageLikeRelations<T> {
@@ -16,9 +16,13 @@ use crate::{
 };
 
 mod change;
+mod transition;
 
 use self::change::membership_change;
-pub use self::change::{Change, MembershipChange, MembershipDetails};
+pub use self::{
+    change::{Change, MembershipChange, MembershipDetails},
+    transition::MembershipTransitionContext,
+};
 
 /// The content of an `m.room.member` event.
 ///
@@ -124,6 +128,25 @@ impl RoomMemberEventContent {
         }
     }
 
+    /// Add the given reason to this `RoomMemberEventContent`.
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Add the given user as the one that authorized this join in a restricted room.
+    pub fn joined_via(mut self, user_id: OwnedUserId) -> Self {
+        self.join_authorized_via_users_server = Some(user_id);
+        self
+    }
+
+    /// Mark the room of this `RoomMemberEventContent` as a direct chat.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_direct(mut self) -> Self {
+        self.is_direct = Some(true);
+        self
+    }
+
     /// Obtain the details about this event that are required to calculate a membership change.
     ///
     /// This is required when you want to calculate the change a redacted `m.room.member` event
@@ -526,6 +549,14 @@ pub struct RoomMemberUnsigned {
     /// This field is generated by the local homeserver, and may be incorrect if the local time on
     /// at least one of the two servers is out of sync, which can cause the age to either be
     /// negative or greater than it actually is.
+    ///
+    /// If you activate the `compat-unsigned-age` feature, this field can also be decoded from a
+    /// floating-point number, which is truncated, or from a number outside of the range of `Int`,
+    /// which is clamped.
+    #[cfg_attr(
+        feature = "compat-unsigned-age",
+        serde(default, deserialize_with = "crate::unsigned::deserialize_lax_age")
+    )]
     pub age: Option<Int>,
 
     /// The client-supplied transaction ID, if the client being given the event is the same one
@@ -574,10 +605,10 @@ mod tests {
     use js_int::uint;
     use maplit::btreemap;
     use ruma_common::{
-        mxc_uri, serde::CanBeEmpty, server_name, server_signing_key_version, user_id,
-        MilliSecondsSinceUnixEpoch, ServerSigningKeyId, SigningKeyAlgorithm,
+        mxc_uri, owned_user_id, serde::CanBeEmpty, server_name, server_signing_key_version,
+        user_id, MilliSecondsSinceUnixEpoch, ServerSigningKeyId, SigningKeyAlgorithm,
     };
-    use serde_json::{from_value as from_json_value, json};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{MembershipState, RoomMemberEventContent};
     use crate::OriginalStateEvent;
@@ -821,4 +852,48 @@ mod tests {
             Some(user_id!("@notcarl:example.com"))
         );
     }
+
+    #[test]
+    fn invalid_state_key() {
+        let json = json!({
+            "type": "m.room.member",
+            "content": {
+                "membership": "join"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "carl"
+        });
+
+        from_json_value::<OriginalStateEvent<RoomMemberEventContent>>(json).unwrap_err();
+    }
+
+    #[test]
+    fn serialize_invite_with_builder_methods() {
+        let content = RoomMemberEventContent::new(MembershipState::Invite)
+            .with_reason("Let's chat".to_owned())
+            .as_direct();
+
+        assert_eq!(
+            to_json_value(content).unwrap(),
+            json!({
+                "is_direct": true,
+                "membership": "invite",
+                "reason": "Let's chat",
+            })
+        );
+
+        let content = RoomMemberEventContent::new(MembershipState::Join)
+            .joined_via(owned_user_id!("@notcarl:example.com"));
+
+        assert_eq!(
+            to_json_value(content).unwrap(),
+            json!({
+                "join_authorised_via_users_server": "@notcarl:example.com",
+                "membership": "join",
+            })
+        );
+    }
 }
@@ -152,3 +152,75 @@ pub(super) fn membership_change<'a>(
         _ => Ch::NotImplemented,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use ruma_common::{mxc_uri, user_id};
+
+    use super::{membership_change, MembershipChange as Ch, MembershipDetails};
+    use crate::room::member::MembershipState as St;
+
+    fn details(membership: &St) -> MembershipDetails<'_> {
+        MembershipDetails { avatar_url: None, displayname: None, membership }
+    }
+
+    #[test]
+    fn membership_transitions() {
+        // The sender is the target of the event.
+        fn own<'a>(prev: Option<&'a St>, new: &'a St) -> Ch<'a> {
+            let alice = user_id!("@alice:example.org");
+            membership_change(details(new), prev.map(details), alice, alice)
+        }
+        // The sender is another user.
+        fn other<'a>(prev: Option<&'a St>, new: &'a St) -> Ch<'a> {
+            let alice = user_id!("@alice:example.org");
+            let bob = user_id!("@bob:example.org");
+            membership_change(details(new), prev.map(details), bob, alice)
+        }
+
+        assert_matches!(own(None, &St::Join), Ch::Joined);
+        assert_matches!(own(Some(&St::Leave), &St::Join), Ch::Joined);
+        assert_matches!(own(Some(&St::Join), &St::Leave), Ch::Left);
+        assert_matches!(own(Some(&St::Join), &St::Join), Ch::None);
+        assert_matches!(own(Some(&St::Invite), &St::Join), Ch::InvitationAccepted);
+        assert_matches!(own(Some(&St::Invite), &St::Leave), Ch::InvitationRejected);
+        assert_matches!(own(Some(&St::Leave), &St::Knock), Ch::Knocked);
+        assert_matches!(own(Some(&St::Knock), &St::Leave), Ch::KnockRetracted);
+
+        assert_matches!(other(Some(&St::Leave), &St::Invite), Ch::Invited);
+        assert_matches!(other(Some(&St::Invite), &St::Leave), Ch::InvitationRevoked);
+        assert_matches!(other(Some(&St::Join), &St::Leave), Ch::Kicked);
+        assert_matches!(other(Some(&St::Leave), &St::Ban), Ch::Banned);
+        assert_matches!(other(Some(&St::Invite), &St::Ban), Ch::Banned);
+        assert_matches!(other(Some(&St::Knock), &St::Ban), Ch::Banned);
+        assert_matches!(other(Some(&St::Join), &St::Ban), Ch::KickedAndBanned);
+        assert_matches!(other(Some(&St::Ban), &St::Leave), Ch::Unbanned);
+        assert_matches!(other(Some(&St::Knock), &St::Invite), Ch::KnockAccepted);
+        assert_matches!(other(Some(&St::Knock), &St::Leave), Ch::KnockDenied);
+        assert_matches!(other(Some(&St::Ban), &St::Join), Ch::Error);
+        assert_matches!(other(Some(&St::Join), &St::Invite), Ch::Error);
+    }
+
+    #[test]
+    fn profile_change() {
+        let alice = user_id!("@alice:example.org");
+        let prev = MembershipDetails {
+            avatar_url: None,
+            displayname: Some("Alice"),
+            membership: &St::Join,
+        };
+        let new = MembershipDetails {
+            avatar_url: Some(mxc_uri!("mxc://example.org/avatar")),
+            displayname: Some("Alice"),
+            membership: &St::Join,
+        };
+
+        assert_matches!(
+            membership_change(new, Some(prev), alice, alice),
+            Ch::ProfileChanged { displayname_change: None, avatar_url_change: Some(change) }
+        );
+        assert_eq!(change.old, None);
+        assert_eq!(change.new.unwrap(), "mxc://example.org/avatar");
+    }
+}
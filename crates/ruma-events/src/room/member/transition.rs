@@ -0,0 +1,146 @@
+use super::MembershipState;
+use crate::room::join_rules::JoinRule;
+
+/// The context of a membership transition, used by [`MembershipState::can_transition_to()`].
+#[derive(Clone, Debug)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct MembershipTransitionContext<'a> {
+    /// Whether the sender of the membership event is the user whose membership changes.
+    pub is_self: bool,
+
+    /// The join rule of the room.
+    pub join_rule: &'a JoinRule,
+}
+
+impl<'a> MembershipTransitionContext<'a> {
+    /// Creates a new `MembershipTransitionContext` with the given sender relationship and join
+    /// rule.
+    pub fn new(is_self: bool, join_rule: &'a JoinRule) -> Self {
+        Self { is_self, join_rule }
+    }
+}
+
+impl MembershipState {
+    /// Whether a user with this membership state can have their membership changed to `target` in
+    /// the given context.
+    ///
+    /// This follows the [authorization rules] for `m.room.member` events, except for the checks
+    /// that require more information about the room:
+    ///
+    /// * The power levels of the sender and the user are not checked, so inviting, kicking, banning
+    ///   or unbanning another user is always considered allowed.
+    /// * For restricted rooms, the user is assumed to satisfy one of the allow conditions.
+    /// * Third-party invites are not supported.
+    ///
+    /// [authorization rules]: https://spec.matrix.org/latest/rooms/v11/#authorization-rules
+    pub fn can_transition_to(
+        &self,
+        target: &MembershipState,
+        context: &MembershipTransitionContext<'_>,
+    ) -> bool {
+        match target {
+            MembershipState::Join => {
+                if !context.is_self {
+                    return false;
+                }
+
+                match context.join_rule {
+                    JoinRule::Public | JoinRule::Restricted(_) | JoinRule::KnockRestricted(_) => {
+                        matches!(self, Self::Invite | Self::Join | Self::Knock | Self::Leave)
+                    }
+                    JoinRule::Invite | JoinRule::Knock => matches!(self, Self::Invite | Self::Join),
+                    _ => false,
+                }
+            }
+            MembershipState::Invite => {
+                !context.is_self && !matches!(self, Self::Ban | Self::Join | Self::_Custom(_))
+            }
+            MembershipState::Leave => {
+                if context.is_self {
+                    matches!(self, Self::Invite | Self::Join | Self::Knock)
+                } else {
+                    !matches!(self, Self::_Custom(_))
+                }
+            }
+            MembershipState::Ban => !context.is_self && !matches!(self, Self::_Custom(_)),
+            MembershipState::Knock => {
+                context.is_self
+                    && matches!(context.join_rule, JoinRule::Knock | JoinRule::KnockRestricted(_))
+                    && matches!(self, Self::Knock | Self::Leave)
+            }
+            MembershipState::_Custom(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{MembershipState, MembershipTransitionContext};
+    use crate::room::join_rules::{JoinRule, Restricted};
+
+    #[test]
+    fn self_transitions() {
+        let public = MembershipTransitionContext::new(true, &JoinRule::Public);
+        let invite = MembershipTransitionContext::new(true, &JoinRule::Invite);
+        let knock = MembershipTransitionContext::new(true, &JoinRule::Knock);
+        let restricted_rule = JoinRule::Restricted(Restricted::new(Vec::new()));
+        let restricted = MembershipTransitionContext::new(true, &restricted_rule);
+
+        // Accepting an invite.
+        assert!(MembershipState::Invite.can_transition_to(&MembershipState::Join, &invite));
+        // Joining a public room.
+        assert!(MembershipState::Leave.can_transition_to(&MembershipState::Join, &public));
+        assert!(MembershipState::Leave.can_transition_to(&MembershipState::Join, &restricted));
+        assert!(!MembershipState::Leave.can_transition_to(&MembershipState::Join, &invite));
+        // Knocking doesn't allow to join without an invite.
+        assert!(MembershipState::Leave.can_transition_to(&MembershipState::Knock, &knock));
+        assert!(!MembershipState::Invite.can_transition_to(&MembershipState::Knock, &knock));
+        assert!(!MembershipState::Leave.can_transition_to(&MembershipState::Knock, &public));
+        assert!(!MembershipState::Knock.can_transition_to(&MembershipState::Join, &knock));
+        // Leaving or rejecting an invite.
+        assert!(MembershipState::Join.can_transition_to(&MembershipState::Leave, &public));
+        assert!(MembershipState::Invite.can_transition_to(&MembershipState::Leave, &invite));
+        assert!(!MembershipState::Leave.can_transition_to(&MembershipState::Leave, &public));
+        // Banned users can't join or unban themselves.
+        assert!(!MembershipState::Ban.can_transition_to(&MembershipState::Join, &public));
+        assert!(!MembershipState::Ban.can_transition_to(&MembershipState::Leave, &public));
+        // Users can't invite or ban themselves.
+        assert!(!MembershipState::Leave.can_transition_to(&MembershipState::Invite, &public));
+        assert!(!MembershipState::Join.can_transition_to(&MembershipState::Ban, &public));
+    }
+
+    #[test]
+    fn join_with_private_or_custom_join_rule() {
+        let private = MembershipTransitionContext::new(true, &JoinRule::Private);
+        let custom_rule: JoinRule =
+            serde_json::from_value(json!({ "join_rule": "local.custom" })).unwrap();
+        let custom = MembershipTransitionContext::new(true, &custom_rule);
+
+        for context in [&private, &custom] {
+            assert!(!MembershipState::Invite.can_transition_to(&MembershipState::Join, context));
+            assert!(!MembershipState::Join.can_transition_to(&MembershipState::Join, context));
+            assert!(!MembershipState::Leave.can_transition_to(&MembershipState::Join, context));
+            assert!(!MembershipState::Knock.can_transition_to(&MembershipState::Join, context));
+        }
+    }
+
+    #[test]
+    fn other_transitions() {
+        let public = MembershipTransitionContext::new(false, &JoinRule::Public);
+
+        // Inviting.
+        assert!(MembershipState::Leave.can_transition_to(&MembershipState::Invite, &public));
+        assert!(MembershipState::Knock.can_transition_to(&MembershipState::Invite, &public));
+        assert!(!MembershipState::Join.can_transition_to(&MembershipState::Invite, &public));
+        assert!(!MembershipState::Ban.can_transition_to(&MembershipState::Invite, &public));
+        // Kicking, banning and unbanning.
+        assert!(MembershipState::Join.can_transition_to(&MembershipState::Leave, &public));
+        assert!(MembershipState::Join.can_transition_to(&MembershipState::Ban, &public));
+        assert!(MembershipState::Ban.can_transition_to(&MembershipState::Leave, &public));
+        // Users can't be forced to join or knock.
+        assert!(!MembershipState::Invite.can_transition_to(&MembershipState::Join, &public));
+        assert!(!MembershipState::Leave.can_transition_to(&MembershipState::Knock, &public));
+    }
+}
@@ -165,7 +165,7 @@ impl RoomMessageEventContent {
         forward_thread: ForwardThread,
         add_mentions: AddMentions,
     ) -> Self {
-        self.without_relation().make_reply_to(metadata, forward_thread, add_mentions)
+        self.before_new_relation().make_reply_to(metadata, forward_thread, add_mentions)
     }
 
     /// Turns `self` into a new message for a [thread], that is optionally a reply.
@@ -188,7 +188,7 @@ impl RoomMessageEventContent {
         is_reply: ReplyWithinThread,
         add_mentions: AddMentions,
     ) -> Self {
-        self.without_relation().make_for_thread(metadata, is_reply, add_mentions)
+        self.before_new_relation().make_for_thread(metadata, is_reply, add_mentions)
     }
 
     /// Turns `self` into a [replacement] (or edit) for a given message.
@@ -211,7 +211,7 @@ impl RoomMessageEventContent {
     /// [replacement]: https://spec.matrix.org/latest/client-server-api/#event-replacements
     #[track_caller]
     pub fn make_replacement(self, metadata: impl Into<ReplacementMetadata>) -> Self {
-        self.without_relation().make_replacement(metadata)
+        self.before_new_relation().make_replacement(metadata)
     }
 
     /// Set the [mentions] of this event.
@@ -267,10 +267,18 @@ impl RoomMessageEventContent {
     ///
     /// [mentions]: https://spec.matrix.org/latest/client-server-api/#user-and-room-mentions
     pub fn add_mentions(mut self, mentions: Mentions) -> Self {
-        self.mentions.get_or_insert_with(Mentions::new).add(mentions);
+        self.mentions.get_or_insert_with(Mentions::new).merge(mentions);
         self
     }
 
+    /// Remove the relation of `self`, e.g. to send the message again in another context.
+    ///
+    /// Unlike converting it to a [`MessageType`], the `mentions` are kept. Use
+    /// [`RoomMessageEventContentWithoutRelation::with_relation()`] to set a new relation.
+    pub fn without_relation(self) -> RoomMessageEventContentWithoutRelation {
+        self.into()
+    }
+
     /// Returns a reference to the `msgtype` string.
     ///
     /// If you want to access the message type-specific data rather than the message type itself,
@@ -318,7 +326,8 @@ impl RoomMessageEventContent {
         self.msgtype.sanitize(mode, remove_reply_fallback);
     }
 
-    fn without_relation(self) -> RoomMessageEventContentWithoutRelation {
+    /// Remove the relation of `self` before setting a new one, warning if there was one.
+    fn before_new_relation(self) -> RoomMessageEventContentWithoutRelation {
         if self.relates_to.is_some() {
             warn!("Overwriting existing relates_to value");
         }
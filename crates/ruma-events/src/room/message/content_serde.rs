@@ -5,7 +5,7 @@ use serde::{de, Deserialize};
 use serde_json::value::RawValue as RawJsonValue;
 
 use super::{
-    relation_serde::deserialize_relation, MessageType, RoomMessageEventContent,
+    relation_serde::deserialize_relation, MessageType, Relation, RoomMessageEventContent,
     RoomMessageEventContentWithoutRelation,
 };
 use crate::Mentions;
@@ -22,7 +22,22 @@ impl<'de> Deserialize<'de> for RoomMessageEventContent {
 
         let MentionsDeHelper { mentions } = from_raw_json_value(&json)?;
 
-        Ok(Self { msgtype: from_raw_json_value(&json)?, relates_to, mentions })
+        let mut msgtype = from_raw_json_value(&json)?;
+        if let MessageType::_Custom(custom) = &mut msgtype {
+            // Remove the fields that are deserialized into the other fields of the content, so
+            // they are not serialized twice.
+            if relates_to.is_some() {
+                custom.data.remove("m.relates_to");
+            }
+            if matches!(relates_to, Some(Relation::Replacement(_))) {
+                custom.data.remove("m.new_content");
+            }
+            if mentions.is_some() {
+                custom.data.remove("m.mentions");
+            }
+        }
+
+        Ok(Self { msgtype, relates_to, mentions })
     }
 }
 
@@ -35,7 +50,16 @@ impl<'de> Deserialize<'de> for RoomMessageEventContentWithoutRelation {
 
         let MentionsDeHelper { mentions } = from_raw_json_value(&json)?;
 
-        Ok(Self { msgtype: from_raw_json_value(&json)?, mentions })
+        let mut msgtype = from_raw_json_value(&json)?;
+        if let MessageType::_Custom(custom) = &mut msgtype {
+            // Remove the field that is deserialized into the other field of the content, so it is
+            // not serialized twice.
+            if mentions.is_some() {
+                custom.data.remove("m.mentions");
+            }
+        }
+
+        Ok(Self { msgtype, mentions })
     }
 }
 
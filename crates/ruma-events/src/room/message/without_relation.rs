@@ -242,7 +242,7 @@ impl RoomMessageEventContentWithoutRelation {
     ///
     /// [mentions]: https://spec.matrix.org/latest/client-server-api/#user-and-room-mentions
     pub fn add_mentions(mut self, mentions: Mentions) -> Self {
-        self.mentions.get_or_insert_with(Mentions::new).add(mentions);
+        self.mentions.get_or_insert_with(Mentions::new).merge(mentions);
         self
     }
 }
@@ -15,6 +15,10 @@ use crate::EmptyStateKey;
 #[ruma_event(type = "m.room.name", kind = State, state_key_type = EmptyStateKey)]
 pub struct RoomNameEventContent {
     /// The name of the room.
+    ///
+    /// It is (de)serialized as a plain string. The specification doesn't limit its size anymore,
+    /// since it was never enforced by servers, so any string is accepted. Clients can truncate it
+    /// for display.
     pub name: String,
 }
 
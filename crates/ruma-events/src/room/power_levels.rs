@@ -126,6 +126,13 @@ impl RoomPowerLevelsEventContent {
             notifications: NotificationPowerLevels::default(),
         }
     }
+
+    /// Get the power level required to trigger the notification with the given key.
+    ///
+    /// Returns the default power level of 50 if the key is absent.
+    pub fn notification_level(&self, key: &str) -> Int {
+        self.notifications.get(key).copied().unwrap_or_else(default_power_level)
+    }
 }
 
 impl Default for RoomPowerLevelsEventContent {
@@ -394,6 +401,13 @@ impl RoomPowerLevels {
         self.events.get(&state_type.into()).copied().unwrap_or(self.state_default)
     }
 
+    /// Get the power level required to trigger the notification with the given key.
+    ///
+    /// Returns the default power level of 50 if the key is absent.
+    pub fn notification_level(&self, key: &str) -> Int {
+        self.notifications.get(key).copied().unwrap_or_else(default_power_level)
+    }
+
     /// Whether the given user can ban other users based on the power levels.
     ///
     /// Shorthand for `power_levels.user_can_do(user_id, PowerLevelAction::Ban)`.
@@ -456,7 +470,7 @@ impl RoomPowerLevels {
     /// Whether the acting user can kick the target user based on the power levels.
     ///
     /// On top of `power_levels.user_can_kick(acting_user_id)`, this performs an extra check
-    /// to make sure the acting user has at least the same power level as the target user.
+    /// to make sure the acting user has a greater power level than the target user.
     ///
     /// Shorthand for `power_levels.user_can_do_to_user(acting_user_id, target_user_id,
     /// PowerLevelUserAction::Kick)`.
@@ -475,7 +489,7 @@ impl RoomPowerLevels {
 
     /// Whether the given user can redact events of other users based on the power levels.
     ///
-    /// Shorthand for `power_levels.user_can_do(user_id, PowerLevelAction::RedactOthers)`.
+    /// Shorthand for `power_levels.user_can_do(user_id, PowerLevelAction::RedactOther)`.
     pub fn user_can_redact_event_of_other(&self, user_id: &UserId) -> bool {
         self.user_can_redact_own_event(user_id) && self.for_user(user_id) >= self.redact
     }
@@ -701,9 +715,13 @@ mod tests {
     use js_int::int;
     use maplit::btreemap;
     use ruma_common::user_id;
-    use serde_json::{json, to_value as to_json_value};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{default_power_level, NotificationPowerLevels, RoomPowerLevelsEventContent};
+    use super::{
+        default_power_level, NotificationPowerLevels, PowerLevelAction, PowerLevelUserAction,
+        RoomPowerLevels, RoomPowerLevelsEventContent,
+    };
+    use crate::{MessageLikeEventType, StateEventType, TimelineEventType};
 
     #[test]
     fn serialization_with_optional_fields_as_none() {
@@ -770,4 +788,141 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn deserialization_with_string_and_integer_values() {
+        // Room versions 1 to 9 allow power levels to be strings.
+        let json = json!({
+            "ban": "100",
+            "events": {
+                "m.room.name": "50",
+                "m.room.power_levels": 100,
+            },
+            "events_default": 0,
+            "invite": "-10",
+            "kick": 50,
+            "redact": "+50",
+            "state_default": "  50",
+            "users": {
+                "@carl:example.com": "100",
+                "@alice:example.com": 50,
+            },
+            "users_default": "0",
+            "notifications": {
+                "room": "20",
+            },
+        });
+
+        let content = from_json_value::<RoomPowerLevelsEventContent>(json).unwrap();
+        assert_eq!(content.ban, int!(100));
+        assert_eq!(content.events[&TimelineEventType::RoomName], int!(50));
+        assert_eq!(content.events[&TimelineEventType::RoomPowerLevels], int!(100));
+        assert_eq!(content.events_default, int!(0));
+        assert_eq!(content.invite, int!(-10));
+        assert_eq!(content.kick, int!(50));
+        assert_eq!(content.redact, int!(50));
+        assert_eq!(content.state_default, int!(50));
+        assert_eq!(content.users[user_id!("@carl:example.com")], int!(100));
+        assert_eq!(content.users[user_id!("@alice:example.com")], int!(50));
+        assert_eq!(content.users_default, int!(0));
+        assert_eq!(content.notifications.room, int!(20));
+        assert_eq!(content.notification_level("room"), int!(20));
+    }
+
+    #[test]
+    fn deserialization_without_notifications() {
+        let json = json!({ "ban": 100 });
+
+        let content = from_json_value::<RoomPowerLevelsEventContent>(json).unwrap();
+        assert_eq!(content.notifications.room, int!(50));
+        assert_eq!(content.notification_level("room"), int!(50));
+        assert_eq!(content.notification_level("unknown"), int!(50));
+
+        let power_levels = RoomPowerLevels::from(content);
+        assert_eq!(power_levels.notification_level("room"), int!(50));
+    }
+
+    #[test]
+    fn deserialization_with_empty_notifications() {
+        let json = json!({ "notifications": {} });
+
+        let content = from_json_value::<RoomPowerLevelsEventContent>(json).unwrap();
+        assert_eq!(content.notification_level("room"), int!(50));
+    }
+
+    fn power_levels() -> RoomPowerLevels {
+        let content = assign!(RoomPowerLevelsEventContent::new(), {
+            events: btreemap! {
+                TimelineEventType::RoomName => int!(25),
+                TimelineEventType::RoomPowerLevels => int!(100),
+                TimelineEventType::RoomRedaction => int!(10),
+            },
+            invite: int!(10),
+            users: btreemap! {
+                user_id!("@admin:example.org").to_owned() => int!(100),
+                user_id!("@mod:example.org").to_owned() => int!(50),
+                user_id!("@helper:example.org").to_owned() => int!(10),
+            },
+        });
+        content.into()
+    }
+
+    #[test]
+    fn user_can_do_actions() {
+        let power_levels = power_levels();
+        let moderator = user_id!("@mod:example.org");
+        let helper = user_id!("@helper:example.org");
+        let user = user_id!("@user:example.org");
+
+        assert_eq!(power_levels.for_user(user), int!(0));
+        assert_eq!(power_levels.max(), int!(100));
+
+        assert!(power_levels.user_can_invite(helper));
+        assert!(!power_levels.user_can_invite(user));
+
+        assert!(power_levels.user_can_kick(moderator));
+        assert!(!power_levels.user_can_kick(helper));
+        assert!(power_levels.user_can_ban(moderator));
+        assert!(power_levels.user_can_unban(moderator));
+
+        // Redacting uses the level of `m.room.redaction`, and `redact` for events of others.
+        assert!(power_levels.user_can_redact_own_event(helper));
+        assert!(!power_levels.user_can_redact_own_event(user));
+        assert!(!power_levels.user_can_redact_event_of_other(helper));
+        assert!(power_levels.user_can_redact_event_of_other(moderator));
+        assert!(power_levels.user_can_do(moderator, PowerLevelAction::RedactOther));
+
+        assert!(power_levels.user_can_send_message(user, MessageLikeEventType::RoomMessage));
+        assert!(!power_levels.user_can_send_state(helper, StateEventType::RoomName));
+        assert!(power_levels.user_can_send_state(moderator, StateEventType::RoomName));
+        assert!(power_levels.user_can_send_state(moderator, StateEventType::RoomTopic));
+        assert!(!power_levels.user_can_send_state(moderator, StateEventType::RoomPowerLevels));
+
+        assert!(!power_levels.user_can_trigger_room_notification(helper));
+        assert!(power_levels.user_can_trigger_room_notification(moderator));
+    }
+
+    #[test]
+    fn user_can_do_to_user_actions() {
+        let power_levels = power_levels();
+        let admin = user_id!("@admin:example.org");
+        let moderator = user_id!("@mod:example.org");
+        let helper = user_id!("@helper:example.org");
+        let user = user_id!("@user:example.org");
+
+        // The target must have a lower power level.
+        assert!(power_levels.user_can_kick_user(moderator, helper));
+        assert!(!power_levels.user_can_kick_user(moderator, admin));
+        assert!(!power_levels.user_can_kick_user(moderator, moderator));
+        assert!(power_levels.user_can_ban_user(admin, moderator));
+        assert!(!power_levels.user_can_ban_user(helper, user));
+        assert!(power_levels.user_can_unban_user(moderator, user));
+        assert!(power_levels.user_can_do_to_user(helper, admin, PowerLevelUserAction::Invite));
+
+        // Changing power levels requires being allowed to send `m.room.power_levels`.
+        assert!(!power_levels.user_can_change_user_power_level(moderator, user));
+        assert!(power_levels.user_can_change_user_power_level(admin, admin));
+        assert!(power_levels.user_can_change_user_power_level(admin, moderator));
+        assert!(power_levels.user_can_change_user_power_level(admin, user));
+    }
 }
@@ -437,6 +437,14 @@ pub struct RoomRedactionUnsigned {
     /// This field is generated by the local homeserver, and may be incorrect if the local time on
     /// at least one of the two servers is out of sync, which can cause the age to either be
     /// negative or greater than it actually is.
+    ///
+    /// If you activate the `compat-unsigned-age` feature, this field can also be decoded from a
+    /// floating-point number, which is truncated, or from a number outside of the range of `Int`,
+    /// which is clamped.
+    #[cfg_attr(
+        feature = "compat-unsigned-age",
+        serde(default, deserialize_with = "crate::unsigned::deserialize_lax_age")
+    )]
     pub age: Option<Int>,
 
     /// The client-supplied transaction ID, if the client being given the event is the same one
@@ -3,6 +3,11 @@ use ruma_common::{
     serde::CanBeEmpty, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedTransactionId, OwnedUserId,
 };
 use serde::{de::DeserializeOwned, Deserialize};
+#[cfg(feature = "compat-unsigned-age")]
+use serde::{
+    de::{self, Visitor},
+    Deserializer,
+};
 
 use super::{
     relation::{BundledMessageLikeRelations, BundledStateRelations},
@@ -20,6 +25,14 @@ pub struct MessageLikeUnsigned<C: MessageLikeEventContent> {
     /// This field is generated by the local homeserver, and may be incorrect if the local time on
     /// at least one of the two servers is out of sync, which can cause the age to either be
     /// negative or greater than it actually is.
+    ///
+    /// If you activate the `compat-unsigned-age` feature, this field can also be decoded from a
+    /// floating-point number, which is truncated, or from a number outside of the range of `Int`,
+    /// which is clamped.
+    #[cfg_attr(
+        feature = "compat-unsigned-age",
+        serde(default, deserialize_with = "crate::unsigned::deserialize_lax_age")
+    )]
     pub age: Option<Int>,
 
     /// The client-supplied transaction ID, if the client being given the event is the same one
@@ -38,6 +51,22 @@ impl<C: MessageLikeEventContent> MessageLikeUnsigned<C> {
     pub fn new() -> Self {
         Self { age: None, transaction_id: None, relations: BundledMessageLikeRelations::default() }
     }
+
+    /// Merge the given unsigned data, received later for the same event, into this one.
+    ///
+    /// The bundled relations that are present in `other` replace the ones in `self`, since they
+    /// are more recent. The `age` and `transaction_id` of `self` are kept if they are set.
+    pub fn merge(&mut self, other: Self) {
+        let Self { age, transaction_id, relations } = other;
+
+        if self.age.is_none() {
+            self.age = age;
+        }
+        if self.transaction_id.is_none() {
+            self.transaction_id = transaction_id;
+        }
+        self.relations.merge(relations);
+    }
 }
 
 impl<C: MessageLikeEventContent> Default for MessageLikeUnsigned<C> {
@@ -66,6 +95,14 @@ pub struct StateUnsigned<C: PossiblyRedactedStateEventContent> {
     /// This field is generated by the local homeserver, and may be incorrect if the local time on
     /// at least one of the two servers is out of sync, which can cause the age to either be
     /// negative or greater than it actually is.
+    ///
+    /// If you activate the `compat-unsigned-age` feature, this field can also be decoded from a
+    /// floating-point number, which is truncated, or from a number outside of the range of `Int`,
+    /// which is clamped.
+    #[cfg_attr(
+        feature = "compat-unsigned-age",
+        serde(default, deserialize_with = "crate::unsigned::deserialize_lax_age")
+    )]
     pub age: Option<Int>,
 
     /// The client-supplied transaction ID, if the client being given the event is the same one
@@ -151,3 +188,76 @@ pub struct UnsignedRoomRedactionEvent {
     #[serde(default)]
     pub unsigned: MessageLikeUnsigned<RoomRedactionEventContent>,
 }
+
+/// Deserialize an optional `age`, accepting floating-point numbers and numbers outside of the
+/// range of `Int`.
+///
+/// Floating-point numbers are truncated and all numbers are clamped to the range of `Int`.
+#[cfg(feature = "compat-unsigned-age")]
+pub(crate) fn deserialize_lax_age<'de, D>(de: D) -> Result<Option<Int>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct LaxAgeVisitor;
+
+    impl<'de> Visitor<'de> for LaxAgeVisitor {
+        type Value = Option<Int>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            formatter.write_str("a number or null")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+            de.deserialize_any(self)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(Some(Int::new_saturating(v)))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(Some(Int::new_saturating(v.try_into().unwrap_or(i64::MAX))))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            // This conversion truncates the value, saturates on overflow and maps NaN to 0.
+            Ok(Some(Int::new_saturating(v as i64)))
+        }
+    }
+
+    de.deserialize_option(LaxAgeVisitor)
+}
+
+#[cfg(all(test, feature = "compat-unsigned-age"))]
+mod tests {
+    use js_int::{int, Int};
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::MessageLikeUnsigned;
+    use crate::room::message::RoomMessageEventContent;
+
+    fn age(json: serde_json::Value) -> Option<Int> {
+        from_json_value::<MessageLikeUnsigned<RoomMessageEventContent>>(json).unwrap().age
+    }
+
+    #[test]
+    fn lax_age() {
+        assert_eq!(age(json!({})), None);
+        assert_eq!(age(json!({ "age": null })), None);
+        assert_eq!(age(json!({ "age": 1000 })), Some(int!(1000)));
+        assert_eq!(age(json!({ "age": -1000 })), Some(int!(-1000)));
+        assert_eq!(age(json!({ "age": 1000.9 })), Some(int!(1000)));
+        assert_eq!(age(json!({ "age": u64::MAX })), Some(Int::MAX));
+        assert_eq!(age(json!({ "age": i64::MIN })), Some(Int::MIN));
+        assert_eq!(age(json!({ "age": 1e30 })), Some(Int::MAX));
+        assert_eq!(age(json!({ "age": -1e30 })), Some(Int::MIN));
+    }
+}
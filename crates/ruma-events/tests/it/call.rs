@@ -13,10 +13,12 @@ use ruma_events::{
         invite::CallInviteEventContent,
         negotiate::CallNegotiateEventContent,
         reject::CallRejectEventContent,
+        sdp_stream_metadata_changed::CallSdpStreamMetadataChangedEventContent,
         select_answer::CallSelectAnswerEventContent,
         SessionDescription,
     },
-    AnyMessageLikeEvent, AnySyncMessageLikeEvent, MessageLikeEvent,
+    AnyMessageLikeEvent, AnySyncMessageLikeEvent, EventContent, MessageLikeEvent,
+    MessageLikeEventType,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -608,3 +610,39 @@ fn select_v1_answer_event_deserialization() {
     assert_eq!(content.selected_party_id, "6336");
     assert_eq!(content.version, VoipVersionId::V1);
 }
+
+#[test]
+fn sdp_stream_metadata_changed_event_type_alias() {
+    let json_data = |event_type: &str| {
+        json!({
+            "content": {
+                "call_id": "abcdef",
+                "party_id": "9876",
+                "version": "1",
+            },
+            "event_id": "$event:notareal.hs",
+            "origin_server_ts": 134_829_848,
+            "room_id": "!roomid:notareal.hs",
+            "sender": "@user:notareal.hs",
+            "type": event_type,
+        })
+    };
+
+    for event_type in
+        ["m.call.sdp_stream_metadata_changed", "org.matrix.call.sdp_stream_metadata_changed"]
+    {
+        let event = from_json_value::<MessageLikeEvent<CallSdpStreamMetadataChangedEventContent>>(
+            json_data(event_type),
+        )
+        .unwrap();
+        assert_matches!(event, MessageLikeEvent::Original(event));
+        assert_eq!(event.content.call_id, "abcdef");
+
+        // The canonical type is always used when sending the content.
+        assert_eq!(event.content.event_type(), MessageLikeEventType::CallSdpStreamMetadataChanged);
+        assert_eq!(event.content.event_type().to_string(), "m.call.sdp_stream_metadata_changed");
+
+        let event = from_json_value::<AnyMessageLikeEvent>(json_data(event_type)).unwrap();
+        assert_matches!(event, AnyMessageLikeEvent::CallSdpStreamMetadataChanged(_));
+    }
+}
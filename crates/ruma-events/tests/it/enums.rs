@@ -7,14 +7,18 @@ use ruma_events::{
         message::{MessageType, RoomMessageEventContent},
         power_levels::RoomPowerLevelsEventContent,
     },
-    AnyEphemeralRoomEvent, AnyMessageLikeEvent, AnyStateEvent, AnySyncMessageLikeEvent,
+    AnyEphemeralRoomEvent, AnyFullStateEventContent, AnyMessageLikeEvent,
+    AnyMessageLikeEventContent, AnyStateEvent, AnyStateEventContent, AnySyncMessageLikeEvent,
     AnySyncStateEvent, AnySyncTimelineEvent, AnyTimelineEvent, EphemeralRoomEventType,
-    GlobalAccountDataEventType, MessageLikeEvent, MessageLikeEventType, OriginalMessageLikeEvent,
-    OriginalStateEvent, OriginalSyncMessageLikeEvent, OriginalSyncStateEvent,
-    RoomAccountDataEventType, StateEvent, StateEventType, SyncMessageLikeEvent, SyncStateEvent,
-    ToDeviceEventType,
+    EventContent, EventContentFromType, FullStateEventContent, GlobalAccountDataEventType,
+    MessageLikeEvent, MessageLikeEventType, OriginalMessageLikeEvent, OriginalStateEvent,
+    OriginalSyncMessageLikeEvent, OriginalSyncStateEvent, RoomAccountDataEventType, StateEvent,
+    StateEventType, SyncMessageLikeEvent, SyncStateEvent, TimelineEventType, ToDeviceEventType,
+};
+use serde_json::{
+    from_value as from_json_value, json, value::to_raw_value as to_raw_json_value,
+    Value as JsonValue,
 };
-use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
 fn message_event() -> JsonValue {
     json!({
@@ -253,6 +257,135 @@ fn alias_event_field_access() {
     assert_eq!(deser.event_type().to_string(), "m.room.aliases");
 }
 
+#[test]
+fn message_event_field_access() {
+    let events = [
+        from_json_value::<AnyMessageLikeEvent>(message_event()).unwrap(),
+        from_json_value::<AnyMessageLikeEvent>(json!({
+            "content": {},
+            "event_id": "$152037280074GZeOm:localhost",
+            "origin_server_ts": 1,
+            "sender": "@example:localhost",
+            "room_id": "!room:room.com",
+            "type": "m.room.message",
+            "unsigned": {
+                "redacted_because": {
+                    "type": "m.room.redaction",
+                    "content": {},
+                    "redacts": "$152037280074GZeOm:localhost",
+                    "event_id": "$h29iv0s8:example.com",
+                    "origin_server_ts": 1,
+                    "sender": "@carl:example.com",
+                },
+            },
+        }))
+        .unwrap(),
+    ];
+
+    // The accessors work for original and redacted events, without matching on the variant.
+    for event in &events {
+        assert_eq!(event.event_type(), MessageLikeEventType::RoomMessage);
+        assert_eq!(event.sender(), "@example:localhost");
+        assert_eq!(event.event_id(), "$152037280074GZeOm:localhost");
+        assert_eq!(event.room_id(), "!room:room.com");
+    }
+}
+
+#[test]
+fn message_event_content_access() {
+    let event = from_json_value::<AnyMessageLikeEvent>(message_event()).unwrap();
+    assert_matches!(
+        event.original_content(),
+        Some(AnyMessageLikeEventContent::RoomMessage(content))
+    );
+    assert_eq!(content.body(), "baba");
+
+    let sync_event = from_json_value::<AnySyncMessageLikeEvent>(message_event_sync()).unwrap();
+    assert_matches!(
+        sync_event.original_content(),
+        Some(AnyMessageLikeEventContent::RoomMessage(content))
+    );
+    assert_eq!(content.body(), "baba");
+
+    let redacted_event = from_json_value::<AnyMessageLikeEvent>(json!({
+        "content": {},
+        "event_id": "$152037280074GZeOm:localhost",
+        "origin_server_ts": 1,
+        "sender": "@example:localhost",
+        "room_id": "!room:room.com",
+        "type": "m.room.message",
+        "unsigned": {
+            "redacted_because": {
+                "type": "m.room.redaction",
+                "content": {},
+                "redacts": "$152037280074GZeOm:localhost",
+                "event_id": "$h29iv0s8:example.com",
+                "origin_server_ts": 1,
+                "sender": "@carl:example.com",
+            },
+        },
+    }))
+    .unwrap();
+    assert_matches!(redacted_event.original_content(), None);
+}
+
+#[test]
+fn state_event_content_access() {
+    let event = from_json_value::<AnyStateEvent>(aliases_event()).unwrap();
+    assert_matches!(
+        event.content(),
+        AnyFullStateEventContent::RoomAliases(FullStateEventContent::Original {
+            content,
+            prev_content: None,
+        })
+    );
+    assert_eq!(content.aliases, vec![room_alias_id!("#somewhere:localhost")]);
+
+    let sync_event = from_json_value::<AnySyncStateEvent>(aliases_event_sync()).unwrap();
+    assert_matches!(
+        sync_event.original_content(),
+        Some(AnyStateEventContent::RoomAliases(content))
+    );
+    assert_eq!(content.aliases, vec![room_alias_id!("#somewhere:localhost")]);
+}
+
+#[test]
+fn content_enum_event_type() {
+    let content =
+        AnyMessageLikeEventContent::RoomMessage(RoomMessageEventContent::text_plain("Hello"));
+    assert_eq!(content.event_type(), MessageLikeEventType::RoomMessage);
+
+    let content = AnyStateEventContent::RoomAliases(RoomAliasesEventContent::new(vec![]));
+    assert_eq!(content.event_type(), StateEventType::RoomAliases);
+
+    let raw_content = to_raw_json_value(&json!({ "foo": "bar" })).unwrap();
+    let content = AnyMessageLikeEventContent::from_parts("dev.ruma.custom", &raw_content).unwrap();
+    assert_eq!(content.event_type().to_string(), "dev.ruma.custom");
+}
+
+#[test]
+fn content_enum_from_parts() {
+    let raw_content = to_raw_json_value(&json!({ "aliases": ["#somewhere:localhost"] })).unwrap();
+    let content =
+        AnyStateEventContent::from_parts(&StateEventType::RoomAliases.to_string(), &raw_content)
+            .unwrap();
+    assert_matches!(content, AnyStateEventContent::RoomAliases(content));
+    assert_eq!(content.aliases, vec![room_alias_id!("#somewhere:localhost")]);
+
+    let raw_content = to_raw_json_value(&json!({ "msgtype": "m.text", "body": "Hello" })).unwrap();
+    let content = AnyMessageLikeEventContent::from_parts(
+        &MessageLikeEventType::RoomMessage.to_string(),
+        &raw_content,
+    )
+    .unwrap();
+    assert_matches!(content, AnyMessageLikeEventContent::RoomMessage(content));
+    assert_eq!(content.body(), "Hello");
+
+    // The content must match the type.
+    AnyStateEventContent::from_parts(&StateEventType::RoomAliases.to_string(), &raw_content)
+        .unwrap_err();
+}
+
 #[test]
 fn ephemeral_event_deserialization() {
     let json_data = json!({
@@ -282,3 +415,33 @@ fn serialize_and_deserialize_from_display_form() {
     serde_json_eq(StateEventType::RoomCreate, json!("m.room.create"));
     serde_json_eq(EphemeralRoomEventType::Typing, json!("m.typing"));
 }
+
+#[test]
+fn event_type_from_str() {
+    assert_eq!(TimelineEventType::from("m.room.member"), TimelineEventType::RoomMember);
+    assert!(!TimelineEventType::from("m.room.member").is_custom());
+    assert!(!EphemeralRoomEventType::from("m.typing").is_custom());
+    assert!(!GlobalAccountDataEventType::from("m.secret_storage.key.abc").is_custom());
+
+    let custom = StateEventType::from("dev.ruma.custom");
+    assert!(custom.is_custom());
+    assert_eq!(custom.to_string(), "dev.ruma.custom");
+}
+
+#[test]
+fn timeline_event_type_kind() {
+    let member = TimelineEventType::from("m.room.member");
+    assert!(member.is_state());
+    assert!(!member.is_message_like());
+
+    let message = TimelineEventType::from("m.room.message");
+    assert!(!message.is_state());
+    assert!(message.is_message_like());
+
+    let custom = TimelineEventType::from("dev.ruma.custom");
+    assert!(!custom.is_state());
+    assert!(!custom.is_message_like());
+
+    assert!(TimelineEventType::from(StateEventType::RoomCreate).is_state());
+    assert!(TimelineEventType::from(MessageLikeEventType::CallAnswer).is_message_like());
+}
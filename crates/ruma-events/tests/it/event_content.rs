@@ -8,4 +8,5 @@ fn ui() {
     t.pass("tests/it/ui/11-content-without-relation-sanity-check.rs");
     t.compile_fail("tests/it/ui/12-no-relates_to.rs");
     t.pass("tests/it/ui/13-private-event-content-type.rs");
+    t.pass("tests/it/ui/14-message-like-content-wildcard.rs");
 }
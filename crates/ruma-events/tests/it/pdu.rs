@@ -244,3 +244,67 @@ fn deserialize_pdu_as_v3() {
         _ => unreachable!("new PDU version"),
     }
 }
+
+#[test]
+#[cfg(feature = "canonical-json")]
+fn build_message_like_pdu() {
+    use ruma_events::{pdu::PduBuilder, room::message::RoomMessageEventContent};
+
+    let pdu = PduBuilder::message_like(
+        owned_room_id!("!n8f893n9:example.com"),
+        owned_user_id!("@sender:example.com"),
+        &RoomMessageEventContent::text_plain("Hello"),
+    )
+    .unwrap()
+    .with_prev_events(vec![owned_event_id!("$previousevent:example.com")])
+    .with_auth_events(vec![owned_event_id!("$someauthevent:example.com")])
+    .with_depth(uint!(12))
+    .with_origin_server_ts(MilliSecondsSinceUnixEpoch(1_592_050_773_658_u64.try_into().unwrap()))
+    .build();
+
+    assert_eq!(
+        to_json_value(&pdu).unwrap(),
+        json!({
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@sender:example.com",
+            "origin_server_ts": 1_592_050_773_658_u64,
+            "type": "m.room.message",
+            "content": {
+                "msgtype": "m.text",
+                "body": "Hello",
+            },
+            "prev_events": ["$previousevent:example.com"],
+            "auth_events": ["$someauthevent:example.com"],
+            "depth": 12,
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "canonical-json")]
+fn build_state_pdu() {
+    use ruma_events::{pdu::PduBuilder, room::topic::RoomTopicEventContent};
+
+    let before = MilliSecondsSinceUnixEpoch::now();
+    let pdu = PduBuilder::state(
+        owned_room_id!("!n8f893n9:example.com"),
+        owned_user_id!("@sender:example.com"),
+        &ruma_events::EmptyStateKey,
+        &RoomTopicEventContent::new("Hello".to_owned()),
+    )
+    .unwrap()
+    .with_depth(uint!(3))
+    .build();
+
+    // The PDU can be deserialized once the hashes and signatures are added.
+    let mut value = to_json_value(&pdu).unwrap();
+    value["hashes"] = json!({ "sha256": "ThisHashCoversAllFieldsInCaseThisIsRedacted" });
+    value["signatures"] = json!({});
+    let pdu = from_json_value::<RoomV3Pdu>(value).unwrap();
+
+    assert_eq!(pdu.kind, TimelineEventType::RoomTopic);
+    assert_eq!(pdu.state_key.as_deref(), Some(""));
+    assert_eq!(pdu.content.get(), r#"{"topic":"Hello"}"#);
+    assert!(pdu.origin_server_ts >= before);
+    assert!(pdu.prev_events.is_empty());
+}
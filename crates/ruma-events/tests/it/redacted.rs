@@ -1,5 +1,6 @@
 use assert_matches2::assert_matches;
-use ruma_common::RoomVersionId;
+use js_int::int;
+use ruma_common::{room::RoomType, RoomVersionId};
 use ruma_events::{
     room::{
         aliases::RedactedRoomAliasesEventContent,
@@ -135,6 +136,53 @@ fn deserialize_redacted_state_event() {
     assert_eq!(redacted.content.creator.unwrap(), "@carl:example.com");
 }
 
+#[test]
+fn deserialize_redacted_state_events_with_fields_kept_by_room_version() {
+    // Since room version 11, all the fields of `m.room.create` are kept on redaction.
+    let redacted = json!({
+        "content": {
+            "m.federate": false,
+            "room_version": "11",
+            "type": "m.space",
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "unsigned": unsigned(),
+        "type": "m.room.create",
+    });
+
+    assert_matches!(
+        from_json_value::<AnySyncStateEvent>(redacted),
+        Ok(AnySyncStateEvent::RoomCreate(SyncStateEvent::Redacted(redacted)))
+    );
+    assert!(!redacted.content.federate);
+    assert_eq!(redacted.content.room_version, RoomVersionId::V11);
+    assert_eq!(redacted.content.room_type, Some(RoomType::Space));
+
+    // Since room version 11, `invite` is kept in `m.room.power_levels` on redaction.
+    let redacted = json!({
+        "content": {
+            "ban": 50,
+            "invite": 30,
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "unsigned": unsigned(),
+        "type": "m.room.power_levels",
+    });
+
+    assert_matches!(
+        from_json_value::<AnySyncStateEvent>(redacted),
+        Ok(AnySyncStateEvent::RoomPowerLevels(SyncStateEvent::Redacted(redacted)))
+    );
+    assert_eq!(redacted.content.ban, int!(50));
+    assert_eq!(redacted.content.invite, int!(30));
+}
+
 #[test]
 fn deserialize_redacted_custom_event() {
     let redacted = json!({
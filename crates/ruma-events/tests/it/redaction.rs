@@ -82,3 +82,86 @@ fn deserialize_redaction_missing_redacts() {
 
     from_json_value::<AnyMessageLikeEvent>(json_data).unwrap_err();
 }
+
+#[test]
+#[cfg(feature = "canonical-json")]
+fn redact_raw_event_in_place() {
+    use ruma_common::{
+        canonical_json::{redact_in_place, RedactedBecause},
+        serde::Raw,
+        CanonicalJsonObject,
+    };
+    use ruma_events::{room::message::RoomMessageEvent, AnyTimelineEvent};
+    use serde_json::value::to_raw_value as to_raw_json_value;
+
+    let event = Raw::<AnyTimelineEvent>::from_json(
+        to_raw_json_value(&json!({
+            "content": {
+                "body": "Hello, world!",
+                "msgtype": "m.text"
+            },
+            "event_id": "$message:example.com",
+            "sender": "@alice:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!roomid:example.com",
+            "type": "m.room.message",
+            "unsigned": {
+                "age": 100
+            }
+        }))
+        .unwrap(),
+    );
+    let redaction = Raw::<RoomRedactionEvent>::from_json(
+        to_raw_json_value(&json!({
+            "content": {
+                "redacts": "$message:example.com",
+                "reason": "spam"
+            },
+            "event_id": "$redaction:example.com",
+            "sender": "@carl:example.com",
+            "origin_server_ts": 2,
+            "room_id": "!roomid:example.com",
+            "type": "m.room.redaction"
+        }))
+        .unwrap(),
+    );
+
+    let mut object = event.deserialize_as::<CanonicalJsonObject>().unwrap();
+    let redacted_because = RedactedBecause::from_raw_event(&redaction).unwrap();
+    redact_in_place(&mut object, &RoomVersionId::V11, Some(redacted_because)).unwrap();
+
+    // The content and the previous unsigned data are removed.
+    assert_eq!(
+        to_json_value(&object).unwrap(),
+        json!({
+            "content": {},
+            "event_id": "$message:example.com",
+            "sender": "@alice:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!roomid:example.com",
+            "type": "m.room.message",
+            "unsigned": {
+                "redacted_because": {
+                    "content": {
+                        "redacts": "$message:example.com",
+                        "reason": "spam"
+                    },
+                    "event_id": "$redaction:example.com",
+                    "sender": "@carl:example.com",
+                    "origin_server_ts": 2,
+                    "room_id": "!roomid:example.com",
+                    "type": "m.room.redaction"
+                }
+            }
+        })
+    );
+
+    let event = Raw::<AnyTimelineEvent>::from_json(to_raw_json_value(&object).unwrap());
+    assert_matches!(
+        event.deserialize(),
+        Ok(AnyTimelineEvent::MessageLike(AnyMessageLikeEvent::RoomMessage(
+            RoomMessageEvent::Redacted(redacted)
+        )))
+    );
+    assert_eq!(redacted.unsigned.redacted_because.event_id, "$redaction:example.com");
+}
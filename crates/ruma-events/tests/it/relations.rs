@@ -1,9 +1,11 @@
 use assert_matches2::assert_matches;
 use assign::assign;
+use js_int::{int, uint};
 use ruma_common::{owned_event_id, serde::Raw};
 use ruma_events::{
     relation::{CustomRelation, InReplyTo, Replacement, Thread},
     room::message::{MessageType, Relation, RoomMessageEventContent},
+    MessageLikeUnsigned,
 };
 use serde_json::{
     from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
@@ -387,3 +389,59 @@ fn custom_serialization_roundtrip() {
     assert_eq!(deser_relation.get("event_id").unwrap().as_str().unwrap(), event_id);
     assert_eq!(deser_relation.get("key").unwrap().as_str().unwrap(), key);
 }
+
+#[test]
+fn merge_unsigned_relations() {
+    let mut unsigned = from_json_value::<MessageLikeUnsigned<RoomMessageEventContent>>(json!({
+        "age": 1000,
+        "transaction_id": "txn",
+        "m.relations": {
+            "m.reference": {
+                "chunk": [{ "event_id": "$reference1" }],
+            },
+        },
+    }))
+    .unwrap();
+    let newer = from_json_value::<MessageLikeUnsigned<RoomMessageEventContent>>(json!({
+        "age": 5000,
+        "m.relations": {
+            "m.thread": {
+                "latest_event": {
+                    "content": { "body": "Threaded", "msgtype": "m.text" },
+                    "event_id": "$thread_reply",
+                    "origin_server_ts": 1,
+                    "room_id": "!room:localhost",
+                    "sender": "@alice:localhost",
+                    "type": "m.room.message",
+                },
+                "count": 1,
+                "current_user_participated": false,
+            },
+        },
+    }))
+    .unwrap();
+
+    unsigned.merge(newer);
+
+    assert_eq!(unsigned.age, Some(int!(1000)));
+    assert_eq!(unsigned.transaction_id.as_deref().unwrap(), "txn");
+    let reference = unsigned.relations.reference.as_ref().unwrap();
+    assert_eq!(reference.chunk.len(), 1);
+    assert_eq!(reference.chunk[0].event_id, "$reference1");
+    let thread = unsigned.relations.thread.as_ref().unwrap();
+    assert_eq!(thread.count, uint!(1));
+
+    let newest = from_json_value::<MessageLikeUnsigned<RoomMessageEventContent>>(json!({
+        "m.relations": {
+            "m.reference": {
+                "chunk": [{ "event_id": "$reference1" }, { "event_id": "$reference2" }],
+            },
+        },
+    }))
+    .unwrap();
+
+    unsigned.merge(newest);
+
+    assert_eq!(unsigned.relations.reference.unwrap().chunk.len(), 2);
+    assert!(unsigned.relations.thread.is_some());
+}
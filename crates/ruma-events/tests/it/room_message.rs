@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
 use assert_matches2::assert_matches;
 use js_int::uint;
@@ -75,6 +75,36 @@ fn custom_msgtype_deserialization() {
     assert_eq!(custom_event.data(), Cow::Owned(expected_json_data));
 }
 
+#[test]
+fn custom_msgtype_content_round_trip() {
+    let json_data = json!({
+        "msgtype": "my_custom_msgtype",
+        "body": "my custom message",
+        "custom_field": "baba",
+        "m.mentions": {
+            "user_ids": ["@alice:example.org"],
+        },
+        "m.relates_to": {
+            "m.in_reply_to": {
+                "event_id": "$abc:example.org",
+            },
+        },
+    });
+
+    let content = from_json_value::<RoomMessageEventContent>(json_data.clone()).unwrap();
+    assert_eq!(content.msgtype.msgtype(), "my_custom_msgtype");
+    assert_eq!(content.msgtype.body(), "my custom message");
+    assert_eq!(content.msgtype.data(), Cow::Owned(json_object! { "custom_field": "baba" }));
+    assert_matches!(&content.relates_to, Some(Relation::Reply { .. }));
+    assert_eq!(content.mentions.as_ref().unwrap().user_ids.len(), 1);
+
+    // The fields of the content must not be serialized twice.
+    let serialized = serde_json::to_string(&content).unwrap();
+    assert_eq!(serialized.matches("m.relates_to").count(), 1);
+    assert_eq!(serialized.matches("m.mentions").count(), 1);
+    assert_eq!(serde_json::from_str::<JsonValue>(&serialized).unwrap(), json_data);
+}
+
 #[test]
 fn text_msgtype_formatted_body_serialization() {
     let message_event_content =
@@ -1070,3 +1100,201 @@ fn test_video_caption() {
         Some("You missed a <strong>great</strong> evening".to_owned())
     );
 }
+
+#[test]
+fn local_age() {
+    let event = OriginalRoomMessageEvent {
+        content: RoomMessageEventContent::text_plain("Hello"),
+        event_id: owned_event_id!("$event"),
+        origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+        room_id: owned_room_id!("!testroomid:example.org"),
+        sender: owned_user_id!("@user:example.org"),
+        unsigned: MessageLikeUnsigned::default(),
+    };
+
+    assert_eq!(
+        event.local_age(MilliSecondsSinceUnixEpoch(uint!(12_500))),
+        Duration::from_millis(2_500)
+    );
+    assert_eq!(event.local_age(MilliSecondsSinceUnixEpoch(uint!(10_000))), Duration::ZERO);
+
+    let sync_event = OriginalSyncRoomMessageEvent::from(event);
+    assert_eq!(
+        sync_event.local_age(MilliSecondsSinceUnixEpoch(uint!(70_000))),
+        Duration::from_secs(60)
+    );
+}
+
+#[test]
+fn local_age_future_dated_event() {
+    let event = OriginalRoomMessageEvent {
+        content: RoomMessageEventContent::text_plain("Hello from the future"),
+        event_id: owned_event_id!("$event"),
+        origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+        room_id: owned_room_id!("!testroomid:example.org"),
+        sender: owned_user_id!("@user:example.org"),
+        unsigned: MessageLikeUnsigned::default(),
+    };
+
+    assert_eq!(event.local_age(MilliSecondsSinceUnixEpoch(uint!(5_000))), Duration::ZERO);
+}
+
+#[test]
+fn sync_message_event_into_full_event() {
+    let json_data = json!({
+        "content": {
+            "body": "Hello",
+            "msgtype": "m.text",
+        },
+        "event_id": "$event",
+        "origin_server_ts": 10_000,
+        "sender": "@user:example.org",
+        "type": "m.room.message",
+    });
+
+    let sync_ev = from_json_value::<OriginalSyncRoomMessageEvent>(json_data).unwrap();
+    let full_ev = sync_ev.into_full_event(owned_room_id!("!testroomid:example.org"));
+
+    assert_eq!(full_ev.content.body(), "Hello");
+    assert_eq!(full_ev.event_id, "$event");
+    assert_eq!(full_ev.origin_server_ts, MilliSecondsSinceUnixEpoch(uint!(10_000)));
+    assert_eq!(full_ev.room_id, "!testroomid:example.org");
+    assert_eq!(full_ev.sender, "@user:example.org");
+}
+
+#[test]
+fn mentions_difference_for_edit() {
+    let previous = Mentions::with_user_ids([
+        owned_user_id!("@a:example.org"),
+        owned_user_id!("@b:example.org"),
+    ]);
+    let mut new = Mentions::with_user_ids([
+        owned_user_id!("@a:example.org"),
+        owned_user_id!("@c:example.org"),
+    ]);
+
+    let difference = new.difference(&previous);
+    assert_eq!(difference.user_ids, [owned_user_id!("@c:example.org")].into());
+    assert!(!difference.room);
+
+    new.merge(Mentions::with_room_mention());
+    assert!(new.difference(&previous).room);
+    assert!(!new.difference(&Mentions::with_room_mention()).room);
+}
+
+#[test]
+#[cfg(feature = "html")]
+fn mentions_from_html() {
+    let mentions = Mentions::from_html(
+        "<p>Hello <a href=\"matrix:u/alice:example.org?action=chat\">Alice</a> and \
+         <a href=\"https://matrix.to/#/%40bob%3Aexample.org\">Bob</a>!</p>\
+         <p>See <a href=\"https://matrix.to/#/#room:example.org\">#room</a> or \
+         <a href=\"https://example.org/@carl:example.org\">Carl</a>.</p>",
+    );
+    assert_eq!(
+        mentions.user_ids,
+        [owned_user_id!("@alice:example.org"), owned_user_id!("@bob:example.org")].into()
+    );
+    assert!(!mentions.room);
+
+    let mentions = Mentions::from_html("<blockquote><strong>@room</strong> look!</blockquote>");
+    assert!(mentions.user_ids.is_empty());
+    assert!(mentions.room);
+
+    // The display name of a mentioned user is ignored.
+    let mentions =
+        Mentions::from_html("<a href=\"https://matrix.to/#/@room:example.org\">@room</a>");
+    assert_eq!(mentions.user_ids, [owned_user_id!("@room:example.org")].into());
+    assert!(!mentions.room);
+
+    // `@room` must be a separate word.
+    let mentions = Mentions::from_html("Hey @room.");
+    assert!(mentions.room);
+    let mentions = Mentions::from_html("<p>@room</p>");
+    assert!(mentions.room);
+    let mentions = Mentions::from_html("Look at my @roomba!");
+    assert!(!mentions.room);
+    let mentions = Mentions::from_html("Write to me@room.org");
+    assert!(!mentions.room);
+    let mentions = Mentions::from_html("Hey @room_1 and @room2");
+    assert!(!mentions.room);
+
+    // The rich reply fallback is ignored.
+    let mentions = Mentions::from_html(
+        "<mx-reply><blockquote>\
+            <a href=\"https://matrix.to/#/!room:example.org/$event\">In reply to</a> \
+            <a href=\"https://matrix.to/#/@alice:example.org\">@alice:example.org</a>\
+            <br>@room, meeting now!\
+        </blockquote></mx-reply>\
+        On my way, <a href=\"https://matrix.to/#/@bob:example.org\">Bob</a>!",
+    );
+    assert_eq!(mentions.user_ids, [owned_user_id!("@bob:example.org")].into());
+    assert!(!mentions.room);
+}
+
+#[test]
+fn deserialize_with_path() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Wrapper {
+        #[allow(dead_code)]
+        list: Vec<RoomMessageEventContent>,
+    }
+
+    // The path is tracked up to the content.
+    let raw: Raw<Wrapper> = from_json_value(json!({
+        "list": [
+            { "msgtype": "m.text", "body": "ok" },
+            { "msgtype": "m.text", "body": 42 },
+        ],
+    }))
+    .unwrap();
+    let error = raw.deserialize_with_path().unwrap_err();
+    assert_eq!(error.path(), "list[1]");
+    assert!(error.inner().to_string().starts_with("invalid type: integer `42`, expected a string"));
+
+    // The content is deserialized from a buffer, so the path of its fields is lost.
+    let raw: Raw<RoomMessageEventContent> = from_json_value(json!({
+        "msgtype": "m.text",
+        "body": "ok",
+        "m.relates_to": {
+            "m.in_reply_to": { "event_id": 42 },
+        },
+    }))
+    .unwrap();
+    let error = raw.deserialize_with_path().unwrap_err();
+    assert_eq!(error.path(), ".");
+    assert!(error.inner().to_string().starts_with("invalid type: integer `42`, expected a string"));
+
+    let raw: Raw<RoomMessageEventContent> = from_json_value(json!({
+        "msgtype": "m.image",
+        "body": "image.png",
+        "url": "mxc://notareal.hs/file",
+        "info": { "w": "wide" },
+    }))
+    .unwrap();
+    let error = raw.deserialize_with_path().unwrap_err();
+    assert_eq!(error.path(), ".");
+
+    // The event tracks the path of its own fields, but not inside the content.
+    let raw: Raw<OriginalSyncRoomMessageEvent> = from_json_value(json!({
+        "type": "m.room.message",
+        "event_id": "$event",
+        "sender": 5,
+        "origin_server_ts": 1,
+        "content": { "msgtype": "m.text", "body": "ok" },
+    }))
+    .unwrap();
+    let error = raw.deserialize_with_path().unwrap_err();
+    assert_eq!(error.path(), "sender");
+
+    let raw: Raw<OriginalSyncRoomMessageEvent> = from_json_value(json!({
+        "type": "m.room.message",
+        "event_id": "$event",
+        "sender": "@user:notareal.hs",
+        "origin_server_ts": 1,
+        "content": { "msgtype": "m.text", "body": 42 },
+    }))
+    .unwrap();
+    let error = raw.deserialize_with_path().unwrap_err();
+    assert_eq!(error.path(), ".");
+}
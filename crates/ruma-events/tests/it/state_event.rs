@@ -1,9 +1,11 @@
 use assert_matches2::assert_matches;
 use js_int::uint;
-use ruma_common::{mxc_uri, room_alias_id, serde::CanBeEmpty, MilliSecondsSinceUnixEpoch};
+use ruma_common::{
+    mxc_uri, owned_room_id, room_alias_id, serde::CanBeEmpty, MilliSecondsSinceUnixEpoch,
+};
 use ruma_events::{
-    room::aliases::RoomAliasesEventContent, AnyStateEvent, AnySyncStateEvent, AnyTimelineEvent,
-    StateEvent, SyncStateEvent,
+    room::{aliases::RoomAliasesEventContent, name::RoomNameEventContent},
+    AnyStateEvent, AnySyncStateEvent, AnyTimelineEvent, StateEvent, SyncStateEvent,
 };
 use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
@@ -54,6 +56,61 @@ fn deserialize_aliases_with_prev_content() {
     assert_eq!(prev_content.aliases.unwrap(), vec![room_alias_id!("#inner:localhost")]);
 }
 
+#[test]
+fn aliases_content_change() {
+    let json_data = aliases_event_with_prev_content();
+
+    let ev = from_json_value::<StateEvent<RoomAliasesEventContent>>(json_data).unwrap();
+    let ev = ev.as_original().unwrap();
+    let (prev_content, content) = ev.content_change().unwrap();
+    assert_eq!(prev_content.aliases.clone().unwrap(), vec![room_alias_id!("#inner:localhost")]);
+    assert_eq!(content.aliases, vec![room_alias_id!("#somewhere:localhost")]);
+
+    let mut json_data = aliases_event_with_prev_content();
+    json_data.as_object_mut().unwrap().remove("unsigned");
+    let ev = from_json_value::<SyncStateEvent<RoomAliasesEventContent>>(json_data).unwrap();
+    assert!(ev.as_original().unwrap().content_change().is_none());
+}
+
+#[test]
+fn deserialize_state_event_with_non_empty_state_key() {
+    // `m.room.name` uses `EmptyStateKey`, so a non-empty state key is invalid.
+    let json_data = json!({
+        "content": {
+            "name": "Room",
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "state_key": "not-empty",
+        "type": "m.room.name",
+    });
+
+    from_json_value::<StateEvent<RoomNameEventContent>>(json_data.clone()).unwrap_err();
+    from_json_value::<AnyStateEvent>(json_data.clone()).unwrap_err();
+    from_json_value::<AnySyncStateEvent>(json_data).unwrap_err();
+}
+
+#[test]
+fn sync_state_event_into_full_event() {
+    let json_data = aliases_event_with_prev_content();
+
+    let sync_ev = from_json_value::<AnySyncStateEvent>(json_data).unwrap();
+    let full_ev = sync_ev.into_full_event(owned_room_id!("!other:room.com"));
+
+    assert_matches!(full_ev, AnyStateEvent::RoomAliases(StateEvent::Original(ev)));
+    assert_eq!(ev.content.aliases, vec![room_alias_id!("#somewhere:localhost")]);
+    assert_eq!(ev.event_id, "$h29iv0s8:example.com");
+    assert_eq!(ev.origin_server_ts, MilliSecondsSinceUnixEpoch(uint!(1)));
+    assert_eq!(ev.room_id, "!other:room.com");
+    assert_eq!(ev.sender, "@carl:example.com");
+    assert_eq!(ev.state_key, "room.com");
+
+    let prev_content = ev.unsigned.prev_content.unwrap();
+    assert_eq!(prev_content.aliases.unwrap(), vec![room_alias_id!("#inner:localhost")]);
+}
+
 #[test]
 fn deserialize_aliases_sync_with_room_id() {
     // The same JSON can be used to create a sync event, it just ignores the `room_id` field
@@ -0,0 +1,64 @@
+use ruma_common::RoomVersionId;
+use ruma_events::{
+    EventContent, EventContentFromType, MessageLikeEvent, RedactContent,
+    RedactedMessageLikeEventContent,
+};
+use ruma_macros::EventContent;
+use serde::Serialize;
+use serde_json::{from_value as from_json_value, json};
+
+#[derive(Clone, Debug, Serialize, EventContent)]
+#[ruma_event(type = "org.example.custom.*", kind = MessageLike)]
+pub struct MacroTestContent {
+    #[ruma_event(type_fragment)]
+    #[serde(skip)]
+    pub frag: String,
+
+    #[ruma_event(skip_redaction)]
+    pub kept: String,
+
+    pub body: String,
+}
+
+fn assert_redacted_content<T: RedactedMessageLikeEventContent>() {}
+
+fn main() {
+    assert_redacted_content::<RedactedMacroTestContent>();
+
+    let content = MacroTestContent::from_parts(
+        "org.example.custom.foo",
+        &serde_json::value::to_raw_value(&json!({ "kept": "a", "body": "b" })).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(content.frag, "foo");
+    assert_eq!(content.event_type().to_string(), "org.example.custom.foo");
+
+    let redacted = content.redact(&RoomVersionId::V11);
+    assert_eq!(redacted.frag, "foo");
+    assert_eq!(redacted.kept, "a");
+    assert_eq!(redacted.event_type().to_string(), "org.example.custom.foo");
+
+    let event = from_json_value::<MessageLikeEvent<MacroTestContent>>(json!({
+        "content": { "kept": "a" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": "org.example.custom.bar",
+        "unsigned": {
+            "redacted_because": {
+                "content": {},
+                "event_id": "$redaction:example.com",
+                "origin_server_ts": 2,
+                "redacts": "$h29iv0s8:example.com",
+                "room_id": "!roomid:room.com",
+                "sender": "@carl:example.com",
+                "type": "m.room.redaction",
+            },
+        },
+    }))
+    .unwrap();
+    let MessageLikeEvent::Redacted(event) = event else { panic!("event should be redacted") };
+    assert_eq!(event.content.frag, "bar");
+    assert_eq!(event.content.kept, "a");
+}
@@ -1,8 +1,9 @@
 use assert_matches2::assert_matches;
-use ruma_common::owned_event_id;
+use ruma_common::{owned_event_id, owned_user_id};
 use ruma_events::{
-    relation::InReplyTo,
+    relation::{InReplyTo, Thread},
     room::message::{MessageType, Relation, RoomMessageEventContent},
+    Mentions,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -47,3 +48,24 @@ fn convert_room_message_content_without_relation_to_full() {
     assert_eq!(text.body, "Hello, world!");
     assert_matches!(relates_to, None);
 }
+
+#[test]
+fn room_message_content_without_relation_round_trip() {
+    let mut content = RoomMessageEventContent::text_plain("Hello, world!")
+        .add_mentions(Mentions::with_user_ids([owned_user_id!("@alice:example.org")]));
+    content.relates_to =
+        Some(Relation::Reply { in_reply_to: InReplyTo::new(owned_event_id!("$eventId")) });
+
+    let without_relation = content.without_relation();
+    let mentions = without_relation.mentions.as_ref().unwrap();
+    assert!(mentions.user_ids.contains(&owned_user_id!("@alice:example.org")));
+
+    let thread = Thread::plain(owned_event_id!("$thread_root"), owned_event_id!("$latest"));
+    let new_content = without_relation.with_relation(Some(Relation::Thread(thread)));
+
+    assert_matches!(new_content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "Hello, world!");
+    assert_matches!(new_content.relates_to, Some(Relation::Thread(thread)));
+    assert_eq!(thread.event_id, "$thread_root");
+    assert!(new_content.mentions.unwrap().user_ids.contains(&owned_user_id!("@alice:example.org")));
+}
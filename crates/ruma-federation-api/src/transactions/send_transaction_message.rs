@@ -93,4 +93,184 @@ pub mod v1 {
             Self { pdus }
         }
     }
+
+    /// A builder for transactions that respect the limits of the spec on the number of PDUs and
+    /// EDUs.
+    ///
+    /// PDUs and EDUs can be added one at a time with [`push_pdu()`] and [`push_edu()`], which
+    /// refuse to add them beyond the limits, or in bulk with [`extend_pdus()`] and
+    /// [`extend_edus()`]. In both cases, [`split()`] builds as many transactions as necessary.
+    ///
+    /// [`push_pdu()`]: Self::push_pdu
+    /// [`push_edu()`]: Self::push_edu
+    /// [`extend_pdus()`]: Self::extend_pdus
+    /// [`extend_edus()`]: Self::extend_edus
+    /// [`split()`]: Self::split
+    #[derive(Clone, Debug)]
+    #[must_use]
+    pub struct TransactionBuilder {
+        origin: OwnedServerName,
+        origin_server_ts: MilliSecondsSinceUnixEpoch,
+        pdus: Vec<Box<RawJsonValue>>,
+        edus: Vec<Raw<Edu>>,
+    }
+
+    impl TransactionBuilder {
+        /// The maximum number of PDUs in a transaction.
+        pub const MAX_PDUS: usize = 50;
+
+        /// The maximum number of EDUs in a transaction.
+        pub const MAX_EDUS: usize = 100;
+
+        /// Creates a new empty `TransactionBuilder` with the given origin and timestamp.
+        pub fn new(origin: OwnedServerName, origin_server_ts: MilliSecondsSinceUnixEpoch) -> Self {
+            Self { origin, origin_server_ts, pdus: Vec::new(), edus: Vec::new() }
+        }
+
+        /// Add the given PDU, if there are less than [`MAX_PDUS`](Self::MAX_PDUS) PDUs.
+        ///
+        /// Returns the PDU back if it can't be added.
+        pub fn push_pdu(&mut self, pdu: Box<RawJsonValue>) -> Result<(), Box<RawJsonValue>> {
+            if self.pdus.len() >= Self::MAX_PDUS {
+                return Err(pdu);
+            }
+
+            self.pdus.push(pdu);
+            Ok(())
+        }
+
+        /// Add the given EDU, if there are less than [`MAX_EDUS`](Self::MAX_EDUS) EDUs.
+        ///
+        /// Returns the EDU back if it can't be added.
+        pub fn push_edu(&mut self, edu: Raw<Edu>) -> Result<(), Raw<Edu>> {
+            if self.edus.len() >= Self::MAX_EDUS {
+                return Err(edu);
+            }
+
+            self.edus.push(edu);
+            Ok(())
+        }
+
+        /// Add all the given PDUs, regardless of the limit.
+        pub fn extend_pdus(&mut self, pdus: impl IntoIterator<Item = Box<RawJsonValue>>) {
+            self.pdus.extend(pdus);
+        }
+
+        /// Add all the given EDUs, regardless of the limit.
+        pub fn extend_edus(&mut self, edus: impl IntoIterator<Item = Raw<Edu>>) {
+            self.edus.extend(edus);
+        }
+
+        /// Whether no more PDUs nor EDUs can be added with [`push_pdu()`](Self::push_pdu) and
+        /// [`push_edu()`](Self::push_edu).
+        pub fn is_full(&self) -> bool {
+            self.pdus.len() >= Self::MAX_PDUS && self.edus.len() >= Self::MAX_EDUS
+        }
+
+        /// Build the transactions containing all the PDUs and EDUs, in the order they were added.
+        ///
+        /// Each transaction contains at most [`MAX_PDUS`](Self::MAX_PDUS) PDUs and
+        /// [`MAX_EDUS`](Self::MAX_EDUS) EDUs. `transaction_id` is called to get a unique ID for
+        /// each transaction.
+        ///
+        /// Returns an empty list if there are no PDUs nor EDUs.
+        pub fn split(self, mut transaction_id: impl FnMut() -> OwnedTransactionId) -> Vec<Request> {
+            let Self { origin, origin_server_ts, pdus, edus } = self;
+
+            let mut pdus = pdus.into_iter().peekable();
+            let mut edus = edus.into_iter().peekable();
+            let mut transactions = Vec::new();
+
+            while pdus.peek().is_some() || edus.peek().is_some() {
+                let mut request = Request::new(transaction_id(), origin.clone(), origin_server_ts);
+                request.pdus = pdus.by_ref().take(Self::MAX_PDUS).collect();
+                request.edus = edus.by_ref().take(Self::MAX_EDUS).collect();
+                transactions.push(request);
+            }
+
+            transactions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::{serde::Raw, server_name, MilliSecondsSinceUnixEpoch, OwnedTransactionId};
+    use serde_json::{
+        json,
+        value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
+    };
+
+    use super::v1::TransactionBuilder;
+    use crate::transactions::edu::Edu;
+
+    fn builder() -> TransactionBuilder {
+        TransactionBuilder::new(
+            server_name!("example.org").to_owned(),
+            MilliSecondsSinceUnixEpoch::now(),
+        )
+    }
+
+    fn pdu(i: usize) -> Box<RawJsonValue> {
+        to_raw_json_value(&json!({ "depth": i })).unwrap()
+    }
+
+    fn edu() -> Raw<Edu> {
+        Raw::new(&json!({ "edu_type": "m.typing", "content": {} })).unwrap().cast()
+    }
+
+    fn transaction_ids() -> impl FnMut() -> OwnedTransactionId {
+        let mut count = 0;
+        move || {
+            count += 1;
+            format!("txn{count}").into()
+        }
+    }
+
+    #[test]
+    fn push_refuses_beyond_limits() {
+        let mut builder = builder();
+
+        for i in 0..TransactionBuilder::MAX_PDUS {
+            builder.push_pdu(pdu(i)).unwrap();
+        }
+        let pdu = builder.push_pdu(pdu(50)).unwrap_err();
+        assert_eq!(pdu.get(), r#"{"depth":50}"#);
+        assert!(!builder.is_full());
+
+        for _ in 0..TransactionBuilder::MAX_EDUS {
+            builder.push_edu(edu()).unwrap();
+        }
+        builder.push_edu(edu()).unwrap_err();
+        assert!(builder.is_full());
+
+        let transactions = builder.split(transaction_ids());
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].pdus.len(), TransactionBuilder::MAX_PDUS);
+        assert_eq!(transactions[0].edus.len(), TransactionBuilder::MAX_EDUS);
+    }
+
+    #[test]
+    fn split_oversized_batch() {
+        let mut builder = builder();
+        builder.extend_pdus((0..120).map(pdu));
+        builder.extend_edus((0..150).map(|_| edu()));
+
+        let transactions = builder.split(transaction_ids());
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[0].pdus.len(), 50);
+        assert_eq!(transactions[0].edus.len(), 100);
+        assert_eq!(transactions[1].pdus.len(), 50);
+        assert_eq!(transactions[1].edus.len(), 50);
+        assert_eq!(transactions[2].pdus.len(), 20);
+        assert_eq!(transactions[2].edus.len(), 0);
+        assert_eq!(transactions[2].pdus[0].get(), r#"{"depth":100}"#);
+        assert_eq!(transactions[0].transaction_id, "txn1");
+        assert_eq!(transactions[1].transaction_id, "txn2");
+    }
+
+    #[test]
+    fn split_empty() {
+        assert!(builder().split(transaction_ids()).is_empty());
+    }
 }
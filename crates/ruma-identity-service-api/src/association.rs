@@ -1,7 +1,175 @@
 //! Endpoints to create associations with a Matrix ID on the identity server.
 
+#[cfg(feature = "signatures")]
+use ruma_common::ServerName;
+use ruma_common::{thirdparty::Medium, MilliSecondsSinceUnixEpoch, OwnedUserId, ServerSignatures};
+#[cfg(feature = "signatures")]
+use ruma_signatures::{Error, KeyPair, PublicKeyMap};
+use serde::{Deserialize, Serialize};
+
 pub mod bind_3pid;
 pub mod check_3pid_validity;
 pub mod email;
 pub mod msisdn;
 pub mod unbind_3pid;
+
+/// An association between a third-party identifier and a Matrix user ID.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct Association {
+    /// The 3PID address of the user.
+    pub address: String,
+
+    /// The medium type of the 3PID.
+    pub medium: Medium,
+
+    /// The Matrix user ID associated with the 3PID.
+    pub mxid: OwnedUserId,
+
+    /// A UNIX timestamp before which the association is not known to be valid.
+    pub not_before: MilliSecondsSinceUnixEpoch,
+
+    /// A UNIX timestamp after which the association is not known to be valid.
+    pub not_after: MilliSecondsSinceUnixEpoch,
+
+    /// The UNIX timestamp at which the association was verified.
+    pub ts: MilliSecondsSinceUnixEpoch,
+}
+
+impl Association {
+    /// Creates a new `Association` with the given 3PID address, medium, Matrix user ID and
+    /// timestamps.
+    pub fn new(
+        address: String,
+        medium: Medium,
+        mxid: OwnedUserId,
+        not_before: MilliSecondsSinceUnixEpoch,
+        not_after: MilliSecondsSinceUnixEpoch,
+        ts: MilliSecondsSinceUnixEpoch,
+    ) -> Self {
+        Self { address, medium, mxid, not_before, not_after, ts }
+    }
+
+    /// Signs this association with the key pair of the given identity server.
+    #[cfg(feature = "signatures")]
+    pub fn sign<K: KeyPair>(
+        self,
+        identity_server: &ServerName,
+        key_pair: &K,
+    ) -> Result<SignedAssociation, Error> {
+        let signatures = crate::signing::sign_value(identity_server, key_pair, &self)?;
+        Ok(SignedAssociation { association: self, signatures })
+    }
+}
+
+/// An association between a third-party identifier and a Matrix user ID, signed by the
+/// identity servers that verified it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct SignedAssociation {
+    /// The association.
+    #[serde(flatten)]
+    pub association: Association,
+
+    /// The signatures of the verifying identity servers which show that the association should
+    /// be trusted, if you trust the verifying identity services.
+    pub signatures: ServerSignatures,
+}
+
+impl SignedAssociation {
+    /// Creates a new `SignedAssociation` with the given association and signatures.
+    pub fn new(association: Association, signatures: ServerSignatures) -> Self {
+        Self { association, signatures }
+    }
+
+    /// Verifies the signatures of this association with the given public keys.
+    ///
+    /// All the signatures of the entities in `public_key_map` must be valid.
+    #[cfg(feature = "signatures")]
+    pub fn verify(&self, public_key_map: &PublicKeyMap) -> Result<(), Error> {
+        crate::signing::verify_value(public_key_map, self)
+    }
+}
+
+impl From<SignedAssociation> for bind_3pid::v2::Response {
+    fn from(signed: SignedAssociation) -> Self {
+        let SignedAssociation {
+            association: Association { address, medium, mxid, not_before, not_after, ts },
+            signatures,
+        } = signed;
+
+        Self::new(address, medium, mxid, not_before, not_after, ts, signatures)
+    }
+}
+
+impl From<bind_3pid::v2::Response> for SignedAssociation {
+    fn from(response: bind_3pid::v2::Response) -> Self {
+        let bind_3pid::v2::Response {
+            address,
+            medium,
+            mxid,
+            not_before,
+            not_after,
+            ts,
+            signatures,
+            ..
+        } = response;
+
+        Self::new(Association::new(address, medium, mxid, not_before, not_after, ts), signatures)
+    }
+}
+
+#[cfg(all(test, feature = "signatures"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use js_int::uint;
+    use ruma_common::{
+        owned_user_id, serde::Base64, server_name, thirdparty::Medium, MilliSecondsSinceUnixEpoch,
+    };
+    use ruma_signatures::{Ed25519KeyPair, PublicKeyMap};
+
+    use super::{Association, SignedAssociation};
+
+    fn key_pair_and_public_key_map() -> (Ed25519KeyPair, PublicKeyMap) {
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".to_owned()).unwrap();
+        let public_key_map = BTreeMap::from([(
+            "id.example.org".to_owned(),
+            BTreeMap::from([("ed25519:0".to_owned(), Base64::new(key_pair.public_key().to_vec()))]),
+        )]);
+
+        (key_pair, public_key_map)
+    }
+
+    fn signed_association(key_pair: &Ed25519KeyPair) -> SignedAssociation {
+        Association::new(
+            "alice@example.org".to_owned(),
+            Medium::Email,
+            owned_user_id!("@alice:example.org"),
+            MilliSecondsSinceUnixEpoch(uint!(1_000)),
+            MilliSecondsSinceUnixEpoch(uint!(3_000)),
+            MilliSecondsSinceUnixEpoch(uint!(2_000)),
+        )
+        .sign(server_name!("id.example.org"), key_pair)
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_valid_association() {
+        let (key_pair, public_key_map) = key_pair_and_public_key_map();
+        let signed = signed_association(&key_pair);
+
+        assert_eq!(signed.signatures.len(), 1);
+        signed.verify(&public_key_map).unwrap();
+    }
+
+    #[test]
+    fn verify_tampered_association() {
+        let (key_pair, public_key_map) = key_pair_and_public_key_map();
+        let mut signed = signed_association(&key_pair);
+        signed.association.mxid = owned_user_id!("@mallory:example.org");
+
+        signed.verify(&public_key_map).unwrap_err();
+    }
+}
@@ -1,4 +1,123 @@
 //! Endpoints to store and sign invitations.
 
+#[cfg(feature = "signatures")]
+use ruma_common::{
+    canonical_json::{CanonicalJsonObject, CanonicalJsonValue},
+    ServerName, UserId,
+};
+use ruma_common::{OwnedUserId, ServerSignatures};
+#[cfg(feature = "signatures")]
+use ruma_signatures::{Error, KeyPair};
+use serde::{Deserialize, Serialize};
+
 pub mod sign_invitation_ed25519;
 pub mod store_invitation;
+
+/// The details of an invitation to a third-party identifier, signed by an identity server.
+///
+/// This matches the `signed` object of the `third_party_invite` of an `m.room.member` event.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct SignedInvitation {
+    /// The Matrix user ID of the invited user.
+    pub mxid: OwnedUserId,
+
+    /// The signatures of the identity server.
+    pub signatures: ServerSignatures,
+
+    /// The token that was returned when the invitation was stored.
+    pub token: String,
+}
+
+impl SignedInvitation {
+    /// Creates a new `SignedInvitation` with the given Matrix user ID, signatures and token.
+    pub fn new(mxid: OwnedUserId, signatures: ServerSignatures, token: String) -> Self {
+        Self { mxid, signatures, token }
+    }
+
+    /// Signs the given Matrix user ID and token with the key pair of the given identity server.
+    #[cfg(feature = "signatures")]
+    pub fn sign<K: KeyPair>(
+        identity_server: &ServerName,
+        key_pair: &K,
+        mxid: &UserId,
+        token: &str,
+    ) -> Result<Self, Error> {
+        let mut object = CanonicalJsonObject::new();
+        object.insert("mxid".to_owned(), CanonicalJsonValue::String(mxid.into()));
+        object.insert("token".to_owned(), CanonicalJsonValue::String(token.to_owned()));
+
+        let signatures = crate::signing::sign_object(identity_server, key_pair, object)?;
+
+        Ok(Self::new(mxid.to_owned(), signatures, token.to_owned()))
+    }
+}
+
+#[cfg(all(test, feature = "signatures"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ruma_common::{canonical_json::CanonicalJsonValue, serde::Base64, server_name, user_id};
+    use ruma_signatures::Ed25519KeyPair;
+    use serde_json::{json, to_value as to_json_value};
+
+    use super::{sign_invitation_ed25519, SignedInvitation};
+
+    #[test]
+    fn sign_invitation() {
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".to_owned()).unwrap();
+        let identity_server = server_name!("id.example.org");
+        let mxid = user_id!("@alice:example.org");
+
+        let signed = SignedInvitation::sign(identity_server, &key_pair, mxid, "abc123").unwrap();
+
+        let mut json = to_json_value(&signed).unwrap();
+        let signature = json["signatures"]["id.example.org"]["ed25519:0"].take();
+        assert!(signature.is_string());
+        assert_eq!(
+            json,
+            json!({
+                "mxid": "@alice:example.org",
+                "signatures": {
+                    "id.example.org": {
+                        "ed25519:0": null,
+                    },
+                },
+                "token": "abc123",
+            })
+        );
+
+        let public_key_map = BTreeMap::from([(
+            identity_server.to_string(),
+            BTreeMap::from([("ed25519:0".to_owned(), Base64::new(key_pair.public_key().to_vec()))]),
+        )]);
+        let CanonicalJsonValue::Object(object) =
+            to_json_value(&signed).unwrap().try_into().unwrap()
+        else {
+            panic!("signed invitation should be an object");
+        };
+        ruma_signatures::verify_json(&public_key_map, &object).unwrap();
+    }
+
+    #[test]
+    fn sign_invitation_response() {
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".to_owned()).unwrap();
+        let identity_server = server_name!("id.example.org");
+
+        let response = sign_invitation_ed25519::v2::Response::sign(
+            identity_server,
+            &key_pair,
+            user_id!("@alice:example.org"),
+            user_id!("@bob:example.org"),
+            "abc123",
+        )
+        .unwrap();
+
+        assert_eq!(response.mxid, "@alice:example.org");
+        assert_eq!(response.sender, "@bob:example.org");
+        assert_eq!(response.token, "abc123");
+        assert_eq!(response.signatures.len(), 1);
+    }
+}
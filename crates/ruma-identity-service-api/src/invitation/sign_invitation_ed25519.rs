@@ -13,6 +13,13 @@ pub mod v2 {
         serde::Base64,
         OwnedUserId, ServerSignatures,
     };
+    #[cfg(feature = "signatures")]
+    use ruma_common::{
+        canonical_json::{CanonicalJsonObject, CanonicalJsonValue},
+        ServerName, UserId,
+    };
+    #[cfg(feature = "signatures")]
+    use ruma_signatures::{Error, KeyPair};
 
     const METADATA: Metadata = metadata! {
         method: POST,
@@ -70,5 +77,25 @@ pub mod v2 {
         ) -> Self {
             Self { mxid, sender, signatures, token }
         }
+
+        /// Creates a `Response` by signing the given Matrix user ID, sender user ID and token
+        /// with the key pair of the given identity server.
+        #[cfg(feature = "signatures")]
+        pub fn sign<K: KeyPair>(
+            identity_server: &ServerName,
+            key_pair: &K,
+            mxid: &UserId,
+            sender: &UserId,
+            token: &str,
+        ) -> Result<Self, Error> {
+            let mut object = CanonicalJsonObject::new();
+            object.insert("mxid".to_owned(), CanonicalJsonValue::String(mxid.into()));
+            object.insert("sender".to_owned(), CanonicalJsonValue::String(sender.into()));
+            object.insert("token".to_owned(), CanonicalJsonValue::String(token.to_owned()));
+
+            let signatures = crate::signing::sign_object(identity_server, key_pair, object)?;
+
+            Ok(Self::new(mxid.to_owned(), sender.to_owned(), signatures, token.to_owned()))
+        }
     }
 }
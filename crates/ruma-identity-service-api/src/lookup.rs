@@ -1,6 +1,10 @@
 //! Endpoints to look up Matrix IDs bound to 3PIDs.
 
-use ruma_common::serde::StringEnum;
+use ruma_common::{
+    serde::{base64::UrlSafe, Base64, StringEnum},
+    thirdparty::Medium,
+};
+use sha2::{Digest, Sha256};
 
 use crate::PrivOwnedStr;
 
@@ -27,13 +31,53 @@ pub enum IdentifierHashingAlgorithm {
     _Custom(PrivOwnedStr),
 }
 
+/// Hash the given 3PID to look it up with the [`IdentifierHashingAlgorithm::Sha256`] algorithm.
+///
+/// The hash is the SHA-256 of `"{address} {medium} {pepper}"`, encoded as unpadded URL-safe
+/// base64. The pepper is the one returned by [`get_hash_parameters`].
+///
+/// Identity servers might require the address to be normalized before it is hashed, e.g. by
+/// lowercasing email addresses.
+///
+/// # Example
+///
+/// ```
+/// use ruma_common::thirdparty::Medium;
+/// use ruma_identity_service_api::lookup::hash_identifier;
+///
+/// let hash = hash_identifier("alice@example.com", &Medium::Email, "matrixrocks");
+/// assert_eq!(hash, "4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc");
+/// ```
+pub fn hash_identifier(address: &str, medium: &Medium, pepper: &str) -> String {
+    let hash = Sha256::digest(format!("{address} {} {pepper}", medium.as_str()));
+    Base64::<UrlSafe, _>::new(hash).encode()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::IdentifierHashingAlgorithm;
+    use ruma_common::thirdparty::Medium;
+
+    use super::{hash_identifier, IdentifierHashingAlgorithm};
 
     #[test]
     fn parse_identifier_hashing_algorithm() {
         assert_eq!(IdentifierHashingAlgorithm::from("sha256"), IdentifierHashingAlgorithm::Sha256);
         assert_eq!(IdentifierHashingAlgorithm::from("none"), IdentifierHashingAlgorithm::None);
     }
+
+    #[test]
+    fn hash_identifier_spec_vectors() {
+        assert_eq!(
+            hash_identifier("alice@example.com", &Medium::Email, "matrixrocks"),
+            "4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc"
+        );
+        assert_eq!(
+            hash_identifier("bob@example.com", &Medium::Email, "matrixrocks"),
+            "LJwSazmv46n0hlMlsb_iYxI0_HXEqy_yj6Jm636cdT8"
+        );
+        assert_eq!(
+            hash_identifier("18005552067", &Medium::Msisdn, "matrixrocks"),
+            "nlo35_T5fzSGZzJApqu8lgIudJvmOQtDaHtr-I4rU7I"
+        );
+    }
 }
@@ -0,0 +1,40 @@
+//! Helpers to sign and verify JSON objects with `ruma-signatures`.
+
+use ruma_common::{canonical_json::CanonicalJsonObject, ServerName, ServerSignatures};
+use ruma_signatures::{Error, JsonError, KeyPair, PublicKeyMap};
+use serde::Serialize;
+
+/// Signs the given object with the key pair of the given identity server and returns the
+/// resulting signatures.
+pub(crate) fn sign_object<K: KeyPair>(
+    identity_server: &ServerName,
+    key_pair: &K,
+    mut object: CanonicalJsonObject,
+) -> Result<ServerSignatures, Error> {
+    ruma_signatures::sign_json(identity_server.as_str(), key_pair, &mut object)?;
+
+    let signatures = object.remove("signatures").expect("signatures were just added");
+    Ok(serde_json::from_value(signatures.into()).map_err(JsonError::from)?)
+}
+
+/// Serializes the given value and signs it with the key pair of the given identity server.
+pub(crate) fn sign_value<T: Serialize, K: KeyPair>(
+    identity_server: &ServerName,
+    key_pair: &K,
+    value: &T,
+) -> Result<ServerSignatures, Error> {
+    sign_object(identity_server, key_pair, to_canonical_object(value)?)
+}
+
+/// Serializes the given signed value and verifies its signatures.
+pub(crate) fn verify_value<T: Serialize>(
+    public_key_map: &PublicKeyMap,
+    value: &T,
+) -> Result<(), Error> {
+    ruma_signatures::verify_json(public_key_map, &to_canonical_object(value)?)
+}
+
+fn to_canonical_object<T: Serialize>(value: &T) -> Result<CanonicalJsonObject, Error> {
+    let json = serde_json::to_value(value).map_err(JsonError::from)?;
+    Ok(serde_json::from_value(json).map_err(JsonError::from)?)
+}
@@ -202,6 +202,7 @@ impl Request {
             }
         });
 
+        let path_spec_impl = self.expand_path_spec(ruma_common);
         let outgoing_request_impl = self.expand_outgoing(ruma_common);
         let incoming_request_impl = self.expand_incoming(ruma_common);
 
@@ -212,12 +213,36 @@ impl Request {
             #[allow(deprecated)]
             mod __request_impls {
                 use super::*;
+                #path_spec_impl
                 #outgoing_request_impl
                 #incoming_request_impl
             }
         }
     }
 
+    fn expand_path_spec(&self, ruma_common: &TokenStream) -> TokenStream {
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics Request #ty_generics #where_clause {
+                /// The segments of the canonical path of this endpoint.
+                ///
+                /// Literal segments and path parameters are distinguished, so server routers
+                /// can build matching routes without parsing the path themselves.
+                pub const fn path_spec() -> &'static [#ruma_common::api::PathSegment] {
+                    const PATH: &::std::primitive::str = METADATA.history.canonical_path();
+                    const SPEC: [
+                        #ruma_common::api::PathSegment;
+                        #ruma_common::api::_path_segment_count(PATH)
+                    ] = #ruma_common::api::_path_segments(PATH);
+
+                    &SPEC
+                }
+            }
+        }
+    }
+
     pub(super) fn check(&self, ruma_common: &TokenStream) -> syn::Result<TokenStream> {
         let http = quote! { #ruma_common::exports::http };
 
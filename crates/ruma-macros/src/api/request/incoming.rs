@@ -38,7 +38,7 @@ impl Request {
             let parse = quote! {
                 #( #cfg_attrs )*
                 let #field_name =
-                    #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
+                    #serde_html_form::from_str(query)?;
             };
 
             (
@@ -55,8 +55,7 @@ impl Request {
             );
 
             let parse = quote! {
-                let request_query: RequestQuery =
-                    #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
+                let request_query: RequestQuery = #serde_html_form::from_str(query)?;
 
                 #decls
             };
@@ -135,31 +134,32 @@ impl Request {
                 })
                 .unzip();
 
-            let parse = quote! {
-                let headers = request.headers();
-
-                #decls
-            };
-
-            (parse, quote! { #(#names,)* })
+            (decls, quote! { #(#names,)* })
         } else {
             (TokenStream::new(), TokenStream::new())
         };
 
         let extract_body = self.has_body_fields().then(|| {
+            // A newtype body can also be a JSON array, so an empty request body might need to be
+            // treated as an empty array instead.
+            let parse_empty_body = if self.has_newtype_body() {
+                quote! {
+                    #serde_json::from_slice(b"{}")
+                        .or_else(|_| #serde_json::from_slice(b"[]"))?
+                }
+            } else {
+                quote! { #serde_json::from_slice(b"{}")? }
+            };
+
             quote! {
                 let request_body: RequestBody = {
-                    let body = ::std::convert::AsRef::<[::std::primitive::u8]>::as_ref(
-                        request.body(),
-                    );
-
-                    #serde_json::from_slice(match body {
+                    match body {
                         // If the request body is completely empty, pretend it is an empty JSON
                         // object instead. This allows requests with only optional body parameters
                         // to be deserialized in that case.
-                        [] => b"{}",
-                        b => b,
-                    })?
+                        [] => #parse_empty_body,
+                        b => #serde_json::from_slice(b)?,
+                    }
                 };
             }
         });
@@ -167,8 +167,7 @@ impl Request {
         let (parse_body, body_vars) = if let Some(field) = self.raw_body_field() {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
             let parse = quote! {
-                let #field_name =
-                    ::std::convert::AsRef::<[u8]>::as_ref(request.body()).to_vec();
+                let #field_name = body.to_vec();
             };
 
             (parse, quote! { #field_name, })
@@ -177,6 +176,42 @@ impl Request {
         };
 
         quote! {
+            #[automatically_derived]
+            #[cfg(feature = "server")]
+            impl Request {
+                /// Tries to construct this request from the parts of an HTTP request.
+                ///
+                /// This is useful for server frameworks that have already extracted the parts of
+                /// the request, to avoid constructing an `http::Request`. The HTTP method is not
+                /// checked.
+                ///
+                /// Note: The strings in `path_args` need to be percent-decoded.
+                #[allow(unused_variables)]
+                pub fn try_from_parts<S>(
+                    path_args: &[S],
+                    query: &::std::primitive::str,
+                    headers: &#http::HeaderMap,
+                    body: &[::std::primitive::u8],
+                ) -> ::std::result::Result<Self, #ruma_common::api::error::FromHttpRequestError>
+                where
+                    S: ::std::convert::AsRef<::std::primitive::str>,
+                {
+                    #parse_request_path
+                    #parse_query
+                    #parse_headers
+
+                    #extract_body
+                    #parse_body
+
+                    ::std::result::Result::Ok(Self {
+                        #path_vars
+                        #query_vars
+                        #header_vars
+                        #body_vars
+                    })
+                }
+            }
+
             #[automatically_derived]
             #[cfg(feature = "server")]
             impl #ruma_common::api::IncomingRequest for Request {
@@ -203,19 +238,12 @@ impl Request {
                         });
                     }
 
-                    #parse_request_path
-                    #parse_query
-                    #parse_headers
-
-                    #extract_body
-                    #parse_body
-
-                    ::std::result::Result::Ok(Self {
-                        #path_vars
-                        #query_vars
-                        #header_vars
-                        #body_vars
-                    })
+                    Self::try_from_parts(
+                        path_args,
+                        request.uri().query().unwrap_or(""),
+                        request.headers(),
+                        ::std::convert::AsRef::<[::std::primitive::u8]>::as_ref(request.body()),
+                    )
                 }
             }
         }
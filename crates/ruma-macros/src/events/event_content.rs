@@ -237,10 +237,12 @@ impl TryFrom<ContentMeta> for ContentAttrs {
             ));
         }
 
-        if prefix.is_some() && !event_kind.is_some_and(|k| k.is_account_data()) {
+        if prefix.is_some()
+            && !event_kind.is_some_and(|k| k.is_account_data() || k == EventKind::MessageLike)
+        {
             return Err(syn::Error::new_spanned(
                 event_type,
-                "only account data events may contain a `.*` suffix",
+                "only account data and message-like events may contain a `.*` suffix",
             ));
         }
 
@@ -409,17 +411,15 @@ fn generate_redacted_event_content<'a>(
     aliases: &[LitStr],
     ruma_events: &TokenStream,
 ) -> syn::Result<TokenStream> {
-    assert!(
-        !event_type.value().contains('*'),
-        "Event type shouldn't contain a `*`, this should have been checked previously"
-    );
-
     let ruma_common = quote! { #ruma_events::exports::ruma_common };
     let serde = quote! { #ruma_events::exports::serde };
 
     let doc = format!("Redacted form of [`{ident}`]");
     let redacted_ident = format_ident!("Redacted{ident}");
 
+    // The type fragment field is kept, so the redacted content has the same event type. Its
+    // `ruma_event` attribute is only needed to generate the `EventContent` implementation.
+    let mut type_fragment_field = None;
     let kept_redacted_fields: Vec<_> = fields
         .map(|f| {
             let mut keep_field = false;
@@ -428,8 +428,12 @@ fn generate_redacted_event_content<'a>(
                 .iter()
                 .map(|a| -> syn::Result<_> {
                     if a.path().is_ident("ruma_event") {
-                        if let EventFieldMeta::SkipRedaction = a.parse_args()? {
-                            keep_field = true;
+                        match a.parse_args()? {
+                            EventFieldMeta::SkipRedaction => keep_field = true,
+                            EventFieldMeta::TypeFragment => {
+                                keep_field = true;
+                                type_fragment_field = Some(f.ident.clone());
+                            }
                         }
 
                         // don't re-emit our `ruma_event` attributes
@@ -450,6 +454,21 @@ fn generate_redacted_event_content<'a>(
         .filter_map(Result::transpose)
         .collect::<syn::Result<_>>()?;
 
+    let kept_redacted_fields_with_type_fragment: Vec<_> = kept_redacted_fields
+        .iter()
+        .map(|f| {
+            let mut f = f.clone();
+            if type_fragment_field.as_ref().is_some_and(|ident| f.ident == *ident) {
+                f.attrs.push(parse_quote! { #[ruma_event(type_fragment)] });
+            }
+            f
+        })
+        .collect();
+
+    // With a type fragment, `EventContentFromType` is implemented by `generate_event_content_impl`
+    // so it must not be implemented through `Deserialize`.
+    let deserialize_derive = type_fragment_field.is_none().then(|| quote! { #serde::Deserialize, });
+
     let redaction_struct_fields = kept_redacted_fields.iter().flat_map(|f| &f.ident);
 
     let constructor = kept_redacted_fields.is_empty().then(|| {
@@ -467,7 +486,7 @@ fn generate_redacted_event_content<'a>(
     let redacted_event_content = generate_event_content_impl(
         &redacted_ident,
         vis,
-        Some(kept_redacted_fields.iter()),
+        Some(kept_redacted_fields_with_type_fragment.iter()),
         event_type,
         Some(event_kind),
         EventKindContentVariation::Redacted,
@@ -495,7 +514,7 @@ fn generate_redacted_event_content<'a>(
         }
 
         #[doc = #doc]
-        #[derive(Clone, Debug, #serde::Deserialize, #serde::Serialize)]
+        #[derive(Clone, Debug, #deserialize_derive #serde::Serialize)]
         #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
         #vis struct #redacted_ident {
             #( #kept_redacted_fields, )*
@@ -960,10 +979,16 @@ fn generate_event_content_impl<'a>(
                         && matches!(a.parse_args(), Ok(EventFieldMeta::TypeFragment))
                 })
             })
-            .map(PrivateField)
+            .map(|f| {
+                // Don't re-emit our `ruma_event` attributes, like `skip_redaction`.
+                let attrs =
+                    f.attrs.iter().filter(|a| !a.path().is_ident("ruma_event")).cloned().collect();
+                Field { attrs, ..f.clone() }
+            })
             .collect::<Vec<_>>();
         let fields_ident_without_type_fragment =
-            fields_without_type_fragment.iter().filter_map(|f| f.0.ident.as_ref());
+            fields_without_type_fragment.iter().filter_map(|f| f.ident.as_ref());
+        let fields_without_type_fragment = fields_without_type_fragment.iter().map(PrivateField);
 
         quote! {
             impl #ruma_events::EventContentFromType for #ident {
@@ -51,6 +51,9 @@ pub fn expand_event_type_enum(
         generate_enum("TimelineEventType", &timeline, &ruma_events)
             .unwrap_or_else(syn::Error::into_compile_error),
     );
+    res.extend(
+        generate_timeline_kind_fns(&state, &message).unwrap_or_else(syn::Error::into_compile_error),
+    );
     res.extend(
         generate_enum("StateEventType", &state, &ruma_events)
             .unwrap_or_else(syn::Error::into_compile_error),
@@ -79,16 +82,44 @@ pub fn expand_event_type_enum(
     Ok(res)
 }
 
-fn generate_enum(
-    ident: &str,
-    input: &[&Vec<EventEnumEntry>],
-    ruma_common: &TokenStream,
+/// Generate the methods of `TimelineEventType` to check the kind of the event type.
+fn generate_timeline_kind_fns(
+    state: &[&Vec<EventEnumEntry>],
+    message: &[&Vec<EventEnumEntry>],
 ) -> syn::Result<TokenStream> {
-    let serde = quote! { #ruma_common::exports::serde };
-    let enum_doc = format!("The type of `{}` this is.", ident.strip_suffix("Type").unwrap());
+    let state_match_arms = timeline_match_arms(state)?;
+    let message_match_arms = timeline_match_arms(message)?;
 
-    let ident = Ident::new(ident, Span::call_site());
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl TimelineEventType {
+            /// Whether this is the type of a state event known by Ruma.
+            ///
+            /// Returns `false` for custom event types.
+            pub fn is_state(&self) -> bool {
+                match self {
+                    #(#state_match_arms => true,)*
+                    _ => false,
+                }
+            }
 
+            /// Whether this is the type of a message-like event known by Ruma.
+            ///
+            /// Returns `false` for custom event types.
+            pub fn is_message_like(&self) -> bool {
+                match self {
+                    #(#message_match_arms => true,)*
+                    _ => false,
+                }
+            }
+        }
+    })
+}
+
+/// Deduplicate the given entries by event type.
+///
+/// If an event type is present several times, the entry without config attributes is kept.
+fn dedup_entries<'a>(input: &[&'a Vec<EventEnumEntry>]) -> Vec<&'a EventEnumEntry> {
     let mut deduped: Vec<&EventEnumEntry> = vec![];
     for item in input.iter().copied().flatten() {
         if let Some(idx) = deduped.iter().position(|e| e.ev_type == item.ev_type) {
@@ -101,6 +132,36 @@ fn generate_enum(
         }
     }
 
+    deduped
+}
+
+/// Generate the patterns matching the `TimelineEventType` variants of the given events.
+fn timeline_match_arms(input: &[&Vec<EventEnumEntry>]) -> syn::Result<Vec<TokenStream>> {
+    dedup_entries(input)
+        .iter()
+        .map(|e| {
+            let v = e.to_variant()?.match_arm(quote! { Self });
+            Ok(if e.has_type_fragment() {
+                quote! { #v(_) }
+            } else {
+                v
+            })
+        })
+        .collect()
+}
+
+fn generate_enum(
+    ident: &str,
+    input: &[&Vec<EventEnumEntry>],
+    ruma_common: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let serde = quote! { #ruma_common::exports::serde };
+    let enum_doc = format!("The type of `{}` this is.", ident.strip_suffix("Type").unwrap());
+
+    let ident = Ident::new(ident, Span::call_site());
+
+    let deduped = dedup_entries(input);
+
     let event_types = deduped.iter().map(|e| &e.ev_type);
 
     let variants: Vec<_> = deduped
@@ -208,6 +269,11 @@ fn generate_enum(
 
         #[allow(deprecated)]
         impl #ident {
+            /// Whether this is a custom event type, that isn't available as a documented variant.
+            pub fn is_custom(&self) -> bool {
+                ::std::matches!(self, Self::_Custom(_))
+            }
+
             fn to_cow_str(&self) -> ::std::borrow::Cow<'_, ::std::primitive::str> {
                 match self {
                     #(#to_cow_str_match_arms,)*
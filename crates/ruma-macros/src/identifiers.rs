@@ -478,6 +478,7 @@ fn expand_checked_impls(input: &ItemStruct, validate: Path) -> TokenStream {
     let parse_box_doc_header = format!("Try parsing a `&str` into a `Box<{id}>`.");
     let parse_rc_docs = format!("Try parsing a `&str` into an `Rc<{id}>`.");
     let parse_arc_docs = format!("Try parsing a `&str` into an `Arc<{id}>`.");
+    let parse_bytes_docs = format!("Try parsing a byte slice into an `Owned{id}`.");
 
     let id_ty = quote! { #id #ty_generics };
     let owned_ty = quote! { #owned #ty_generics };
@@ -523,6 +524,15 @@ fn expand_checked_impls(input: &ItemStruct, validate: Path) -> TokenStream {
                 #validate(s.as_ref())?;
                 Ok(#id::from_arc(s.into()))
             }
+
+            #[doc = #parse_bytes_docs]
+            ///
+            /// Fails with `IdParseError::InvalidUtf8` if the bytes are not valid UTF-8, otherwise
+            /// this is the same as [`parse`](Self::parse). This never panics, regardless of the
+            /// input.
+            pub fn parse_bytes(bytes: &[u8]) -> Result<#owned_ty, crate::IdParseError> {
+                Self::parse(std::str::from_utf8(bytes)?)
+            }
         }
 
         #[automatically_derived]
@@ -124,6 +124,14 @@ pub fn event_enum(input: TokenStream) -> TokenStream {
 /// You can use `cargo doc` to find out more details, its `--document-private-items` flag also lets
 /// you generate documentation for binaries or private parts of a library.
 ///
+/// An event type that was renamed, for example when an unstable `org.matrix.mscXXXX.foo` type is
+/// stabilized as `m.foo`, can be declared with one or more `alias` attributes, like
+/// `#[ruma_event(type = "m.foo", alias = "org.matrix.mscXXXX.foo", kind = MessageLike)]`. The
+/// aliases are accepted during deserialization, but the main type is always returned by
+/// `EventContent::event_type()`, and used for serialization. To accept the aliases in the event
+/// enums too, the corresponding entries in the `event_enum!` macro need a
+/// `#[ruma_enum(alias = "...")]` attribute.
+///
 /// By default, the type this macro is used on and the generated types get a `#[non_exhaustive]`
 /// attribute. This behavior can be controlled by setting the `ruma_unstable_exhaustive_types`
 /// compile-time `cfg` setting as `--cfg=ruma_unstable_exhaustive_types` using `RUSTFLAGS` or
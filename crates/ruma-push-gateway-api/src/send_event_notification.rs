@@ -7,15 +7,17 @@ pub mod v1 {
     //!
     //! [spec]: https://spec.matrix.org/latest/push-gateway-api/#post_matrixpushv1notify
 
+    use std::collections::BTreeMap;
+
     use js_int::{uint, UInt};
     use ruma_common::{
         api::{request, response, Metadata},
         metadata,
         push::{PushFormat, Tweak},
-        serde::{JsonObject, StringEnum},
-        OwnedEventId, OwnedRoomAliasId, OwnedRoomId, OwnedUserId, SecondsSinceUnixEpoch,
+        serde::{JsonObject, Raw, StringEnum},
+        OwnedEventId, OwnedRoomAliasId, OwnedRoomId, OwnedUserId, RoomId, SecondsSinceUnixEpoch,
     };
-    use ruma_events::TimelineEventType;
+    use ruma_events::{AnySyncTimelineEvent, TimelineEventType};
     use serde::{Deserialize, Serialize};
     use serde_json::value::RawValue as RawJsonValue;
 
@@ -137,6 +139,69 @@ pub mod v1 {
         pub fn new(devices: Vec<Device>) -> Self {
             Notification { devices, ..Default::default() }
         }
+
+        /// Create a new notification about the given event.
+        ///
+        /// The `event_id`, `type`, `sender` and `content` fields are extracted from the event,
+        /// without deserializing it completely. Fields that are missing or invalid are left empty.
+        ///
+        /// The other fields, like `devices`, must be set by the caller.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use js_int::uint;
+        /// use ruma_common::{room_id, serde::Raw};
+        /// use ruma_push_gateway_api::send_event_notification::v1::{
+        ///     Notification, NotificationCounts,
+        /// };
+        /// use serde_json::json;
+        ///
+        /// let event = Raw::new(&json!({
+        ///     "content": { "body": "Hello", "msgtype": "m.text" },
+        ///     "event_id": "$143273582443PhrSn:example.org",
+        ///     "origin_server_ts": 1_432_735_824_653_u64,
+        ///     "sender": "@alice:example.org",
+        ///     "type": "m.room.message",
+        /// }))
+        /// .unwrap()
+        /// .cast();
+        ///
+        /// let notification = Notification::from_event(
+        ///     &event,
+        ///     room_id!("!jEsUZKDJdhlrceRyVU:example.org"),
+        ///     NotificationCounts::new(uint!(2), uint!(0)),
+        /// );
+        /// assert_eq!(notification.sender.unwrap(), "@alice:example.org");
+        /// ```
+        pub fn from_event(
+            event: &Raw<AnySyncTimelineEvent>,
+            room_id: &RoomId,
+            counts: NotificationCounts,
+        ) -> Self {
+            Notification {
+                event_id: event.get_field("event_id").ok().flatten(),
+                room_id: Some(room_id.to_owned()),
+                event_type: event.get_field("type").ok().flatten(),
+                sender: event.get_field("sender").ok().flatten(),
+                content: event.get_field("content").ok().flatten(),
+                counts,
+                ..Default::default()
+            }
+        }
+
+        /// Group the devices of this notification by their `app_id`.
+        ///
+        /// Within each group, the devices keep the order in which they appear in `devices`.
+        pub fn devices_by_app_id(&self) -> BTreeMap<&str, Vec<&Device>> {
+            let mut groups = BTreeMap::<_, Vec<_>>::new();
+
+            for device in &self.devices {
+                groups.entry(device.app_id.as_str()).or_default().push(device);
+            }
+
+            groups
+        }
     }
 
     /// Type for passing information about notification priority.
@@ -229,6 +294,34 @@ pub mod v1 {
                 tweaks: Vec::new(),
             }
         }
+
+        /// The kind of the pusher that this device was registered with.
+        ///
+        /// Email pushers use the `m.email` app ID, all other pushers are considered to be HTTP
+        /// pushers.
+        pub fn pusher_kind(&self) -> PusherKind {
+            if self.app_id == PusherKind::EMAIL_APP_ID {
+                PusherKind::Email
+            } else {
+                PusherKind::Http
+            }
+        }
+    }
+
+    /// The kind of a pusher.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+    pub enum PusherKind {
+        /// A pusher that sends HTTP pokes.
+        Http,
+
+        /// A pusher that emails the user with unread notifications.
+        Email,
+    }
+
+    impl PusherKind {
+        /// The app ID of email pushers.
+        pub const EMAIL_APP_ID: &'static str = "m.email";
     }
 
     /// Information for the pusher implementation itself.
@@ -346,15 +439,71 @@ pub mod v1 {
     mod tests {
         use js_int::uint;
         use ruma_common::{
-            owned_event_id, owned_room_alias_id, owned_room_id, owned_user_id,
+            owned_event_id, owned_room_alias_id, owned_room_id, owned_user_id, room_id, serde::Raw,
             SecondsSinceUnixEpoch,
         };
-        use ruma_events::TimelineEventType;
+        use ruma_events::{AnySyncTimelineEvent, TimelineEventType};
         use serde_json::{
-            from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+            from_str as from_json_str, from_value as from_json_value, json,
+            to_value as to_json_value, Value as JsonValue,
+        };
+
+        use super::{
+            Device, Notification, NotificationCounts, NotificationPriority, PusherKind, Tweak,
         };
 
-        use super::{Device, Notification, NotificationCounts, NotificationPriority, Tweak};
+        #[test]
+        fn notification_from_event() {
+            let event = Raw::new(&json!({
+                "content": {
+                    "body": "I'm floating in a most peculiar way.",
+                    "msgtype": "m.text",
+                },
+                "event_id": "$3957tyerfgewrf384",
+                "origin_server_ts": 1_432_735_824_653_u64,
+                "sender": "@exampleuser:matrix.org",
+                "type": "m.room.message",
+            }))
+            .unwrap()
+            .cast::<AnySyncTimelineEvent>();
+
+            let notification = Notification::from_event(
+                &event,
+                room_id!("!slw48wfj34rtnrf:example.com"),
+                NotificationCounts::new(uint!(2), uint!(0)),
+            );
+
+            assert_eq!(notification.event_id.unwrap(), "$3957tyerfgewrf384");
+            assert_eq!(notification.room_id.unwrap(), "!slw48wfj34rtnrf:example.com");
+            assert_eq!(notification.event_type.unwrap(), TimelineEventType::RoomMessage);
+            assert_eq!(notification.sender.unwrap(), "@exampleuser:matrix.org");
+            assert_eq!(
+                from_json_str::<JsonValue>(notification.content.unwrap().get()).unwrap(),
+                json!({
+                    "body": "I'm floating in a most peculiar way.",
+                    "msgtype": "m.text",
+                })
+            );
+            assert_eq!(notification.counts.unread, uint!(2));
+            assert!(notification.devices.is_empty());
+        }
+
+        #[test]
+        fn notification_from_invalid_event() {
+            let event =
+                Raw::new(&json!({ "event_id": 42, "type": "m.room.message" })).unwrap().cast();
+
+            let notification = Notification::from_event(
+                &event,
+                room_id!("!slw48wfj34rtnrf:example.com"),
+                NotificationCounts::default(),
+            );
+
+            assert_eq!(notification.event_id, None);
+            assert_eq!(notification.event_type.unwrap(), TimelineEventType::RoomMessage);
+            assert_eq!(notification.sender, None);
+            assert!(notification.content.is_none());
+        }
 
         #[test]
         fn serialize_request() {
@@ -424,5 +573,34 @@ pub mod v1 {
 
             assert_eq!(expected, to_json_value(notice).unwrap());
         }
+
+        #[test]
+        fn group_devices_by_app_id() {
+            let notification = Notification::new(vec![
+                Device::new("org.matrix.ios".into(), "ios_key_1".into()),
+                Device::new("m.email".into(), "alice@example.com".into()),
+                Device::new("org.matrix.android".into(), "android_key".into()),
+                Device::new("org.matrix.ios".into(), "ios_key_2".into()),
+            ]);
+
+            let groups = notification.devices_by_app_id();
+            assert_eq!(groups.len(), 3);
+
+            let ios = &groups["org.matrix.ios"];
+            assert_eq!(ios.len(), 2);
+            assert_eq!(ios[0].pushkey, "ios_key_1");
+            assert_eq!(ios[1].pushkey, "ios_key_2");
+            assert!(ios.iter().all(|device| device.pusher_kind() == PusherKind::Http));
+
+            let android = &groups["org.matrix.android"];
+            assert_eq!(android.len(), 1);
+            assert_eq!(android[0].pushkey, "android_key");
+            assert_eq!(android[0].pusher_kind(), PusherKind::Http);
+
+            let email = &groups["m.email"];
+            assert_eq!(email.len(), 1);
+            assert_eq!(email[0].pushkey, "alice@example.com");
+            assert_eq!(email[0].pusher_kind(), PusherKind::Email);
+        }
     }
 }
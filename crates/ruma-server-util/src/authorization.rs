@@ -4,12 +4,21 @@ use std::{fmt, str::FromStr};
 
 use headers::authorization::Credentials;
 use http::HeaderValue;
+#[cfg(feature = "signatures")]
+use http::{Method, Uri};
 use http_auth::ChallengeParser;
+#[cfg(feature = "signatures")]
+use ruma_common::{
+    canonical_json::{CanonicalJsonObject, CanonicalJsonValue},
+    ServerName,
+};
 use ruma_common::{
     http_headers::quote_ascii_string_if_required,
     serde::{Base64, Base64DecodeError},
     IdParseError, OwnedServerName, OwnedServerSigningKeyId,
 };
+#[cfg(feature = "signatures")]
+use ruma_signatures::{KeyPair, PublicKeyMap, VerificationError};
 use thiserror::Error;
 use tracing::debug;
 
@@ -47,6 +56,82 @@ impl XMatrix {
         Self { origin, destination: Some(destination), key, sig }
     }
 
+    /// Sign a request with the given key pair and construct the X-Matrix Authorization header
+    /// for it.
+    ///
+    /// The signed JSON object is built from the request's `method`, the path and query of its
+    /// `uri`, the `origin` and `destination` server names and its `content`, if any, as defined
+    /// in the [Matrix Server-Server API][spec].
+    ///
+    /// The header value can be obtained with the `Display` implementation or by converting the
+    /// returned `XMatrix` into a [`HeaderValue`].
+    ///
+    /// [spec]: https://spec.matrix.org/latest/server-server-api/#request-authentication
+    #[cfg(feature = "signatures")]
+    pub fn sign_request<K: KeyPair>(
+        method: &Method,
+        uri: &Uri,
+        origin: OwnedServerName,
+        destination: OwnedServerName,
+        content: Option<&CanonicalJsonValue>,
+        key_pair: &K,
+    ) -> Result<Self, XMatrixSignatureError> {
+        let object = request_object(method, uri, &origin, &destination, content);
+        let signature = key_pair.sign(ruma_signatures::canonical_json(&object)?.as_bytes());
+
+        let key = signature.id().try_into().map_err(XMatrixSignatureError::KeyId)?;
+        let sig = Base64::new(signature.as_bytes().to_vec());
+
+        Ok(Self::new(origin, destination, key, sig))
+    }
+
+    /// Verify the signature of this X-Matrix Authorization header for the given request.
+    ///
+    /// `destination` must be the name of the receiving server. If this header contains a
+    /// different destination, [`XMatrixSignatureError::DestinationMismatch`] is returned.
+    ///
+    /// Only the keys of the origin server are used from `public_key_map`, it is an error if
+    /// there are none.
+    #[cfg(feature = "signatures")]
+    pub fn verify_request(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        destination: &ServerName,
+        content: Option<&CanonicalJsonValue>,
+        public_key_map: &PublicKeyMap,
+    ) -> Result<(), XMatrixSignatureError> {
+        if let Some(header_destination) = &self.destination {
+            if header_destination != destination {
+                return Err(XMatrixSignatureError::DestinationMismatch {
+                    expected: destination.to_owned(),
+                    found: header_destination.clone(),
+                });
+            }
+        }
+
+        let origin_public_keys = public_key_map.get(self.origin.as_str()).ok_or_else(|| {
+            ruma_signatures::Error::from(VerificationError::PublicKeyNotFound(self.origin.clone()))
+        })?;
+        let public_key_map =
+            PublicKeyMap::from([(self.origin.as_str().to_owned(), origin_public_keys.clone())]);
+
+        let mut object = request_object(method, uri, &self.origin, destination, content);
+        let signatures = CanonicalJsonObject::from([(
+            self.key.to_string(),
+            CanonicalJsonValue::String(self.sig.encode()),
+        )]);
+        object.insert(
+            "signatures".to_owned(),
+            CanonicalJsonValue::Object(CanonicalJsonObject::from([(
+                self.origin.as_str().to_owned(),
+                CanonicalJsonValue::Object(signatures),
+            )])),
+        );
+
+        Ok(ruma_signatures::verify_json(&public_key_map, &object)?)
+    }
+
     /// Parse an X-Matrix Authorization header from the given string.
     pub fn parse(s: impl AsRef<str>) -> Result<Self, XMatrixParseError> {
         let parser = ChallengeParser::new(s.as_ref());
@@ -110,6 +195,32 @@ impl XMatrix {
     }
 }
 
+/// Build the JSON object that is signed for the X-Matrix Authorization header of a request.
+#[cfg(feature = "signatures")]
+fn request_object(
+    method: &Method,
+    uri: &Uri,
+    origin: &ServerName,
+    destination: &ServerName,
+    content: Option<&CanonicalJsonValue>,
+) -> CanonicalJsonObject {
+    let uri =
+        uri.path_and_query().map_or_else(|| uri.path(), |path_and_query| path_and_query.as_str());
+
+    let mut object = CanonicalJsonObject::from([
+        ("method".to_owned(), CanonicalJsonValue::String(method.as_str().to_owned())),
+        ("uri".to_owned(), CanonicalJsonValue::String(uri.to_owned())),
+        ("origin".to_owned(), CanonicalJsonValue::String(origin.as_str().to_owned())),
+        ("destination".to_owned(), CanonicalJsonValue::String(destination.as_str().to_owned())),
+    ]);
+
+    if let Some(content) = content {
+        object.insert("content".to_owned(), content.clone());
+    }
+
+    object
+}
+
 impl fmt::Debug for XMatrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("XMatrix")
@@ -207,6 +318,30 @@ pub enum XMatrixParseError {
     DuplicateParameter(String),
 }
 
+/// An error when trying to sign or verify the X-Matrix Authorization header of a request.
+#[cfg(feature = "signatures")]
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum XMatrixSignatureError {
+    /// The destination in the header does not match the name of the receiving server.
+    #[error("destination `{found}` does not match the receiving server `{expected}`")]
+    DestinationMismatch {
+        /// The name of the receiving server.
+        expected: OwnedServerName,
+
+        /// The destination in the header.
+        found: OwnedServerName,
+    },
+
+    /// The ID of the signing key could not be parsed as a server signing key ID.
+    #[error("invalid signing key ID: {0}")]
+    KeyId(#[source] IdParseError),
+
+    /// Signing or verifying the request failed.
+    #[error(transparent)]
+    Signatures(#[from] ruma_signatures::Error),
+}
+
 impl<'a> From<http_auth::parser::Error<'a>> for XMatrixParseError {
     fn from(value: http_auth::parser::Error<'a>) -> Self {
         Self::ParseStr(value.to_string())
@@ -294,4 +429,116 @@ mod tests {
         assert_eq!(credentials.key, "ed25519:key1");
         assert_eq!(credentials.sig, sig);
     }
+
+    #[cfg(feature = "signatures")]
+    #[test]
+    fn xmatrix_sign_and_verify_request() {
+        use std::collections::BTreeMap;
+
+        use assert_matches2::assert_matches;
+        use http::{Method, Uri};
+        use ruma_common::{canonical_json::CanonicalJsonValue, server_name};
+        use ruma_signatures::{Ed25519KeyPair, PublicKeyMap};
+
+        use super::XMatrixSignatureError;
+
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "key1".to_owned())
+                .unwrap();
+        let mut public_key_map = PublicKeyMap::new();
+        public_key_map.insert(
+            "origin.hs.example.com".to_owned(),
+            BTreeMap::from([(
+                "ed25519:key1".to_owned(),
+                Base64::new(key_pair.public_key().to_vec()),
+            )]),
+        );
+
+        let method = Method::PUT;
+        let uri = Uri::from_static(
+            "https://destination.hs.example.com/_matrix/federation/v1/send/1234?foo=bar",
+        );
+        let content = CanonicalJsonValue::Object(
+            [("pdus".to_owned(), CanonicalJsonValue::Array(vec![]))].into(),
+        );
+        let destination = server_name!("destination.hs.example.com");
+
+        let xmatrix = XMatrix::sign_request(
+            &method,
+            &uri,
+            "origin.hs.example.com".try_into().unwrap(),
+            destination.to_owned(),
+            Some(&content),
+            &key_pair,
+        )
+        .unwrap();
+        assert_eq!(xmatrix.origin, "origin.hs.example.com");
+        assert_eq!(xmatrix.destination.as_deref(), Some(destination));
+        assert_eq!(xmatrix.key, "ed25519:key1");
+
+        // Roundtrip through the header value.
+        let xmatrix = XMatrix::try_from(&xmatrix.encode()).unwrap();
+        xmatrix
+            .verify_request(&method, &uri, destination, Some(&content), &public_key_map)
+            .unwrap();
+
+        // Only the path and query of the URI are signed.
+        let relative_uri = Uri::from_static("/_matrix/federation/v1/send/1234?foo=bar");
+        xmatrix
+            .verify_request(&method, &relative_uri, destination, Some(&content), &public_key_map)
+            .unwrap();
+
+        // Any change to the request invalidates the signature.
+        xmatrix
+            .verify_request(&Method::POST, &uri, destination, Some(&content), &public_key_map)
+            .unwrap_err();
+        xmatrix
+            .verify_request(
+                &method,
+                &Uri::from_static("/_matrix/federation/v1/send/1234"),
+                destination,
+                Some(&content),
+                &public_key_map,
+            )
+            .unwrap_err();
+        xmatrix
+            .verify_request(
+                &method,
+                &uri,
+                server_name!("other.hs.example.com"),
+                Some(&content),
+                &public_key_map,
+            )
+            .unwrap_err();
+        xmatrix.verify_request(&method, &uri, destination, None, &public_key_map).unwrap_err();
+
+        // A different destination in the header is rejected before checking the signature.
+        let other_destination = server_name!("other.hs.example.com");
+        let mut wrong_destination = xmatrix.clone();
+        wrong_destination.destination = Some(other_destination.to_owned());
+        assert_matches!(
+            wrong_destination.verify_request(
+                &method,
+                &uri,
+                destination,
+                Some(&content),
+                &public_key_map,
+            ),
+            Err(XMatrixSignatureError::DestinationMismatch { expected, found })
+        );
+        assert_eq!(expected, destination);
+        assert_eq!(found, other_destination);
+
+        // A header without destination is accepted for compatibility with older servers.
+        let mut no_destination = xmatrix.clone();
+        no_destination.destination = None;
+        no_destination
+            .verify_request(&method, &uri, destination, Some(&content), &public_key_map)
+            .unwrap();
+
+        // The public key of the origin is required.
+        xmatrix
+            .verify_request(&method, &uri, destination, Some(&content), &PublicKeyMap::new())
+            .unwrap_err();
+    }
 }
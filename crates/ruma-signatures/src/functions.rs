@@ -10,13 +10,17 @@ use base64::{alphabet, Engine};
 use ruma_common::{
     canonical_json::{redact, JsonType},
     serde::{base64::Standard, Base64},
-    CanonicalJsonObject, CanonicalJsonValue, OwnedEventId, OwnedServerName, RoomVersionId, UserId,
+    CanonicalJsonObject, CanonicalJsonValue, CrossSigningOrDeviceSigningKeyId,
+    MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedServerName, RoomVersionId, SigningKeyAlgorithm,
+    UserId,
+};
+use serde_json::{
+    from_slice as from_json_slice, from_str as from_json_str, to_string as to_json_string,
 };
-use serde_json::{from_str as from_json_str, to_string as to_json_string};
 use sha2::{digest::Digest, Sha256};
 
 use crate::{
-    keys::{KeyPair, PublicKeyMap},
+    keys::{KeyPair, PublicKeyMap, PublicKeyMapWithValidity},
     split_id,
     verification::{Ed25519Verifier, Verified, Verifier},
     Error, JsonError, ParseError, VerificationError,
@@ -158,6 +162,46 @@ pub fn canonical_json(object: &CanonicalJsonObject) -> Result<String, Error> {
     canonical_json_with_fields_to_remove(object, CANONICAL_JSON_FIELDS_TO_REMOVE)
 }
 
+/// Parses a JSON object from bytes and converts it into the [canonical] string form.
+///
+/// This is equivalent to deserializing a [`CanonicalJsonObject`] and calling [`canonical_json`] on
+/// it, without copying the object. Like [`canonical_json`], the `signatures` and `unsigned` fields
+/// are removed.
+///
+/// [canonical]: https://spec.matrix.org/latest/appendices/#canonical-json
+///
+/// # Parameters
+///
+/// * bytes: The JSON object to convert, as bytes.
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a valid JSON object, or if it contains a floating-point
+/// number or an integer outside of the range of canonical JSON.
+///
+/// # Examples
+///
+/// ```rust
+/// let input = r#"{
+///     "本": 2,
+///     "日": 1
+/// }"#;
+///
+/// let canonical = ruma_signatures::canonical_json_from_slice(input.as_bytes()).unwrap();
+///
+/// assert_eq!(canonical, r#"{"日":1,"本":2}"#);
+/// ```
+pub fn canonical_json_from_slice(bytes: &[u8]) -> Result<String, Error> {
+    let mut object =
+        from_json_slice::<CanonicalJsonObject>(bytes).map_err(|e| Error::Json(e.into()))?;
+
+    for field in CANONICAL_JSON_FIELDS_TO_REMOVE {
+        object.remove(*field);
+    }
+
+    to_json_string(&object).map_err(|e| Error::Json(e.into()))
+}
+
 /// Uses a set of public keys to verify a signed JSON object.
 ///
 /// Unlike `content_hash` and `reference_hash`, this function does not report an error if the
@@ -281,11 +325,85 @@ where
     verifier.verify_json(public_key, signature, canonical_json(object)?.as_bytes())
 }
 
+/// Verifies the signature of a key object, like device keys or cross-signing keys.
+///
+/// The signature of the key with the given ID is looked up under the `signatures` of `user_id`,
+/// and verified against the canonical form of the object, without its `signatures` and `unsigned`
+/// fields.
+///
+/// To check that device keys are self-signed, the key ID is the `ed25519` key of the device,
+/// e.g. `ed25519:DEVICEID`, and the public key is the one in the `keys` of the object. For
+/// cross-signing keys, the key ID is made of the base64-encoded public key of the signing key,
+/// e.g. the master key for a self-signing key.
+///
+/// # Parameters
+///
+/// * object: The key object that was signed.
+/// * user_id: The ID of the user that signed the object.
+/// * key_id: The ID of the key used to sign the object.
+/// * public_key: The public key used to sign the object.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * `object` doesn't contain a signature for `user_id` and `key_id`.
+/// * The algorithm of `key_id` is not supported.
+/// * Verification fails.
+pub fn verify_key_object(
+    object: &CanonicalJsonObject,
+    user_id: &UserId,
+    key_id: &CrossSigningOrDeviceSigningKeyId,
+    public_key: &Base64,
+) -> Result<(), Error> {
+    let signature_map = match object.get("signatures") {
+        Some(CanonicalJsonValue::Object(signatures)) => signatures,
+        Some(_) => return Err(JsonError::not_of_type("signatures", JsonType::Object)),
+        None => return Err(JsonError::field_missing_from_object("signatures")),
+    };
+
+    let signature_set = match signature_map.get(user_id.as_str()) {
+        Some(CanonicalJsonValue::Object(set)) => set,
+        Some(_) => {
+            return Err(JsonError::not_multiples_of_type("signature sets", JsonType::Object))
+        }
+        None => {
+            return Err(JsonError::key_missing("signatures", "signature set", user_id.as_str()))
+        }
+    };
+
+    let signature = match signature_set.get(key_id.as_str()) {
+        Some(CanonicalJsonValue::String(signature)) => signature,
+        Some(_) => return Err(JsonError::not_of_type("signature", JsonType::String)),
+        None => {
+            return Err(JsonError::key_missing(
+                format!("signatures of {user_id}"),
+                "signature",
+                key_id.as_str(),
+            ))
+        }
+    };
+
+    let algorithm = key_id.algorithm();
+    if algorithm != SigningKeyAlgorithm::Ed25519 {
+        return Err(Error::UnsupportedAlgorithm(algorithm.to_string()));
+    }
+
+    let signature = Base64::<Standard>::parse(signature)
+        .map_err(|e| ParseError::base64("signature", signature, e))?;
+
+    verify_json_with(&Ed25519Verifier, public_key.as_bytes(), signature.as_bytes(), object)
+}
+
 /// Creates a *content hash* for an event.
 ///
 /// The content hash of an event covers the complete event including the unredacted contents. It is
 /// used during federation and is described in the Matrix server-server specification.
 ///
+/// The content hash is the same for all room versions. It is stored in the `hashes` field of the
+/// event, which is itself covered by the [reference hash](reference_hash). Use
+/// [`add_content_hash`] to add it to an event.
+///
 /// # Parameters
 ///
 /// object: A JSON object to generate a content hash for.
@@ -312,6 +430,9 @@ pub fn content_hash(object: &CanonicalJsonObject) -> Result<Base64<Standard, [u8
 /// hashes. It is used to generate event identifiers and is described in the Matrix server-server
 /// specification.
 ///
+/// Since the reference hash covers the `hashes` field, the [content hash](content_hash) should be
+/// added to the event before computing the reference hash, for example with [`add_content_hash`].
+///
 /// # Parameters
 ///
 /// object: A JSON object to generate a reference hash for.
@@ -346,6 +467,72 @@ pub fn reference_hash(
     Ok(base64_engine.encode(hash))
 }
 
+/// Gets the ID of an event.
+///
+/// In room versions 1 and 2, the event ID is taken from the `event_id` field of the event. In later
+/// room versions, the event ID is computed from the [reference hash](reference_hash) of the event,
+/// which uses the correct base64 alphabet for the room version.
+///
+/// # Parameters
+///
+/// * object: A JSON object of the event, as it is sent over federation.
+/// * version: The room version of the event.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The `event_id` field is missing or invalid, for room versions 1 and 2.
+/// * The event is too large or redaction fails, for other room versions.
+pub fn event_id_from_pdu(
+    object: &CanonicalJsonObject,
+    version: &RoomVersionId,
+) -> Result<OwnedEventId, Error> {
+    let event_id = match version {
+        RoomVersionId::V1 | RoomVersionId::V2 => match object.get("event_id") {
+            Some(CanonicalJsonValue::String(event_id)) => event_id.clone(),
+            Some(_) => return Err(JsonError::not_of_type("event_id", JsonType::String)),
+            None => return Err(JsonError::field_missing_from_object("event_id")),
+        },
+        _ => format!("${}", reference_hash(object, version)?),
+    };
+
+    event_id.try_into().map_err(|e| ParseError::EventId(e).into())
+}
+
+/// Computes the [content hash](content_hash) of an event and adds it to the object under the
+/// `hashes` key.
+///
+/// If `hashes` is already present, the new hash will be added to the existing data, replacing any
+/// existing SHA-256 hash. The `signatures` of the event are not modified.
+///
+/// # Parameters
+///
+/// * object: A JSON object to be hashed according to the Matrix specification.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The event is too large.
+/// * `object` contains a field called `hashes` that is not a JSON object.
+pub fn add_content_hash(object: &mut CanonicalJsonObject) -> Result<(), Error> {
+    let hash = content_hash(object)?;
+
+    let hashes_value = object
+        .entry("hashes".to_owned())
+        .or_insert_with(|| CanonicalJsonValue::Object(BTreeMap::new()));
+
+    match hashes_value {
+        CanonicalJsonValue::Object(hashes) => {
+            hashes.insert("sha256".into(), CanonicalJsonValue::String(hash.encode()))
+        }
+        _ => return Err(JsonError::not_of_type("hashes", JsonType::Object)),
+    };
+
+    Ok(())
+}
+
 /// Hashes and signs an event and adds the hash and signature to objects under the keys `hashes` and
 /// `signatures`, respectively.
 ///
@@ -450,18 +637,7 @@ pub fn hash_and_sign_event<K>(
 where
     K: KeyPair,
 {
-    let hash = content_hash(object)?;
-
-    let hashes_value = object
-        .entry("hashes".to_owned())
-        .or_insert_with(|| CanonicalJsonValue::Object(BTreeMap::new()));
-
-    match hashes_value {
-        CanonicalJsonValue::Object(hashes) => {
-            hashes.insert("sha256".into(), CanonicalJsonValue::String(hash.encode()))
-        }
-        _ => return Err(JsonError::not_of_type("hashes", JsonType::Object)),
-    };
+    add_content_hash(object)?;
 
     let mut redacted = redact(object.clone(), version, None)?;
 
@@ -537,7 +713,9 @@ where
 /// // Verify at least one signature for each entity in `public_key_map`.
 /// let verification_result = verify_event(&public_key_map, &object, &RoomVersionId::V6);
 /// assert!(verification_result.is_ok());
-/// assert_eq!(verification_result.unwrap(), Verified::All);
+/// let verified = verification_result.unwrap();
+/// assert_eq!(verified, Verified::All);
+/// assert!(!verified.requires_redaction());
 /// ```
 pub fn verify_event(
     public_key_map: &PublicKeyMap,
@@ -628,6 +806,49 @@ pub fn verify_event(
     Ok(Verified::Signatures)
 }
 
+/// Verifies that the signed event contains all the required valid signatures, made with keys that
+/// were valid at the given time.
+///
+/// This works like [`verify_event`], except that each public key has a validity period. In room
+/// versions 5 and later, a signature made with a key whose `valid_until_ts` is older than `at` is
+/// rejected as if the key was unknown, with a [`VerificationError::UnknownPublicKeysForSignature`].
+/// In earlier room versions, the validity period of the keys is ignored.
+///
+/// # Parameters
+///
+/// * public_key_map: A map from entity identifiers to a map from key identifiers to public keys
+///   with their validity period.
+/// * object: The JSON object of the event that was signed.
+/// * version: Room version of the given event.
+/// * at: The time at which the keys must be valid. This is usually the `origin_server_ts` of the
+///   event.
+pub fn verify_event_at(
+    public_key_map: &PublicKeyMapWithValidity,
+    object: &CanonicalJsonObject,
+    version: &RoomVersionId,
+    at: MilliSecondsSinceUnixEpoch,
+) -> Result<Verified, Error> {
+    let enforce_key_validity = !matches!(
+        version,
+        RoomVersionId::V1 | RoomVersionId::V2 | RoomVersionId::V3 | RoomVersionId::V4
+    );
+
+    let public_key_map = public_key_map
+        .iter()
+        .map(|(entity_id, public_keys)| {
+            let public_keys = public_keys
+                .iter()
+                .filter(|(_, public_key)| !enforce_key_validity || public_key.valid_until_ts >= at)
+                .map(|(key_id, public_key)| (key_id.clone(), public_key.key.clone()))
+                .collect();
+
+            (entity_id.clone(), public_keys)
+        })
+        .collect();
+
+    verify_event(&public_key_map, object, version)
+}
+
 /// Internal implementation detail of the canonical JSON algorithm.
 ///
 /// Allows customization of the fields that will be removed before serializing.
@@ -692,7 +913,11 @@ fn servers_to_check_signatures(
         | RoomVersionId::V6
         | RoomVersionId::V7 => {}
         // TODO: And for all future versions that have join_authorised_via_users_server
-        RoomVersionId::V8 | RoomVersionId::V9 | RoomVersionId::V10 | RoomVersionId::V11 => {
+        RoomVersionId::V8
+        | RoomVersionId::V9
+        | RoomVersionId::V10
+        | RoomVersionId::V11
+        | RoomVersionId::V12 => {
             if let Some(authorized_user) = object
                 .get("content")
                 .and_then(|c| c.as_object())
@@ -727,13 +952,17 @@ mod tests {
 
     use assert_matches2::assert_matches;
     use ruma_common::{
-        serde::Base64, CanonicalJsonValue, RoomVersionId, ServerSigningKeyId, SigningKeyAlgorithm,
+        serde::{base64::Standard, Base64},
+        user_id, CanonicalJsonObject, CanonicalJsonValue, CrossSigningOrDeviceSigningKeyId,
+        MilliSecondsSinceUnixEpoch, RoomVersionId, ServerSigningKeyId, SigningKeyAlgorithm,
     };
     use serde_json::json;
 
-    use super::canonical_json;
+    use super::{canonical_json, canonical_json_from_slice};
     use crate::{
-        sign_json, verify_event, Ed25519KeyPair, Error, PublicKeyMap, PublicKeySet,
+        add_content_hash, event_id_from_pdu, hash_and_sign_event, reference_hash, sign_json,
+        verify_event, verify_event_at, verify_key_object, Ed25519KeyPair, Error, JsonError,
+        PublicKeyMap, PublicKeyMapWithValidity, PublicKeySet, PublicKeyWithValidity,
         VerificationError, Verified,
     };
 
@@ -769,6 +998,113 @@ mod tests {
         assert_eq!(canonical_json(&object).unwrap(), canonical);
     }
 
+    #[test]
+    fn canonical_json_from_bytes() {
+        let json = r#"{
+            "unsigned": { "age": 5 },
+            "content": { "body": "日本", "count": 9007199254740991 },
+            "signatures": {},
+            "auth": [true, null, -1]
+        }"#;
+
+        assert_eq!(
+            canonical_json_from_slice(json.as_bytes()).unwrap(),
+            r#"{"auth":[true,null,-1],"content":{"body":"日本","count":9007199254740991}}"#
+        );
+
+        assert_matches!(
+            canonical_json_from_slice(br#"{ "number": 1.5 }"#),
+            Err(Error::Json(JsonError::Serde(_)))
+        );
+        assert_matches!(
+            canonical_json_from_slice(br#"{ "number": 9007199254740992 }"#),
+            Err(Error::Json(JsonError::Serde(_)))
+        );
+        assert_matches!(canonical_json_from_slice(b"[]"), Err(Error::Json(JsonError::Serde(_))));
+    }
+
+    #[test]
+    fn add_content_hash_does_not_sign() {
+        let mut object: CanonicalJsonObject = serde_json::from_value(json!({
+            "room_id": "!x:domain",
+            "sender": "@a:domain",
+            "origin": "domain",
+            "origin_server_ts": 1_000_000,
+            "type": "X",
+            "content": {},
+            "prev_events": [],
+            "auth_events": [],
+            "depth": 3,
+            "unsigned": {
+                "age_ts": 1_000_000
+            }
+        }))
+        .unwrap();
+        let mut signed_object = object.clone();
+
+        add_content_hash(&mut object).unwrap();
+        assert_eq!(
+            object.get("hashes"),
+            Some(&CanonicalJsonValue::Object(BTreeMap::from([(
+                "sha256".to_owned(),
+                CanonicalJsonValue::String(
+                    "5jM4wQpv6lnBo7CLIghJuHdW+s2CMBJPUOGOC89ncos".to_owned()
+                )
+            )])))
+        );
+        assert!(!object.contains_key("signatures"));
+
+        // The hash is the same as the one added during signing.
+        let key_pair = generate_key_pair("1");
+        hash_and_sign_event("domain", &key_pair, &mut signed_object, &RoomVersionId::V1).unwrap();
+        assert_eq!(object.get("hashes"), signed_object.get("hashes"));
+    }
+
+    #[test]
+    fn event_id_from_pdu_by_room_version() {
+        let mut object: CanonicalJsonObject = serde_json::from_value(json!({
+            "event_id": "$abcdef:domain",
+            "room_id": "!x:domain",
+            "sender": "@a:domain",
+            "origin": "domain",
+            "origin_server_ts": 1_000_000,
+            "type": "X",
+            "content": {},
+            "prev_events": [],
+            "auth_events": [],
+            "depth": 3,
+        }))
+        .unwrap();
+        add_content_hash(&mut object).unwrap();
+
+        // The event ID is part of the event.
+        assert_eq!(event_id_from_pdu(&object, &RoomVersionId::V1).unwrap(), "$abcdef:domain");
+
+        // The event ID is computed from the reference hash, without padding.
+        let event_id = event_id_from_pdu(&object, &RoomVersionId::V3).unwrap();
+        assert_eq!(
+            event_id.as_str(),
+            format!("${}", reference_hash(&object, &RoomVersionId::V3).unwrap())
+        );
+        assert_eq!(event_id.as_str().len(), 44);
+        assert_eq!(event_id.server_name(), None);
+
+        // The event ID uses the URL-safe alphabet.
+        let event_id = event_id_from_pdu(&object, &RoomVersionId::V11).unwrap();
+        assert_eq!(
+            event_id.as_str(),
+            format!("${}", reference_hash(&object, &RoomVersionId::V11).unwrap())
+        );
+        assert!(!event_id.as_str().contains(['+', '/']));
+
+        object.remove("event_id");
+        assert_matches!(
+            event_id_from_pdu(&object, &RoomVersionId::V2),
+            Err(Error::Json(JsonError::JsonFieldMissingFromObject(field)))
+        );
+        assert_eq!(field, "event_id");
+    }
+
     #[test]
     fn verify_event_does_not_check_signatures_for_third_party_invites() {
         let signed_event = serde_json::from_str(
@@ -801,6 +1137,7 @@ mod tests {
             verify_event(&public_key_map, &signed_event, &RoomVersionId::V6).unwrap();
 
         assert_eq!(verification, Verified::Signatures);
+        assert!(verification.requires_redaction());
     }
 
     #[test]
@@ -841,6 +1178,66 @@ mod tests {
         assert_eq!(verification, Verified::Signatures);
     }
 
+    #[test]
+    fn verify_event_at_checks_key_validity() {
+        let key_pair = generate_key_pair("1");
+        let mut signed_event = serde_json::from_str(
+            r#"{
+                "auth_events": [],
+                "content": {},
+                "depth": 3,
+                "hashes": {
+                    "sha256": "5jM4wQpv6lnBo7CLIghJuHdW+s2CMBJPUOGOC89ncos"
+                },
+                "origin": "domain",
+                "origin_server_ts": 1000000,
+                "prev_events": [],
+                "room_id": "!x:domain",
+                "sender": "@name:domain",
+                "type": "X",
+                "unsigned": {
+                    "age_ts": 1000000
+                }
+            }"#,
+        )
+        .unwrap();
+        sign_json("domain", &key_pair, &mut signed_event).unwrap();
+
+        let mut public_key_map = BTreeMap::new();
+        add_key_to_map(&mut public_key_map, "domain", &key_pair);
+        let public_key_map: PublicKeyMapWithValidity = public_key_map
+            .into_iter()
+            .map(|(entity_id, public_keys)| {
+                let public_keys = public_keys
+                    .into_iter()
+                    .map(|(key_id, key)| {
+                        let valid_until_ts = MilliSecondsSinceUnixEpoch(1_000_000_u32.into());
+                        (key_id, PublicKeyWithValidity::new(key, valid_until_ts))
+                    })
+                    .collect();
+                (entity_id, public_keys)
+            })
+            .collect();
+
+        // The key is valid at the time of the event.
+        let at = MilliSecondsSinceUnixEpoch(1_000_000_u32.into());
+        let verification =
+            verify_event_at(&public_key_map, &signed_event, &RoomVersionId::V6, at).unwrap();
+        assert_eq!(verification, Verified::Signatures);
+
+        // The key is expired.
+        let at = MilliSecondsSinceUnixEpoch(1_000_001_u32.into());
+        assert_matches!(
+            verify_event_at(&public_key_map, &signed_event, &RoomVersionId::V6, at),
+            Err(Error::Verification(VerificationError::UnknownPublicKeysForSignature))
+        );
+
+        // The validity of the key is ignored before room version 5.
+        let verification =
+            verify_event_at(&public_key_map, &signed_event, &RoomVersionId::V4, at).unwrap();
+        assert_eq!(verification, Verified::Signatures);
+    }
+
     #[test]
     fn verify_event_check_signatures_for_authorized_user() {
         let key_pair_sender = generate_key_pair("1");
@@ -1151,12 +1548,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_device_keys() {
+        let user_id = user_id!("@alice:example.org");
+        let key_pair = generate_key_pair("JLAFKJWSCS");
+        let public_key = Base64::new(key_pair.public_key().to_vec());
+        let mut object: CanonicalJsonObject = serde_json::from_value(json!({
+            "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+            "device_id": "JLAFKJWSCS",
+            "keys": {
+                "curve25519:JLAFKJWSCS": "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI",
+                "ed25519:JLAFKJWSCS": public_key.encode(),
+            },
+            "user_id": user_id,
+        }))
+        .unwrap();
+
+        sign_json(user_id.as_str(), &key_pair, &mut object).unwrap();
+        object.insert(
+            "unsigned".to_owned(),
+            CanonicalJsonValue::Object(
+                serde_json::from_value(json!({ "device_display_name": "Alice's mobile phone" }))
+                    .unwrap(),
+            ),
+        );
+
+        let key_id = CrossSigningOrDeviceSigningKeyId::parse("ed25519:JLAFKJWSCS").unwrap();
+        verify_key_object(&object, user_id, &key_id, &public_key).unwrap();
+
+        // Signature from another user.
+        assert_matches!(
+            verify_key_object(&object, user_id!("@bob:example.org"), &key_id, &public_key),
+            Err(Error::Json(JsonError::JsonKeyMissing { .. }))
+        );
+
+        // Signature from another key.
+        let other_key_id = CrossSigningOrDeviceSigningKeyId::parse("ed25519:OTHER").unwrap();
+        assert_matches!(
+            verify_key_object(&object, user_id, &other_key_id, &public_key),
+            Err(Error::Json(JsonError::JsonKeyMissing { .. }))
+        );
+
+        // Modified object.
+        object.insert("device_id".to_owned(), CanonicalJsonValue::String("OTHER".to_owned()));
+        assert_matches!(
+            verify_key_object(&object, user_id, &key_id, &public_key),
+            Err(Error::Verification(VerificationError::Signature(_)))
+        );
+    }
+
+    #[test]
+    fn verify_cross_signing_keys() {
+        let user_id = user_id!("@alice:example.org");
+        let device_key_pair = generate_key_pair("JLAFKJWSCS");
+        let device_public_key = Base64::new(device_key_pair.public_key().to_vec());
+
+        let master_key_pair = generate_cross_signing_key_pair();
+        let master_public_key = Base64::new(master_key_pair.public_key().to_vec());
+        let master_key_id = format!("ed25519:{}", master_key_pair.version());
+
+        let mut master_key: CanonicalJsonObject = serde_json::from_value(json!({
+            "keys": { &master_key_id: master_public_key.encode() },
+            "usage": ["master"],
+            "user_id": user_id,
+        }))
+        .unwrap();
+        sign_json(user_id.as_str(), &device_key_pair, &mut master_key).unwrap();
+
+        let device_key_id = CrossSigningOrDeviceSigningKeyId::parse("ed25519:JLAFKJWSCS").unwrap();
+        verify_key_object(&master_key, user_id, &device_key_id, &device_public_key).unwrap();
+
+        let self_signing_key_pair = generate_cross_signing_key_pair();
+        let mut self_signing_key: CanonicalJsonObject = serde_json::from_value(json!({
+            "keys": {
+                format!("ed25519:{}", self_signing_key_pair.version()):
+                    self_signing_key_pair.version(),
+            },
+            "usage": ["self_signing"],
+            "user_id": user_id,
+        }))
+        .unwrap();
+        sign_json(user_id.as_str(), &master_key_pair, &mut self_signing_key).unwrap();
+
+        let master_key_id = CrossSigningOrDeviceSigningKeyId::parse(master_key_id).unwrap();
+        verify_key_object(&self_signing_key, user_id, &master_key_id, &master_public_key).unwrap();
+
+        // Wrong public key.
+        assert_matches!(
+            verify_key_object(&self_signing_key, user_id, &master_key_id, &device_public_key),
+            Err(Error::Verification(VerificationError::Signature(_)))
+        );
+    }
+
     fn generate_key_pair(name: &str) -> Ed25519KeyPair {
         let key_content = Ed25519KeyPair::generate().unwrap();
         Ed25519KeyPair::from_der(&key_content, name.to_owned())
             .unwrap_or_else(|_| panic!("{:?}", &key_content))
     }
 
+    /// Generate a key pair whose version is its public key, like cross-signing keys.
+    fn generate_cross_signing_key_pair() -> Ed25519KeyPair {
+        let key_content = Ed25519KeyPair::generate().unwrap();
+        let public_key =
+            Ed25519KeyPair::from_der(&key_content, String::new()).unwrap().public_key();
+        Ed25519KeyPair::from_der(&key_content, Base64::<Standard, _>::new(public_key).encode())
+            .unwrap()
+    }
+
     fn add_key_to_map(public_key_map: &mut PublicKeyMap, name: &str, pair: &Ed25519KeyPair) {
         let sender_key_map = public_key_map.entry(name.to_owned()).or_default();
         let encoded_public_key = Base64::new(pair.public_key().to_vec());
@@ -9,7 +9,7 @@ use ed25519_dalek::{pkcs8::ALGORITHM_OID, SecretKey, Signer, SigningKey, PUBLIC_
 use pkcs8::{
     der::zeroize::Zeroizing, DecodePrivateKey, EncodePrivateKey, ObjectIdentifier, PrivateKeyInfo,
 };
-use ruma_common::serde::Base64;
+use ruma_common::{serde::Base64, MilliSecondsSinceUnixEpoch};
 
 use crate::{signatures::Signature, Algorithm, Error, ParseError};
 
@@ -183,6 +183,37 @@ pub type PublicKeyMap = BTreeMap<String, PublicKeySet>;
 /// This is represented as a map from key ID to base64-encoded signature.
 pub type PublicKeySet = BTreeMap<String, Base64>;
 
+/// A map from entity names to sets of public keys for that entity, with their validity period.
+///
+/// "Entity" is generally a homeserver, e.g. "example.com".
+pub type PublicKeyMapWithValidity = BTreeMap<String, PublicKeySetWithValidity>;
+
+/// A set of public keys for a single homeserver, with their validity period.
+///
+/// This is represented as a map from key ID to public key.
+pub type PublicKeySetWithValidity = BTreeMap<String, PublicKeyWithValidity>;
+
+/// A public key with the timestamp until which it is valid.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct PublicKeyWithValidity {
+    /// The base64-encoded public key.
+    pub key: Base64,
+
+    /// The timestamp until which the key is valid.
+    ///
+    /// For the keys of a homeserver, this is the `valid_until_ts` of its current keys, or the
+    /// `expired_ts` of its old keys.
+    pub valid_until_ts: MilliSecondsSinceUnixEpoch,
+}
+
+impl PublicKeyWithValidity {
+    /// Creates a new `PublicKeyWithValidity` with the given key and validity timestamp.
+    pub fn new(key: Base64, valid_until_ts: MilliSecondsSinceUnixEpoch) -> Self {
+        Self { key, valid_until_ts }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Ed25519KeyPair;
@@ -41,6 +41,9 @@
 //! To verify a signature on arbitrary JSON, use the `verify_json` function. To verify the
 //! signatures and hashes on an event, use the `verify_event` function. See the documentation for
 //! these respective functions for more details and full examples of use.
+//!
+//! To verify the signature of a key object in end-to-end encryption, like device keys or
+//! cross-signing keys, use the `verify_key_object` function.
 
 #![warn(missing_docs)]
 
@@ -49,10 +52,14 @@ use ruma_common::serde::{AsRefStr, DisplayAsRefStr};
 pub use self::{
     error::{Error, JsonError, ParseError, VerificationError},
     functions::{
-        canonical_json, content_hash, hash_and_sign_event, reference_hash, sign_json, verify_event,
-        verify_json,
+        add_content_hash, canonical_json, canonical_json_from_slice, content_hash,
+        event_id_from_pdu, hash_and_sign_event, reference_hash, sign_json, verify_event,
+        verify_event_at, verify_json, verify_key_object,
+    },
+    keys::{
+        Ed25519KeyPair, KeyPair, PublicKeyMap, PublicKeyMapWithValidity, PublicKeySet,
+        PublicKeySetWithValidity, PublicKeyWithValidity,
     },
-    keys::{Ed25519KeyPair, KeyPair, PublicKeyMap, PublicKeySet},
     signatures::Signature,
     verification::Verified,
 };
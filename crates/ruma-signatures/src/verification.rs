@@ -62,3 +62,13 @@ pub enum Verified {
     /// This may indicate a redacted event.
     Signatures,
 }
+
+impl Verified {
+    /// Whether the event must be redacted before being used.
+    ///
+    /// When the content hash doesn't match, the event must be redacted according to the rules of
+    /// its room version before it is used, e.g. to run authorization checks.
+    pub fn requires_redaction(&self) -> bool {
+        matches!(self, Self::Signatures)
+    }
+}
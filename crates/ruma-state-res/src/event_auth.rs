@@ -1,8 +1,9 @@
-use std::{borrow::Borrow, collections::BTreeSet};
+use std::{borrow::Borrow, collections::BTreeSet, iter};
 
 use js_int::{int, Int};
 use ruma_common::{
-    serde::{Base64, Raw},
+    canonical_json::{CanonicalJsonObject, CanonicalJsonValue},
+    serde::Raw,
     OwnedUserId, RoomVersionId, UserId,
 };
 use ruma_events::room::{
@@ -12,6 +13,7 @@ use ruma_events::room::{
     power_levels::RoomPowerLevelsEventContent,
     third_party_invite::RoomThirdPartyInviteEventContent,
 };
+use ruma_signatures::{PublicKeyMap, PublicKeySet};
 use serde::{
     de::{Error as _, IgnoredAny},
     Deserialize,
@@ -20,6 +22,7 @@ use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::{
+    events::RoomMemberEvent,
     power_levels::{
         deserialize_power_levels, deserialize_power_levels_content_fields,
         deserialize_power_levels_content_invite, deserialize_power_levels_content_redact,
@@ -37,12 +40,82 @@ struct GetMembership {
 #[derive(Deserialize)]
 struct RoomMemberContentFields {
     membership: Option<Raw<MembershipState>>,
-    join_authorised_via_users_server: Option<Raw<OwnedUserId>>,
+}
+
+/// An auth event that is required to authenticate an event.
+///
+/// Returned by [`auth_types_for_event()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub enum AuthEventKind {
+    /// The `m.room.create` event of the room.
+    Create,
+
+    /// The current `m.room.power_levels` event of the room.
+    PowerLevels,
+
+    /// The current `m.room.member` event of the sender of the event.
+    SenderMember(OwnedUserId),
+
+    /// The current `m.room.join_rules` event of the room.
+    JoinRules,
+
+    /// The current `m.room.member` event of the target of a membership event.
+    ///
+    /// This is the state key of the membership event.
+    TargetMember(String),
+
+    /// The current `m.room.member` event of the user that authorised a join to a restricted room.
+    AuthorisingUserMember(OwnedUserId),
+
+    /// The `m.room.third_party_invite` event with the given token.
+    ThirdPartyInvite(String),
+}
+
+impl AuthEventKind {
+    /// The type of the auth event.
+    pub fn event_type(&self) -> StateEventType {
+        match self {
+            Self::Create => StateEventType::RoomCreate,
+            Self::PowerLevels => StateEventType::RoomPowerLevels,
+            Self::SenderMember(_) | Self::TargetMember(_) | Self::AuthorisingUserMember(_) => {
+                StateEventType::RoomMember
+            }
+            Self::JoinRules => StateEventType::RoomJoinRules,
+            Self::ThirdPartyInvite(_) => StateEventType::RoomThirdPartyInvite,
+        }
+    }
+
+    /// The state key of the auth event.
+    pub fn state_key(&self) -> &str {
+        match self {
+            Self::Create | Self::PowerLevels | Self::JoinRules => "",
+            Self::SenderMember(user_id) | Self::AuthorisingUserMember(user_id) => user_id.as_str(),
+            Self::TargetMember(state_key) | Self::ThirdPartyInvite(state_key) => state_key,
+        }
+    }
+
+    /// Convert this `AuthEventKind` into a `(event_type, state_key)` tuple, as used as the key of
+    /// a [`StateMap`](crate::StateMap).
+    pub fn into_state_map_key(self) -> (StateEventType, String) {
+        let event_type = self.event_type();
+        let state_key = match self {
+            Self::Create | Self::PowerLevels | Self::JoinRules => String::new(),
+            Self::SenderMember(user_id) | Self::AuthorisingUserMember(user_id) => user_id.into(),
+            Self::TargetMember(state_key) | Self::ThirdPartyInvite(state_key) => state_key,
+        };
+
+        (event_type, state_key)
+    }
 }
 
 /// For the given event `kind` what are the relevant auth events that are needed to authenticate
 /// this `content`.
 ///
+/// Each auth event is only returned once, even if it is required for several reasons. For
+/// example, if the sender is also the target of a membership event, only
+/// [`AuthEventKind::SenderMember`] is returned.
+///
 /// # Errors
 ///
 /// This function will return an error if the supplied `content` is not a JSON object.
@@ -51,15 +124,15 @@ pub fn auth_types_for_event(
     sender: &UserId,
     state_key: Option<&str>,
     content: &RawJsonValue,
-) -> serde_json::Result<Vec<(StateEventType, String)>> {
+) -> serde_json::Result<Vec<AuthEventKind>> {
     if kind == &TimelineEventType::RoomCreate {
         return Ok(vec![]);
     }
 
     let mut auth_types = vec![
-        (StateEventType::RoomPowerLevels, "".to_owned()),
-        (StateEventType::RoomMember, sender.to_string()),
-        (StateEventType::RoomCreate, "".to_owned()),
+        AuthEventKind::PowerLevels,
+        AuthEventKind::SenderMember(sender.to_owned()),
+        AuthEventKind::Create,
     ];
 
     if kind == &TimelineEventType::RoomMember {
@@ -70,6 +143,17 @@ pub fn auth_types_for_event(
             join_authorised_via_users_server: Option<Raw<OwnedUserId>>,
         }
 
+        fn push_unique(auth_types: &mut Vec<AuthEventKind>, kind: AuthEventKind) {
+            let is_duplicate = auth_types.iter().any(|existing| {
+                existing.event_type() == kind.event_type()
+                    && existing.state_key() == kind.state_key()
+            });
+
+            if !is_duplicate {
+                auth_types.push(kind);
+            }
+        }
+
         if let Some(state_key) = state_key {
             let content: RoomMemberContentFields = from_json_str(content.get())?;
 
@@ -77,32 +161,23 @@ pub fn auth_types_for_event(
                 if [MembershipState::Join, MembershipState::Invite, MembershipState::Knock]
                     .contains(&membership)
                 {
-                    let key = (StateEventType::RoomJoinRules, "".to_owned());
-                    if !auth_types.contains(&key) {
-                        auth_types.push(key);
-                    }
+                    push_unique(&mut auth_types, AuthEventKind::JoinRules);
 
                     if let Some(Ok(u)) =
                         content.join_authorised_via_users_server.map(|m| m.deserialize())
                     {
-                        let key = (StateEventType::RoomMember, u.to_string());
-                        if !auth_types.contains(&key) {
-                            auth_types.push(key);
-                        }
+                        push_unique(&mut auth_types, AuthEventKind::AuthorisingUserMember(u));
                     }
                 }
 
-                let key = (StateEventType::RoomMember, state_key.to_owned());
-                if !auth_types.contains(&key) {
-                    auth_types.push(key);
-                }
+                push_unique(&mut auth_types, AuthEventKind::TargetMember(state_key.to_owned()));
 
                 if membership == MembershipState::Invite {
                     if let Some(Ok(t_id)) = content.third_party_invite.map(|t| t.deserialize()) {
-                        let key = (StateEventType::RoomThirdPartyInvite, t_id.signed.token);
-                        if !auth_types.contains(&key) {
-                            auth_types.push(key);
-                        }
+                        push_unique(
+                            &mut auth_types,
+                            AuthEventKind::ThirdPartyInvite(t_id.signed.token),
+                        );
                     }
                 }
             }
@@ -284,8 +359,10 @@ pub fn auth_check<E: Event>(
         let target_user =
             <&UserId>::try_from(state_key).map_err(|e| Error::InvalidPdu(format!("{e}")))?;
 
+        // An invalid `join_authorised_via_users_server` field is treated as missing, which means
+        // that the join is only allowed if it is authorized by other means.
         let user_for_join_auth =
-            content.join_authorised_via_users_server.as_ref().and_then(|u| u.deserialize().ok());
+            RoomMemberEvent::new(&incoming_event).join_authorised_via_users_server().ok().flatten();
 
         let user_for_join_auth_membership = user_for_join_auth
             .as_ref()
@@ -602,7 +679,7 @@ fn valid_membership_change(
         }
         MembershipState::Invite => {
             // If content has third_party_invite key
-            if let Some(tp_id) = third_party_invite.and_then(|i| i.deserialize().ok()) {
+            if let Some(tp_id) = third_party_invite {
                 if target_user_current_membership == MembershipState::Ban {
                     warn!(?target_user_membership_event_id, "can't invite banned user");
                     false
@@ -691,9 +768,9 @@ fn valid_membership_change(
         }
         MembershipState::Knock if room_version.allow_knocking => {
             // 1. If the `join_rule` is anything other than `knock` or `knock_restricted`, reject.
-            if join_rules != JoinRule::Knock
+            if !(join_rules == JoinRule::Knock
                 || room_version.knock_restricted_join_rule
-                    && matches!(join_rules, JoinRule::KnockRestricted(_))
+                    && matches!(join_rules, JoinRule::KnockRestricted(_)))
             {
                 warn!("join rule is not set to knock or knock_restricted, knocking is not allowed");
                 false
@@ -957,11 +1034,15 @@ fn get_send_level(
 fn verify_third_party_invite(
     target_user: Option<&UserId>,
     sender: &UserId,
-    tp_id: &ThirdPartyInvite,
+    raw_tp_id: &Raw<ThirdPartyInvite>,
     current_third_party_invite: Option<impl Event>,
 ) -> bool {
     // 1. Check for user being banned happens before this is called
     // checking for mxid and token keys is done by ruma when deserializing
+    let tp_id = match raw_tp_id.deserialize() {
+        Ok(tp_id) => tp_id,
+        Err(_) => return false,
+    };
 
     // The state key must match the invitee
     if target_user != Some(&tp_id.signed.mxid) {
@@ -991,28 +1072,60 @@ fn verify_third_party_invite(
             Err(_) => return false,
         };
 
-    let decoded_invite_token = match Base64::parse(&tp_id.signed.token) {
-        Ok(tok) => tok,
-        // FIXME: Log a warning?
-        Err(_) => return false,
+    // Use the raw JSON of signed, in case it contains fields that are unknown to ruma, since they
+    // are part of the signed object.
+    let signed = match raw_tp_id.get_field::<CanonicalJsonObject>("signed") {
+        Ok(Some(signed)) => signed,
+        _ => return false,
     };
 
-    // A list of public keys in the public_keys field
-    for key in tpid_ev.public_keys.unwrap_or_default() {
-        if key.public_key == decoded_invite_token {
-            return true;
+    // A single public key in the public_key field and a list of public keys in the public_keys
+    // field
+    let public_keys: Vec<_> = iter::once(tpid_ev.public_key)
+        .chain(tpid_ev.public_keys.unwrap_or_default().into_iter().map(|key| key.public_key))
+        .collect();
+
+    for (server_name, signatures) in tp_id.signed.signatures.iter() {
+        for (key_id, signature) in signatures {
+            // Only keep this signature, verify_json checks all the signatures in the object.
+            let mut signed = signed.clone();
+            signed.insert(
+                "signatures".to_owned(),
+                CanonicalJsonValue::Object(CanonicalJsonObject::from([(
+                    server_name.as_str().to_owned(),
+                    CanonicalJsonValue::Object(CanonicalJsonObject::from([(
+                        key_id.to_string(),
+                        CanonicalJsonValue::String(signature.clone()),
+                    )])),
+                )])),
+            );
+
+            for public_key in &public_keys {
+                let public_key_map = PublicKeyMap::from([(
+                    server_name.as_str().to_owned(),
+                    PublicKeySet::from([(key_id.to_string(), public_key.clone())]),
+                )]);
+
+                if ruma_signatures::verify_json(&public_key_map, &signed).is_ok() {
+                    return true;
+                }
+            }
         }
     }
 
-    // A single public key in the public_key field
-    tpid_ev.public_key == decoded_invite_token
+    false
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
+    use ruma_common::{
+        canonical_json::CanonicalJsonObject,
+        serde::{base64::Standard, Base64},
+    };
     use ruma_events::{
+        pdu::Pdu,
         room::{
             join_rules::{
                 AllowRule, JoinRule, Restricted, RoomJoinRulesEventContent, RoomMembership,
@@ -1021,10 +1134,14 @@ mod tests {
         },
         StateEventType, TimelineEventType,
     };
-    use serde_json::value::to_raw_value as to_raw_json_value;
+    use ruma_signatures::Ed25519KeyPair;
+    use serde_json::{
+        from_value as from_json_value, json, value::to_raw_value as to_raw_json_value,
+    };
 
     use crate::{
-        event_auth::valid_membership_change,
+        event_auth::{auth_check, auth_types_for_event, valid_membership_change, AuthEventKind},
+        events::RoomMemberEvent,
         test_utils::{
             alice, charlie, ella, event_id, member_content_ban, member_content_join, room_id,
             to_pdu_event, PduEvent, INITIAL_EVENTS, INITIAL_EVENTS_CREATE_ROOM,
@@ -1074,6 +1191,50 @@ mod tests {
         .unwrap());
     }
 
+    #[test]
+    fn test_redaction_room_v1() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+        let events = INITIAL_EVENTS();
+
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+
+        let redaction = |redacts: &str| {
+            let mut event = (*to_pdu_event(
+                "REDACTION",
+                charlie(),
+                TimelineEventType::RoomRedaction,
+                None,
+                to_raw_json_value(&json!({})).unwrap(),
+                &["CREATE", "IMC", "IPOWER"],
+                &["IMC"],
+            ))
+            .clone();
+            let Pdu::RoomV3Pdu(pdu) = &mut event.rest else { unreachable!() };
+            pdu.redacts = Some(event_id(redacts));
+            Arc::new(event)
+        };
+
+        // Charlie doesn't have the power to redact events, but the redacted event comes from
+        // the same server as the redaction.
+        assert!(auth_check(&RoomVersion::V1, redaction("IMA"), None::<PduEvent>, |ty, key| {
+            auth_events.get(&ty.with_state_key(key))
+        })
+        .unwrap());
+
+        // The redacted event comes from another server.
+        assert!(!auth_check(
+            &RoomVersion::V1,
+            redaction("$IMA:other.server"),
+            None::<PduEvent>,
+            |ty, key| auth_events.get(&ty.with_state_key(key))
+        )
+        .unwrap());
+    }
+
     #[test]
     fn test_join_non_creator() {
         let _ =
@@ -1200,6 +1361,102 @@ mod tests {
         .unwrap());
     }
 
+    fn third_party_invite_membership_change(
+        signing_key_pair: &Ed25519KeyPair,
+        invite_key_pair: &Ed25519KeyPair,
+    ) -> bool {
+        let events = INITIAL_EVENTS();
+
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+
+        let token = "abc123";
+        let third_party_invite = to_pdu_event(
+            "THIRDPARTY",
+            alice(),
+            TimelineEventType::RoomThirdPartyInvite,
+            Some(token),
+            to_raw_json_value(&json!({
+                "display_name": "e...@example.org",
+                "key_validity_url": "https://id.example.org/_matrix/identity/v2/pubkey/isvalid",
+                "public_key": Base64::<Standard>::new(invite_key_pair.public_key().to_vec()),
+            }))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IMC"],
+        );
+
+        // Unknown fields of signed are part of the signed object.
+        let mut signed: CanonicalJsonObject = from_json_value(json!({
+            "mxid": ella(),
+            "token": token,
+            "unknown": "field",
+        }))
+        .unwrap();
+        ruma_signatures::sign_json("id.example.org", signing_key_pair, &mut signed).unwrap();
+
+        let requester = to_pdu_event(
+            "HELLO",
+            alice(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&json!({
+                "membership": "invite",
+                "third_party_invite": {
+                    "display_name": "e...@example.org",
+                    "signed": signed,
+                },
+            }))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER", "THIRDPARTY"],
+            &["THIRDPARTY"],
+        );
+
+        let fetch_state = |ty, key| auth_events.get(&(ty, key)).cloned();
+        let target_user = ella();
+        let sender = alice();
+
+        valid_membership_change(
+            &RoomVersion::V6,
+            target_user,
+            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            sender,
+            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            &requester,
+            Some(third_party_invite),
+            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
+            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            None,
+            &MembershipState::Leave,
+            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_third_party_invite_pass() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".to_owned()).unwrap();
+
+        assert!(third_party_invite_membership_change(&key_pair, &key_pair));
+    }
+
+    #[test]
+    fn test_third_party_invite_fail_wrong_signature() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".to_owned()).unwrap();
+        let other_key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".to_owned()).unwrap();
+
+        assert!(!third_party_invite_membership_change(&other_key_pair, &key_pair));
+    }
+
     #[test]
     fn test_restricted_join_rule() {
         let _ =
@@ -1275,6 +1532,185 @@ mod tests {
         .unwrap());
     }
 
+    #[test]
+    fn test_restricted_join_without_authorising_user() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+        let mut events = INITIAL_EVENTS();
+        *events.get_mut(&event_id("IJR")).unwrap() = to_pdu_event(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            Some(""),
+            to_raw_json_value(&RoomJoinRulesEventContent::new(JoinRule::Restricted(
+                Restricted::new(vec![AllowRule::RoomMembership(RoomMembership::new(
+                    room_id().to_owned(),
+                ))]),
+            )))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IPOWER"],
+        );
+
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+        let fetch_state =
+            |ty: &StateEventType, key: &str| auth_events.get(&ty.with_state_key(key)).cloned();
+
+        // The join is not authorised by anyone.
+        let join = to_pdu_event(
+            "HELLO",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Join)).unwrap(),
+            &["CREATE", "IJR", "IPOWER"],
+            &["IJR"],
+        );
+        assert_eq!(RoomMemberEvent::new(&join).join_authorised_via_users_server().unwrap(), None);
+        assert!(!auth_check(&RoomVersion::V9, &join, None::<PduEvent>, fetch_state).unwrap());
+
+        // The join is authorised by a user that can invite.
+        let mut content = RoomMemberEventContent::new(MembershipState::Join);
+        content.join_authorized_via_users_server = Some(alice().to_owned());
+        let join = to_pdu_event(
+            "HELLO",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&content).unwrap(),
+            &["CREATE", "IJR", "IPOWER", "IMA"],
+            &["IJR"],
+        );
+        assert_eq!(
+            RoomMemberEvent::new(&join).join_authorised_via_users_server().unwrap().as_deref(),
+            Some(alice())
+        );
+        assert!(auth_check(&RoomVersion::V9, &join, None::<PduEvent>, fetch_state).unwrap());
+    }
+
+    #[test]
+    fn test_knock_restricted_join_rule() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+        let mut events = INITIAL_EVENTS();
+        *events.get_mut(&event_id("IJR")).unwrap() = to_pdu_event(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            Some(""),
+            to_raw_json_value(&RoomJoinRulesEventContent::new(JoinRule::KnockRestricted(
+                Restricted::new(vec![AllowRule::RoomMembership(RoomMembership::new(
+                    room_id().to_owned(),
+                ))]),
+            )))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IPOWER"],
+        );
+
+        let mut member = RoomMemberEventContent::new(MembershipState::Join);
+        member.join_authorized_via_users_server = Some(alice().to_owned());
+
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+
+        let join = to_pdu_event(
+            "HELLO",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&member).unwrap(),
+            &["CREATE", "IJR", "IPOWER", "new"],
+            &["new"],
+        );
+        let knock = to_pdu_event(
+            "HELLO",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Knock)).unwrap(),
+            &["CREATE", "IJR", "IPOWER", "new"],
+            &["new"],
+        );
+
+        let fetch_state = |ty, key| auth_events.get(&(ty, key)).cloned();
+        let target_user = ella();
+        let sender = ella();
+
+        // A join authorised by a user that can invite is allowed.
+        assert!(valid_membership_change(
+            &RoomVersion::V10,
+            target_user,
+            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            sender,
+            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            &join,
+            None::<PduEvent>,
+            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
+            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            Some(alice()),
+            &MembershipState::Join,
+            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+        )
+        .unwrap());
+
+        // Without an authorising user, the join is rejected.
+        assert!(!valid_membership_change(
+            &RoomVersion::V10,
+            target_user,
+            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            sender,
+            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            &join,
+            None::<PduEvent>,
+            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
+            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            None,
+            &MembershipState::Leave,
+            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+        )
+        .unwrap());
+
+        // The join rule is not supported before room version 10.
+        assert!(!valid_membership_change(
+            &RoomVersion::V9,
+            target_user,
+            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            sender,
+            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            &join,
+            None::<PduEvent>,
+            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
+            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            Some(alice()),
+            &MembershipState::Join,
+            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+        )
+        .unwrap());
+
+        // Knocking is allowed.
+        assert!(valid_membership_change(
+            &RoomVersion::V10,
+            target_user,
+            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            sender,
+            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            &knock,
+            None::<PduEvent>,
+            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
+            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            None,
+            &MembershipState::Leave,
+            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+        )
+        .unwrap());
+    }
+
     #[test]
     fn test_knock() {
         let _ =
@@ -1325,4 +1761,58 @@ mod tests {
         )
         .unwrap());
     }
+
+    #[test]
+    fn auth_types_for_restricted_join() {
+        let mut content = RoomMemberEventContent::new(MembershipState::Join);
+        content.join_authorized_via_users_server = Some(ella().to_owned());
+
+        let auth_types = auth_types_for_event(
+            &TimelineEventType::RoomMember,
+            alice(),
+            Some(alice().as_str()),
+            &to_raw_json_value(&content).unwrap(),
+        )
+        .unwrap();
+
+        // The sender is also the target, so their membership is only needed once.
+        assert_eq!(
+            auth_types,
+            [
+                AuthEventKind::PowerLevels,
+                AuthEventKind::SenderMember(alice().to_owned()),
+                AuthEventKind::Create,
+                AuthEventKind::JoinRules,
+                AuthEventKind::AuthorisingUserMember(ella().to_owned()),
+            ]
+        );
+        assert_eq!(
+            AuthEventKind::AuthorisingUserMember(ella().to_owned()).into_state_map_key(),
+            (StateEventType::RoomMember, ella().to_string())
+        );
+        assert_eq!(
+            AuthEventKind::JoinRules.into_state_map_key(),
+            (StateEventType::RoomJoinRules, "".to_owned())
+        );
+    }
+
+    #[test]
+    fn auth_types_for_message() {
+        let auth_types = auth_types_for_event(
+            &TimelineEventType::RoomMessage,
+            charlie(),
+            None,
+            &to_raw_json_value(&serde_json::json!({ "body": "hi" })).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            auth_types,
+            [
+                AuthEventKind::PowerLevels,
+                AuthEventKind::SenderMember(charlie().to_owned()),
+                AuthEventKind::Create,
+            ]
+        );
+    }
 }
@@ -0,0 +1,7 @@
+//! Helper types to access the content of the events used in the authorization rules.
+//!
+//! The content of the events is only deserialized when it is accessed.
+
+mod member;
+
+pub use self::member::RoomMemberEvent;
@@ -0,0 +1,61 @@
+use std::ops::Deref;
+
+use ruma_common::OwnedUserId;
+use ruma_events::room::member::MembershipState;
+use serde::Deserialize;
+use serde_json::from_str as from_json_str;
+
+use crate::{Error, Event, Result};
+
+/// A helper type for an [`Event`] of type `m.room.member`.
+///
+/// This is a type that deserializes each field lazily, when requested.
+#[derive(Debug, Clone)]
+pub struct RoomMemberEvent<E: Event>(E);
+
+impl<E: Event> RoomMemberEvent<E> {
+    /// Construct a new `RoomMemberEvent` around the given event.
+    pub fn new(event: E) -> Self {
+        Self(event)
+    }
+
+    /// The membership of the user.
+    pub fn membership(&self) -> Result<MembershipState> {
+        #[derive(Deserialize)]
+        struct RoomMemberContentMembership {
+            membership: MembershipState,
+        }
+
+        let content: RoomMemberContentMembership =
+            from_json_str(self.content().get()).map_err(|err| {
+                Error::InvalidPdu(format!("missing or invalid `membership` field: {err}"))
+            })?;
+        Ok(content.membership)
+    }
+
+    /// If this is a `join` event, the ID of a user on the homeserver that authorized it.
+    ///
+    /// Returns `Ok(None)` if the field is missing, and an error if it is not a valid user ID.
+    pub fn join_authorised_via_users_server(&self) -> Result<Option<OwnedUserId>> {
+        #[derive(Deserialize)]
+        struct RoomMemberContentJoinAuthorisedViaUsersServer {
+            join_authorised_via_users_server: Option<OwnedUserId>,
+        }
+
+        let content: RoomMemberContentJoinAuthorisedViaUsersServer =
+            from_json_str(self.content().get()).map_err(|err| {
+                Error::InvalidPdu(format!(
+                    "invalid `join_authorised_via_users_server` field: {err}"
+                ))
+            })?;
+        Ok(content.join_authorised_via_users_server)
+    }
+}
+
+impl<E: Event> Deref for RoomMemberEvent<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
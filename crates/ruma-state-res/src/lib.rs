@@ -1,7 +1,7 @@
 use std::{
     borrow::Borrow,
-    cmp::{Ordering, Reverse},
-    collections::{BinaryHeap, HashMap, HashSet},
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap, HashSet},
     hash::Hash,
 };
 
@@ -16,14 +16,17 @@ use tracing::{debug, info, instrument, trace, warn};
 
 mod error;
 pub mod event_auth;
+pub mod events;
 mod power_levels;
 pub mod room_version;
 mod state_event;
 #[cfg(test)]
 mod test_utils;
+#[cfg(any(test, feature = "unstable-testing"))]
+pub mod testing;
 
 pub use error::{Error, Result};
-pub use event_auth::{auth_check, auth_types_for_event};
+pub use event_auth::{auth_check, auth_types_for_event, AuthEventKind};
 use power_levels::PowerLevelsContentFields;
 pub use room_version::RoomVersion;
 pub use state_event::Event;
@@ -51,13 +54,51 @@ pub type StateMap<T> = HashMap<(StateEventType, String), T>;
 ///
 /// The caller of `resolve` must ensure that all the events are from the same room. Although this
 /// function takes a `RoomId` it does not check that each event is part of the same room.
-#[instrument(skip(state_sets, auth_chain_sets, fetch_event))]
 pub fn resolve<'a, E, SetIter>(
     room_version: &RoomVersionId,
     state_sets: impl IntoIterator<IntoIter = SetIter>,
     auth_chain_sets: Vec<HashSet<E::Id>>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
 ) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    resolve_with_trace(room_version, state_sets, auth_chain_sets, fetch_event)
+        .map(|trace| trace.resolved)
+}
+
+/// The result of [`resolve_with_trace`], with the orderings of the conflicted events used to
+/// resolve the state.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct ResolutionTrace<Id> {
+    /// The conflicted power events, as sorted by the reverse topological power ordering, before
+    /// they are authorized in that order.
+    pub power_order: Vec<Id>,
+
+    /// The other conflicted events, as sorted by the mainline ordering of the resolved power
+    /// levels event, before they are authorized in that order.
+    pub mainline_order: Vec<Id>,
+
+    /// The resolved state.
+    pub resolved: StateMap<Id>,
+}
+
+/// Resolve sets of state events like [`resolve`], and return the orderings of the conflicted events
+/// along with the resolved state.
+///
+/// This is useful to debug why two servers came to different results for the same state sets.
+///
+/// If the state sets don't conflict, the orderings are empty.
+#[instrument(skip(state_sets, auth_chain_sets, fetch_event))]
+pub fn resolve_with_trace<'a, E, SetIter>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> Result<ResolutionTrace<E::Id>>
 where
     E: Event + Clone,
     E::Id: 'a,
@@ -73,7 +114,11 @@ where
 
     if conflicting.is_empty() {
         info!("no conflicting state found");
-        return Ok(clean);
+        return Ok(ResolutionTrace {
+            power_order: Vec::new(),
+            mainline_order: Vec::new(),
+            resolved: clean,
+        });
     }
 
     info!(count = conflicting.len(), "conflicting events");
@@ -117,7 +162,7 @@ where
 
     // At this point the control_events have been resolved we now have to
     // sort the remaining events using the mainline of the resolved power level.
-    let deduped_power_ev = sorted_control_levels.into_iter().collect::<HashSet<_>>();
+    let deduped_power_ev = sorted_control_levels.iter().cloned().collect::<HashSet<_>>();
 
     // This removes the control events that passed auth and more importantly those that failed
     // auth
@@ -148,11 +193,42 @@ where
 
     // Add unconflicted state to the resolved state
     // We priorities the unconflicting state
-    resolved_state.extend(clean);
+    apply_delta(&mut resolved_state, clean);
 
     info!("state resolution finished");
 
-    Ok(resolved_state)
+    Ok(ResolutionTrace {
+        power_order: sorted_control_levels,
+        mainline_order: sorted_left_events,
+        resolved: resolved_state,
+    })
+}
+
+/// Get the keys of the state that is conflicting between the given state sets.
+///
+/// State is conflicting for a key if not all the state sets contain the same event ID for it,
+/// which includes the case where the key is missing from some of the state sets. This is cheaper
+/// than running [`resolve`], so it can be used to check whether resolution is necessary at all.
+pub fn conflicted_keys<'a, Id>(
+    state_sets: impl IntoIterator<Item = &'a StateMap<Id>>,
+) -> BTreeSet<(StateEventType, String)>
+where
+    Id: Eq + 'a,
+{
+    let state_sets: Vec<_> = state_sets.into_iter().collect();
+    let mut conflicted_keys = BTreeSet::new();
+
+    for (key, id) in state_sets.iter().copied().flatten() {
+        if conflicted_keys.contains(key) {
+            continue;
+        }
+
+        if state_sets.iter().any(|state_set| state_set.get(key) != Some(id)) {
+            conflicted_keys.insert(key.clone());
+        }
+    }
+
+    conflicted_keys
 }
 
 /// Split the events that have no conflicts from those that are conflicting.
@@ -210,6 +286,65 @@ where
     id_counts.into_iter().filter_map(move |(id, count)| (count < num_sets).then_some(id))
 }
 
+/// Returns a new state map with the entries of `delta` applied on top of `base`.
+///
+/// The entries of `delta` replace the entries of `base` with the same key. See [`apply_delta`]
+/// for the in-place version.
+pub fn overlay<Id: Clone>(base: &StateMap<Id>, delta: &StateMap<Id>) -> StateMap<Id> {
+    let mut state = base.clone();
+    apply_delta(&mut state, delta.iter().map(|(key, id)| (key.clone(), id.clone())));
+    state
+}
+
+/// Applies the entries of `delta` on top of `base`, in place.
+///
+/// The entries of `delta` replace the entries of `base` with the same key. This can be used to
+/// update the current state of a room incrementally, for example with the result of [`resolve`].
+pub fn apply_delta<Id>(
+    base: &mut StateMap<Id>,
+    delta: impl IntoIterator<Item = ((StateEventType, String), Id)>,
+) {
+    base.extend(delta);
+}
+
+/// Returns the deduplicated IDs that appear in some of the given auth chains but not in all of
+/// them, computed in parallel.
+///
+/// This is the auth difference that [`resolve`] computes from its `auth_chain_sets`. The result
+/// is the same, but the chains are processed on the [`rayon`] thread pool, which is faster for
+/// rooms with large forks.
+///
+/// Only available with the `rayon` cargo feature.
+#[cfg(feature = "rayon")]
+pub fn get_auth_chain_diff_parallel<Id>(auth_chain_sets: Vec<HashSet<Id>>) -> HashSet<Id>
+where
+    Id: Eq + Hash + Send,
+{
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    let num_sets = auth_chain_sets.len();
+
+    auth_chain_sets
+        .into_par_iter()
+        .fold(HashMap::<Id, usize>::new, |mut id_counts, auth_chain| {
+            for id in auth_chain {
+                *id_counts.entry(id).or_default() += 1;
+            }
+            id_counts
+        })
+        .reduce(HashMap::new, |a, b| {
+            // Merge the smaller map into the larger one.
+            let (mut larger, smaller) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+            for (id, count) in smaller {
+                *larger.entry(id).or_default() += count;
+            }
+            larger
+        })
+        .into_par_iter()
+        .filter_map(|(id, count)| (count < num_sets).then_some(id))
+        .collect()
+}
+
 /// Events are sorted from "earliest" to "latest".
 ///
 /// They are compared using the negative power level (reverse topological ordering), the origin
@@ -271,42 +406,35 @@ where
     F: Fn(&EventId) -> Result<(Int, MilliSecondsSinceUnixEpoch)>,
     Id: Clone + Eq + Ord + Hash + Borrow<EventId>,
 {
-    #[derive(PartialEq, Eq)]
-    struct TieBreaker<'a, Id> {
-        power_level: Int,
-        origin_server_ts: MilliSecondsSinceUnixEpoch,
-        event_id: &'a Id,
-    }
-
-    impl<Id> Ord for TieBreaker<'_, Id>
-    where
-        Id: Ord,
-    {
-        fn cmp(&self, other: &Self) -> Ordering {
-            // NOTE: the power level comparison is "backwards" intentionally.
-            // See the "Mainline ordering" section of the Matrix specification
-            // around where it says the following:
-            //
-            // > for events `x` and `y`, `x < y` if [...]
-            //
-            // <https://spec.matrix.org/latest/rooms/v11/#definitions>
-            other
-                .power_level
-                .cmp(&self.power_level)
-                .then(self.origin_server_ts.cmp(&other.origin_server_ts))
-                .then(self.event_id.cmp(other.event_id))
-        }
-    }
-
-    impl<Id> PartialOrd for TieBreaker<'_, Id>
-    where
-        Id: Ord,
-    {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
-    }
+    lexicographical_topological_sort_by_key(graph, |event_id| {
+        let (power_level, origin_server_ts) = key_fn(event_id.borrow())?;
+
+        // NOTE: the power level comparison is "backwards" intentionally.
+        // See the "Mainline ordering" section of the Matrix specification
+        // around where it says the following:
+        //
+        // > for events `x` and `y`, `x < y` if [...]
+        //
+        // <https://spec.matrix.org/latest/rooms/v11/#definitions>
+        Ok((Reverse(power_level), origin_server_ts))
+    })
+}
 
+/// Sorts the graph based on number of outgoing/incoming edges, using the given key to break ties.
+///
+/// This is the algorithm used by [`lexicographical_topological_sort`], with a generic key type:
+/// among the nodes whose outgoing edges have all been sorted, the one with the smallest key
+/// returned by `key_fn` comes first. Ties between equal keys are broken with the IDs of the nodes.
+#[instrument(skip_all)]
+pub fn lexicographical_topological_sort_by_key<Id, K, F>(
+    graph: &HashMap<Id, HashSet<Id>>,
+    key_fn: F,
+) -> Result<Vec<Id>>
+where
+    F: Fn(&Id) -> Result<K>,
+    K: Ord,
+    Id: Clone + Eq + Ord + Hash,
+{
     // NOTE: an event that has no incoming edges happened most recently,
     // and an event that has no outgoing edges happened least recently.
 
@@ -327,14 +455,9 @@ where
 
     for (node, edges) in graph {
         if edges.is_empty() {
-            let (power_level, origin_server_ts) = key_fn(node.borrow())?;
             // The `Reverse` is because rusts `BinaryHeap` sorts largest -> smallest we need
             // smallest -> largest
-            zero_outdegree.push(Reverse(TieBreaker {
-                power_level,
-                origin_server_ts,
-                event_id: node,
-            }));
+            zero_outdegree.push(Reverse((key_fn(node)?, node)));
         }
 
         reverse_graph.entry(node).or_default();
@@ -348,20 +471,17 @@ where
     // We remove the oldest node (most incoming edges) and check against all other
     let mut sorted = vec![];
     // Destructure the `Reverse` and take the smallest `node` each time
-    while let Some(Reverse(item)) = heap.pop() {
-        let node = item.event_id;
-
+    while let Some(Reverse((_, node))) = heap.pop() {
         for &parent in reverse_graph.get(node).expect("EventId in heap is also in reverse_graph") {
             // The number of outgoing edges this node has
             let out = outdegree_map
-                .get_mut(parent.borrow())
+                .get_mut(parent)
                 .expect("outdegree_map knows of all referenced EventIds");
 
             // Only push on the heap once older events have been cleared
-            out.remove(node.borrow());
+            out.remove(node);
             if out.is_empty() {
-                let (power_level, origin_server_ts) = key_fn(parent.borrow())?;
-                heap.push(Reverse(TieBreaker { power_level, origin_server_ts, event_id: parent }));
+                heap.push(Reverse((key_fn(parent)?, parent)));
             }
         }
 
@@ -416,6 +536,7 @@ fn get_power_level_for_sender<E: Event>(
 ///
 /// For each `events_to_check` event we gather the events needed to auth it from the the
 /// `fetch_event` closure and verify each event using the `event_auth::auth_check` function.
+/// The events that were [rejected](Event::rejected) are not used to auth other events.
 fn iterative_auth_check<E: Event + Clone>(
     room_version: &RoomVersion,
     events_to_check: &[E::Id],
@@ -438,8 +559,11 @@ fn iterative_auth_check<E: Event + Clone>(
         let mut auth_events = StateMap::new();
         for aid in event.auth_events() {
             if let Some(ev) = fetch_event(aid.borrow()) {
-                // TODO synapse check "rejected_reason" which is most likely
-                // related to soft-failing
+                if ev.rejected() {
+                    warn!(event_id = aid.borrow().as_str(), "ignoring rejected auth event");
+                    continue;
+                }
+
                 auth_events.insert(
                     ev.event_type().with_state_key(ev.state_key().ok_or_else(|| {
                         Error::InvalidPdu("State event had no state key".to_owned())
@@ -457,10 +581,14 @@ fn iterative_auth_check<E: Event + Clone>(
             Some(state_key),
             event.content(),
         )? {
+            let key = key.into_state_map_key();
             if let Some(ev_id) = resolved_state.get(&key) {
                 if let Some(event) = fetch_event(ev_id.borrow()) {
-                    // TODO synapse checks `rejected_reason` is None here
-                    auth_events.insert(key.to_owned(), event);
+                    if event.rejected() {
+                        warn!(event_id = ev_id.borrow().as_str(), "ignoring rejected state event");
+                    } else {
+                        auth_events.insert(key, event);
+                    }
                 }
             }
         }
@@ -692,6 +820,7 @@ mod tests {
             alice, bob, charlie, do_check, ella, event_id, member_content_ban, member_content_join,
             room_id, to_init_pdu_event, to_pdu_event, zara, PduEvent, TestStore, INITIAL_EVENTS,
         },
+        testing::Scenario,
         Event, EventTypeExt, StateMap,
     };
 
@@ -1098,6 +1227,76 @@ mod tests {
         assert_eq!(expected, resolved);
     }
 
+    #[test]
+    fn resolve_with_trace() {
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let auth_chain_sets = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+
+        let trace =
+            crate::resolve_with_trace(&RoomVersionId::V2, &state_sets, auth_chain_sets, |id| {
+                ev_map.get(id).cloned()
+            })
+            .unwrap();
+
+        assert_eq!(trace.resolved, expected);
+        assert_eq!(trace.power_order, Vec::<OwnedEventId>::new());
+        assert_eq!(trace.mainline_order, [event_id("IMB"), event_id("IMC")]);
+
+        // Without conflicts, there is no ordering.
+        let state_sets = [expected.clone(), expected.clone()];
+        let trace = crate::resolve_with_trace(&RoomVersionId::V2, &state_sets, Vec::new(), |id| {
+            ev_map.get(id).cloned()
+        })
+        .unwrap();
+
+        assert_eq!(trace.resolved, expected);
+        assert!(trace.power_order.is_empty());
+        assert!(trace.mainline_order.is_empty());
+    }
+
+    #[test]
+    fn resolve_with_trace_power_events() {
+        let scenario = Scenario::ban_vs_power_levels();
+        let store = TestStore(
+            scenario
+                .events
+                .iter()
+                .map(|(event_id, pdu)| (event_id.clone(), PduEvent::from_pdu(event_id, pdu)))
+                .collect(),
+        );
+        let auth_chain_sets = scenario
+            .state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+
+        let trace = crate::resolve_with_trace(
+            &scenario.room_version,
+            &scenario.state_sets,
+            auth_chain_sets,
+            |id| store.0.get(id).cloned(),
+        )
+        .unwrap();
+
+        // The conflicted power events include the difference of the auth chains. The ban, sent by
+        // a user with a higher power level, comes before the power levels change, which is then
+        // rejected.
+        assert_eq!(
+            trace.power_order,
+            [event_id("IPOWER"), event_id("IMB"), event_id("MB"), event_id("PB")]
+        );
+        // There are no other conflicted events.
+        assert!(trace.mainline_order.is_empty());
+        assert_eq!(trace.resolved, scenario.expected);
+    }
+
     #[test]
     fn test_lexicographical_sort() {
         let _ =
@@ -1125,6 +1324,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lexicographical_sort_by_key() {
+        let graph = hashmap! {
+            "l" => hashset!["o"],
+            "m" => hashset!["n", "o"],
+            "n" => hashset!["o"],
+            "o" => hashset![],
+            "p" => hashset!["o"],
+        };
+
+        // Without a key, ties are broken with the IDs.
+        let res = crate::lexicographical_topological_sort_by_key(&graph, |_id| Ok(())).unwrap();
+        assert_eq!(res, ["o", "l", "n", "m", "p"]);
+
+        // The key takes precedence over the IDs, but not over the edges.
+        let res =
+            crate::lexicographical_topological_sort_by_key(&graph, |id| Ok(std::cmp::Reverse(*id)))
+                .unwrap();
+        assert_eq!(res, ["o", "p", "n", "m", "l"]);
+    }
+
     #[test]
     fn ban_with_auth_chains() {
         let _ =
@@ -1227,6 +1447,41 @@ mod tests {
         do_check(&join_rule.values().cloned().collect::<Vec<_>>(), edges, expected_state_ids);
     }
 
+    #[test]
+    fn iterative_auth_check_ignores_rejected_events() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+
+        let check_bob_join = |events: &HashMap<OwnedEventId, Arc<PduEvent>>| {
+            let unconflicted_state = ["CREATE", "IMA", "IPOWER", "IJR"]
+                .into_iter()
+                .map(|id| {
+                    let ev = &events[&*event_id(id)];
+                    (ev.event_type().with_state_key(ev.state_key().unwrap()), ev.event_id.clone())
+                })
+                .collect::<StateMap<_>>();
+
+            let resolved_state = crate::iterative_auth_check(
+                &RoomVersion::V6,
+                &[event_id("IMB")],
+                unconflicted_state,
+                |id| events.get(id).cloned(),
+            )
+            .unwrap();
+
+            resolved_state.get(&(StateEventType::RoomMember, bob().to_string())).cloned()
+        };
+
+        let mut events = INITIAL_EVENTS();
+        assert_eq!(check_bob_join(&events), Some(event_id("IMB")));
+
+        // Without the public join rule, bob is not allowed to join.
+        let mut join_rule = (*events[&*event_id("IJR")]).clone();
+        join_rule.rejected = true;
+        events.insert(event_id("IJR"), Arc::new(join_rule));
+        assert_eq!(check_bob_join(&events), None);
+    }
+
     #[allow(non_snake_case)]
     fn BAN_STATE_SET() -> HashMap<OwnedEventId, Arc<PduEvent>> {
         vec![
@@ -1408,4 +1663,89 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn conflicted_keys_mixed() {
+        let conflicted_keys = super::conflicted_keys(&[
+            state_set![StateEventType::RoomMember => "@a:hs1" => 0],
+            state_set![
+                StateEventType::RoomMember => "@a:hs1" => 0,
+                StateEventType::RoomMember => "@b:hs1" => 1,
+            ],
+            state_set![
+                StateEventType::RoomMember => "@a:hs1" => 0,
+                StateEventType::RoomMember => "@b:hs1" => 2,
+                StateEventType::RoomMember => "@c:hs1" => 3,
+            ],
+        ]);
+
+        assert_eq!(
+            conflicted_keys.into_iter().collect::<Vec<_>>(),
+            vec![
+                (StateEventType::RoomMember, "@b:hs1".to_owned()),
+                (StateEventType::RoomMember, "@c:hs1".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn conflicted_keys_unconflicted() {
+        let conflicted_keys = super::conflicted_keys(&[
+            state_set![
+                StateEventType::RoomCreate => "" => 0,
+                StateEventType::RoomMember => "@a:hs1" => 1,
+            ],
+            state_set![
+                StateEventType::RoomCreate => "" => 0,
+                StateEventType::RoomMember => "@a:hs1" => 1,
+            ],
+        ]);
+
+        assert!(conflicted_keys.is_empty());
+    }
+
+    #[test]
+    fn overlay_and_apply_delta() {
+        let base = state_set![
+            StateEventType::RoomCreate => "" => 0,
+            StateEventType::RoomMember => "@a:hs1" => 1,
+        ];
+        let delta = state_set![
+            StateEventType::RoomMember => "@a:hs1" => 2,
+            StateEventType::RoomMember => "@b:hs1" => 3,
+        ];
+        let expected = state_set![
+            StateEventType::RoomCreate => "" => 0,
+            StateEventType::RoomMember => "@a:hs1" => 2,
+            StateEventType::RoomMember => "@b:hs1" => 3,
+        ];
+
+        assert_eq!(super::overlay(&base, &delta), expected);
+
+        let mut state = base;
+        super::apply_delta(&mut state, delta);
+        assert_eq!(state, expected);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn auth_chain_diff_parallel() {
+        use std::collections::HashSet;
+
+        let auth_chain_sets: Vec<HashSet<u32>> = vec![
+            (0..1000).collect(),
+            (0..1000).filter(|id| id % 3 != 0).collect(),
+            (500..1500).collect(),
+            HashSet::new(),
+        ];
+
+        let sequential: HashSet<_> = super::get_auth_chain_diff(auth_chain_sets.clone()).collect();
+        let parallel = super::get_auth_chain_diff_parallel(auth_chain_sets);
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel.len(), 1500);
+
+        let auth_chain_sets: Vec<HashSet<u32>> =
+            vec![(0..1000).collect(), (0..1000).collect(), (0..1000).collect()];
+        assert!(super::get_auth_chain_diff_parallel(auth_chain_sets).is_empty());
+    }
 }
@@ -2,7 +2,7 @@ use ruma_common::RoomVersionId;
 
 use crate::{Error, Result};
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[allow(clippy::exhaustive_enums)]
 pub enum RoomDisposition {
     /// A room version that has a stable specification.
@@ -11,7 +11,7 @@ pub enum RoomDisposition {
     Unstable,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
 pub enum EventFormatVersion {
     /// $id:server event id format
@@ -22,7 +22,7 @@ pub enum EventFormatVersion {
     V3,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
 pub enum StateResolutionVersion {
     /// State resolution for rooms at version 1.
@@ -31,6 +31,12 @@ pub enum StateResolutionVersion {
     V2,
 }
 
+/// The rules of a room version.
+///
+/// Each field describes a behavior that changed between room versions, so code that depends on
+/// the room version can check the corresponding field instead of matching on the
+/// [`RoomVersionId`].
+#[derive(Clone, Debug)]
 #[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
 pub struct RoomVersion {
     /// The stability of this room.
@@ -79,6 +85,12 @@ pub struct RoomVersion {
     ///
     /// See: [MSC2175](https://github.com/matrix-org/matrix-spec-proposals/pull/2175) for more information.
     pub use_room_create_sender: bool,
+    /// Use the updated redaction rules: the `redacts` key of `m.room.redaction` events is moved
+    /// to the content, and more fields are preserved by the redaction algorithm.
+    ///
+    /// See: [MSC2174](https://github.com/matrix-org/matrix-spec-proposals/pull/2174) and
+    /// [MSC2176](https://github.com/matrix-org/matrix-spec-proposals/pull/2176) for more information.
+    pub updated_redaction_rules: bool,
 }
 
 impl RoomVersion {
@@ -96,6 +108,7 @@ impl RoomVersion {
         knock_restricted_join_rule: false,
         integer_power_levels: false,
         use_room_create_sender: false,
+        updated_redaction_rules: false,
     };
 
     pub const V2: Self = Self { state_res: StateResolutionVersion::V2, ..Self::V1 };
@@ -123,8 +136,12 @@ impl RoomVersion {
     pub const V10: Self =
         Self { knock_restricted_join_rule: true, integer_power_levels: true, ..Self::V9 };
 
-    pub const V11: Self = Self { use_room_create_sender: true, ..Self::V10 };
+    pub const V11: Self =
+        Self { use_room_create_sender: true, updated_redaction_rules: true, ..Self::V10 };
 
+    /// Get the rules of the given room version.
+    ///
+    /// Returns an error if the room version is not supported.
     pub fn new(version: &RoomVersionId) -> Result<Self> {
         Ok(match version {
             RoomVersionId::V1 => Self::V1,
@@ -142,3 +159,40 @@ impl RoomVersion {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::RoomVersionId;
+
+    use super::{EventFormatVersion, RoomVersion, StateResolutionVersion};
+    use crate::Error;
+
+    #[test]
+    fn rules_for_versions() {
+        let v1 = RoomVersion::new(&RoomVersionId::V1).unwrap();
+        assert_eq!(v1.event_format, EventFormatVersion::V1);
+        assert_eq!(v1.state_res, StateResolutionVersion::V1);
+        assert!(v1.special_case_aliases_auth);
+        assert!(!v1.allow_knocking);
+
+        let v7 = RoomVersion::new(&RoomVersionId::V7).unwrap();
+        assert_eq!(v7.event_format, EventFormatVersion::V3);
+        assert!(v7.allow_knocking);
+        assert!(!v7.restricted_join_rules);
+
+        let v10 = RoomVersion::new(&RoomVersionId::V10).unwrap();
+        assert!(v10.knock_restricted_join_rule);
+        assert!(!v10.use_room_create_sender);
+        assert!(!v10.updated_redaction_rules);
+
+        let v11 = RoomVersion::new(&RoomVersionId::V11).unwrap();
+        assert!(v11.use_room_create_sender);
+        assert!(v11.updated_redaction_rules);
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let version = RoomVersionId::try_from("org.example.custom").unwrap();
+        assert!(matches!(RoomVersion::new(&version), Err(Error::Unsupported(_))));
+    }
+}
@@ -43,7 +43,29 @@ pub trait Event {
     fn auth_events(&self) -> Box<dyn DoubleEndedIterator<Item = &Self::Id> + '_>;
 
     /// If this event is a redaction event this is the event it redacts.
+    ///
+    /// In room versions that use the [updated redaction rules], the `redacts` key is in the
+    /// content of the event, so this should return its value.
+    ///
+    /// This is used by the authorization rules instead of deserializing the content of the event.
+    ///
+    /// [updated redaction rules]: crate::RoomVersion::updated_redaction_rules
     fn redacts(&self) -> Option<&Self::Id>;
+
+    /// Whether this event was rejected.
+    ///
+    /// An event is rejected when it fails the authorization checks based on its auth events, or
+    /// when it is invalid. A rejected event is excluded from the auth events used to authorize
+    /// other events during state resolution.
+    ///
+    /// This is different from a soft-failed event, which passes the authorization checks based on
+    /// its auth events but fails them based on the current state of the room. Soft-failed events
+    /// are not rejected and can still be used to authorize other events.
+    ///
+    /// Defaults to `false`.
+    fn rejected(&self) -> bool {
+        false
+    }
 }
 
 impl<T: Event> Event for &T {
@@ -88,6 +110,10 @@ impl<T: Event> Event for &T {
     fn redacts(&self) -> Option<&Self::Id> {
         (*self).redacts()
     }
+
+    fn rejected(&self) -> bool {
+        (*self).rejected()
+    }
 }
 
 impl<T: Event> Event for Arc<T> {
@@ -132,4 +158,8 @@ impl<T: Event> Event for Arc<T> {
     fn redacts(&self) -> Option<&Self::Id> {
         (**self).redacts()
     }
+
+    fn rejected(&self) -> bool {
+        (**self).rejected()
+    }
 }
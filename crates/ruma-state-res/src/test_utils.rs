@@ -1,6 +1,6 @@
 use std::{
     borrow::Borrow,
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{HashMap, HashSet},
     sync::{
         atomic::{AtomicU64, Ordering::SeqCst},
         Arc,
@@ -9,11 +9,10 @@ use std::{
 
 use js_int::{int, uint};
 use ruma_common::{
-    event_id, room_id, user_id, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId,
-    RoomVersionId, ServerSignatures, UserId,
+    event_id, user_id, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, RoomVersionId,
+    UserId,
 };
 use ruma_events::{
-    pdu::{EventHash, Pdu, RoomV3Pdu},
     room::{
         join_rules::{JoinRule, RoomJoinRulesEventContent},
         member::{MembershipState, RoomMemberEventContent},
@@ -27,7 +26,11 @@ use serde_json::{
 use tracing::info;
 
 pub(crate) use self::event::PduEvent;
-use crate::{auth_types_for_event, Error, Event, EventTypeExt, Result, StateMap};
+pub(crate) use crate::testing::{alice, bob, charlie, event_id, room_id};
+use crate::{
+    auth_types_for_event, testing::to_pdu, AuthEventKind, Error, Event, EventTypeExt, Result,
+    StateMap,
+};
 
 static SERVER_TIMESTAMP: AtomicU64 = AtomicU64::new(0);
 
@@ -141,7 +144,7 @@ pub(crate) fn do_check(
         .unwrap();
 
         let mut auth_events = vec![];
-        for key in auth_types {
+        for key in auth_types.into_iter().map(AuthEventKind::into_state_map_key) {
             if state_before.contains_key(&key) {
                 auth_events.push(state_before[&key].clone());
             }
@@ -329,26 +332,6 @@ impl TestStore<PduEvent> {
     }
 }
 
-pub(crate) fn event_id(id: &str) -> OwnedEventId {
-    if id.contains('$') {
-        return id.try_into().unwrap();
-    }
-
-    format!("${id}:foo").try_into().unwrap()
-}
-
-pub(crate) fn alice() -> &'static UserId {
-    user_id!("@alice:foo")
-}
-
-pub(crate) fn bob() -> &'static UserId {
-    user_id!("@bob:foo")
-}
-
-pub(crate) fn charlie() -> &'static UserId {
-    user_id!("@charlie:foo")
-}
-
 pub(crate) fn ella() -> &'static UserId {
     user_id!("@ella:foo")
 }
@@ -357,10 +340,6 @@ pub(crate) fn zara() -> &'static UserId {
     user_id!("@zara:foo")
 }
 
-pub(crate) fn room_id() -> &'static RoomId {
-    room_id!("!test:foo")
-}
-
 pub(crate) fn member_content_ban() -> Box<RawJsonValue> {
     to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Ban)).unwrap()
 }
@@ -376,28 +355,7 @@ pub(crate) fn to_init_pdu_event(
     state_key: Option<&str>,
     content: Box<RawJsonValue>,
 ) -> Arc<PduEvent> {
-    let ts = SERVER_TIMESTAMP.fetch_add(1, SeqCst);
-    let id = if id.contains('$') { id.to_owned() } else { format!("${id}:foo") };
-
-    let state_key = state_key.map(ToOwned::to_owned);
-    Arc::new(PduEvent {
-        event_id: id.try_into().unwrap(),
-        rest: Pdu::RoomV3Pdu(RoomV3Pdu {
-            room_id: room_id().to_owned(),
-            sender: sender.to_owned(),
-            origin_server_ts: MilliSecondsSinceUnixEpoch(ts.try_into().unwrap()),
-            state_key,
-            kind: ev_type,
-            content,
-            redacts: None,
-            unsigned: BTreeMap::new(),
-            auth_events: vec![],
-            prev_events: vec![],
-            depth: uint!(0),
-            hashes: EventHash::new("".to_owned()),
-            signatures: ServerSignatures::default(),
-        }),
-    })
+    to_pdu_event::<&str>(id, sender, ev_type, state_key, content, &[], &[])
 }
 
 pub(crate) fn to_pdu_event<S>(
@@ -413,28 +371,20 @@ where
     S: AsRef<str>,
 {
     let ts = SERVER_TIMESTAMP.fetch_add(1, SeqCst);
-    let id = if id.contains('$') { id.to_owned() } else { format!("${id}:foo") };
-    let auth_events = auth_events.iter().map(AsRef::as_ref).map(event_id).collect::<Vec<_>>();
-    let prev_events = prev_events.iter().map(AsRef::as_ref).map(event_id).collect::<Vec<_>>();
+    let origin_server_ts = MilliSecondsSinceUnixEpoch(ts.try_into().unwrap());
 
-    let state_key = state_key.map(ToOwned::to_owned);
     Arc::new(PduEvent {
-        event_id: id.try_into().unwrap(),
-        rest: Pdu::RoomV3Pdu(RoomV3Pdu {
-            room_id: room_id().to_owned(),
-            sender: sender.to_owned(),
-            origin_server_ts: MilliSecondsSinceUnixEpoch(ts.try_into().unwrap()),
+        event_id: event_id(id),
+        rest: to_pdu(
+            sender,
+            ev_type,
             state_key,
-            kind: ev_type,
             content,
-            redacts: None,
-            unsigned: BTreeMap::new(),
             auth_events,
             prev_events,
-            depth: uint!(0),
-            hashes: EventHash::new("".to_owned()),
-            signatures: ServerSignatures::default(),
-        }),
+            origin_server_ts,
+        ),
+        rejected: false,
     })
 }
 
@@ -546,7 +496,9 @@ pub(crate) fn INITIAL_EDGES() -> Vec<OwnedEventId> {
 }
 
 pub(crate) mod event {
-    use ruma_common::{MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, UserId};
+    use std::sync::Arc;
+
+    use ruma_common::{EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, UserId};
     use ruma_events::{pdu::Pdu, TimelineEventType};
     use serde::{Deserialize, Serialize};
     use serde_json::value::RawValue as RawJsonValue;
@@ -640,6 +592,10 @@ pub(crate) mod event {
                 _ => unreachable!("new PDU version"),
             }
         }
+
+        fn rejected(&self) -> bool {
+            self.rejected
+        }
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -648,5 +604,14 @@ pub(crate) mod event {
         pub(crate) event_id: OwnedEventId,
         #[serde(flatten)]
         pub(crate) rest: Pdu,
+        #[serde(skip)]
+        pub(crate) rejected: bool,
+    }
+
+    impl PduEvent {
+        /// Create an event with the given ID from the given PDU.
+        pub(crate) fn from_pdu(event_id: &EventId, pdu: &Pdu) -> Arc<Self> {
+            Arc::new(Self { event_id: event_id.to_owned(), rest: pdu.clone(), rejected: false })
+        }
     }
 }
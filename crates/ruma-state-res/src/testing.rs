@@ -0,0 +1,417 @@
+//! Helpers to check the integration of state resolution with a custom [`Event`] implementation.
+//!
+//! Server authors can run the [`Scenario`]s of this module against their own `Event` type, or
+//! use [`assert_resolves_to()`] with their own events, to make sure that their implementation of
+//! the trait gives the expected results.
+//!
+//! Only available with the `unstable-testing` cargo feature, which should not be enabled outside
+//! of tests.
+
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashSet},
+};
+
+use js_int::{uint, UInt};
+use ruma_common::{
+    room_id, user_id, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, RoomVersionId,
+    ServerSignatures, UserId,
+};
+use ruma_events::{
+    pdu::{EventHash, Pdu, RoomV3Pdu},
+    TimelineEventType,
+};
+use serde_json::{
+    json,
+    value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
+    Value as JsonValue,
+};
+
+use crate::{Event, EventTypeExt, StateMap};
+
+/// A state resolution scenario, with the events of a room, its forked state and the expected
+/// resolved state.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(ruma_unstable_exhaustive_types), non_exhaustive)]
+pub struct Scenario {
+    /// The version of the room.
+    pub room_version: RoomVersionId,
+
+    /// All the events of the room, by event ID.
+    pub events: BTreeMap<OwnedEventId, Pdu>,
+
+    /// The state of the room in each fork.
+    pub state_sets: Vec<StateMap<OwnedEventId>>,
+
+    /// The expected result of the resolution of `state_sets`.
+    pub expected: StateMap<OwnedEventId>,
+}
+
+impl Scenario {
+    /// A room where Alice bans Bob in one fork, while Bob changes the power levels in another
+    /// fork.
+    ///
+    /// The ban is applied and the power levels change is rejected, because Bob is not in the
+    /// room anymore.
+    pub fn ban_vs_power_levels() -> Self {
+        let mut builder = ScenarioBuilder::with_initial_state();
+
+        builder.add(
+            "MB",
+            alice(),
+            TimelineEventType::RoomMember,
+            bob().as_str(),
+            json!({ "membership": "ban" }),
+            &["CREATE", "IMA", "IPOWER", "IMB"],
+            &["T1"],
+        );
+        builder.add(
+            "PB",
+            bob(),
+            TimelineEventType::RoomPowerLevels,
+            "",
+            json!({ "users": { alice(): 100, bob(): 50, charlie(): 50 } }),
+            &["CREATE", "IPOWER", "IMB"],
+            &["T1"],
+        );
+
+        builder.build(&[&["MB"], &["PB"]], &["CREATE", "IMA", "IPOWER", "IJR", "MB", "IMC", "T1"])
+    }
+
+    /// A room where Alice demotes Bob in one fork, while Bob changes the topic in another fork.
+    ///
+    /// The power levels change is applied, and the topic is reset to the one before the fork,
+    /// because Bob doesn't have the power to change it anymore.
+    pub fn topic_conflict() -> Self {
+        let mut builder = ScenarioBuilder::with_initial_state();
+
+        builder.add(
+            "PA",
+            alice(),
+            TimelineEventType::RoomPowerLevels,
+            "",
+            json!({ "users": { alice(): 100 } }),
+            &["CREATE", "IMA", "IPOWER"],
+            &["T1"],
+        );
+        builder.add(
+            "T2",
+            bob(),
+            TimelineEventType::RoomTopic,
+            "",
+            json!({ "topic": "Bob's topic" }),
+            &["CREATE", "IPOWER", "IMB"],
+            &["T1"],
+        );
+
+        builder.build(&[&["PA"], &["T2"]], &["CREATE", "IMA", "PA", "IJR", "IMB", "IMC", "T1"])
+    }
+
+    /// Run this scenario with the given conversion from the events of the scenario to the
+    /// [`Event`] implementation to test.
+    ///
+    /// # Panics
+    ///
+    /// Panics if state resolution fails or if the resolved state is not the expected state.
+    pub fn assert_resolves<E, F>(&self, to_event: F)
+    where
+        E: Event + Clone,
+        E::Id: for<'a> From<&'a EventId>,
+        F: Fn(&EventId, &Pdu) -> E,
+    {
+        let events = self
+            .events
+            .iter()
+            .map(|(event_id, pdu)| (event_id.clone(), to_event(event_id, pdu)))
+            .collect::<BTreeMap<_, _>>();
+        let convert_state = |state: &StateMap<OwnedEventId>| {
+            state
+                .iter()
+                .map(|(key, event_id)| (key.clone(), E::Id::from(event_id)))
+                .collect::<StateMap<_>>()
+        };
+
+        assert_resolves_to(
+            &self.room_version,
+            &self.state_sets.iter().map(convert_state).collect::<Vec<_>>(),
+            &convert_state(&self.expected),
+            |event_id| events.get(event_id).cloned(),
+        );
+    }
+}
+
+/// Assert that the given state sets resolve to the expected state.
+///
+/// The auth chains of the state sets are computed from the events returned by `fetch_event`.
+///
+/// # Panics
+///
+/// Panics if an event can't be fetched, if state resolution fails or if the resolved state is not
+/// the expected state.
+pub fn assert_resolves_to<E>(
+    room_version: &RoomVersionId,
+    state_sets: &[StateMap<E::Id>],
+    expected: &StateMap<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) where
+    E: Event + Clone,
+{
+    let auth_chain_sets =
+        state_sets.iter().map(|state| auth_chain(state.values(), &fetch_event)).collect();
+
+    let resolved = crate::resolve(room_version, state_sets, auth_chain_sets, &fetch_event)
+        .unwrap_or_else(|error| panic!("state resolution failed: {error}"));
+
+    assert_eq!(&resolved, expected, "the resolved state is not the expected state");
+}
+
+/// Get the full auth chain of the given events, including the events themselves.
+fn auth_chain<'a, E: Event>(
+    event_ids: impl IntoIterator<Item = &'a E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> HashSet<E::Id>
+where
+    E::Id: 'a,
+{
+    let mut auth_chain = HashSet::new();
+    let mut stack = event_ids.into_iter().cloned().collect::<Vec<_>>();
+
+    while let Some(event_id) = stack.pop() {
+        if auth_chain.contains(&event_id) {
+            continue;
+        }
+
+        let event = fetch_event(event_id.borrow())
+            .unwrap_or_else(|| panic!("failed to fetch event {event_id}"));
+        stack.extend(event.auth_events().cloned());
+
+        auth_chain.insert(event_id);
+    }
+
+    auth_chain
+}
+
+/// Helper to build the events of a [`Scenario`].
+struct ScenarioBuilder {
+    events: BTreeMap<OwnedEventId, Pdu>,
+    next_ts: UInt,
+}
+
+impl ScenarioBuilder {
+    /// Create a builder with a room created by Alice, where Bob has a power level of 50, and that
+    /// Bob and Charlie joined.
+    fn with_initial_state() -> Self {
+        let mut builder = Self { events: BTreeMap::new(), next_ts: uint!(0) };
+
+        builder.add(
+            "CREATE",
+            alice(),
+            TimelineEventType::RoomCreate,
+            "",
+            json!({ "creator": alice() }),
+            &[],
+            &[],
+        );
+        builder.add(
+            "IMA",
+            alice(),
+            TimelineEventType::RoomMember,
+            alice().as_str(),
+            json!({ "membership": "join" }),
+            &["CREATE"],
+            &["CREATE"],
+        );
+        builder.add(
+            "IPOWER",
+            alice(),
+            TimelineEventType::RoomPowerLevels,
+            "",
+            json!({ "users": { alice(): 100, bob(): 50 } }),
+            &["CREATE", "IMA"],
+            &["IMA"],
+        );
+        builder.add(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            "",
+            json!({ "join_rule": "public" }),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IPOWER"],
+        );
+        builder.add(
+            "IMB",
+            bob(),
+            TimelineEventType::RoomMember,
+            bob().as_str(),
+            json!({ "membership": "join" }),
+            &["CREATE", "IJR", "IPOWER"],
+            &["IJR"],
+        );
+        builder.add(
+            "IMC",
+            charlie(),
+            TimelineEventType::RoomMember,
+            charlie().as_str(),
+            json!({ "membership": "join" }),
+            &["CREATE", "IJR", "IPOWER"],
+            &["IMB"],
+        );
+        builder.add(
+            "T1",
+            alice(),
+            TimelineEventType::RoomTopic,
+            "",
+            json!({ "topic": "Alice's topic" }),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IMC"],
+        );
+
+        builder
+    }
+
+    /// Add a state event with the given short ID.
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &mut self,
+        id: &str,
+        sender: &UserId,
+        kind: TimelineEventType,
+        state_key: &str,
+        content: JsonValue,
+        auth_events: &[&str],
+        prev_events: &[&str],
+    ) {
+        let origin_server_ts = MilliSecondsSinceUnixEpoch(self.next_ts);
+        self.next_ts += uint!(1);
+
+        let pdu = to_pdu(
+            sender,
+            kind,
+            Some(state_key),
+            to_raw_json_value(&content).expect("JSON value serialization to succeed"),
+            auth_events,
+            prev_events,
+            origin_server_ts,
+        );
+
+        self.events.insert(event_id(id), pdu);
+    }
+
+    /// Build a scenario, where each state set is the initial state with the given events, and the
+    /// expected state contains the given events.
+    fn build(self, forks: &[&[&str]], expected: &[&str]) -> Scenario {
+        let initial_state = ["CREATE", "IMA", "IPOWER", "IJR", "IMB", "IMC", "T1"];
+        let state_sets = forks
+            .iter()
+            .map(|fork| self.state(initial_state.into_iter().chain(fork.iter().copied())))
+            .collect();
+        let expected = self.state(expected.iter().copied());
+
+        Scenario { room_version: RoomVersionId::V6, events: self.events, state_sets, expected }
+    }
+
+    /// Get the state made of the events with the given short IDs.
+    ///
+    /// If several events have the same state key, the last one is used.
+    fn state<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> StateMap<OwnedEventId> {
+        ids.into_iter()
+            .map(|id| {
+                let event_id = event_id(id);
+                let Pdu::RoomV3Pdu(pdu) = &self.events[&event_id] else {
+                    unreachable!("the builder only creates room v3 PDUs");
+                };
+                let state_key = pdu.state_key.clone().expect("all events are state events");
+
+                (pdu.kind.clone().with_state_key(state_key), event_id)
+            })
+            .collect()
+    }
+}
+
+// The helpers below are also used by the tests of this crate.
+
+/// Build a room v3 PDU in the room of the scenarios.
+///
+/// The auth and prev events are short IDs or full event IDs.
+pub(crate) fn to_pdu<S: AsRef<str>>(
+    sender: &UserId,
+    kind: TimelineEventType,
+    state_key: Option<&str>,
+    content: Box<RawJsonValue>,
+    auth_events: &[S],
+    prev_events: &[S],
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+) -> Pdu {
+    Pdu::RoomV3Pdu(RoomV3Pdu {
+        room_id: room_id().to_owned(),
+        sender: sender.to_owned(),
+        origin_server_ts,
+        kind,
+        content,
+        state_key: state_key.map(ToOwned::to_owned),
+        prev_events: prev_events.iter().map(|id| event_id(id.as_ref())).collect(),
+        depth: uint!(0),
+        auth_events: auth_events.iter().map(|id| event_id(id.as_ref())).collect(),
+        redacts: None,
+        unsigned: BTreeMap::new(),
+        hashes: EventHash::new(String::new()),
+        signatures: ServerSignatures::default(),
+    })
+}
+
+/// Get the event ID with the given short ID, or parse the given full event ID.
+pub(crate) fn event_id(id: &str) -> OwnedEventId {
+    if id.starts_with('$') {
+        id.try_into().expect("event ID should be valid")
+    } else {
+        format!("${id}:foo").try_into().expect("event ID should be valid")
+    }
+}
+
+pub(crate) fn room_id() -> &'static RoomId {
+    room_id!("!test:foo")
+}
+
+pub(crate) fn alice() -> &'static UserId {
+    user_id!("@alice:foo")
+}
+
+pub(crate) fn bob() -> &'static UserId {
+    user_id!("@bob:foo")
+}
+
+pub(crate) fn charlie() -> &'static UserId {
+    user_id!("@charlie:foo")
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::RoomVersionId;
+
+    use super::{assert_resolves_to, Scenario};
+    use crate::test_utils::PduEvent;
+
+    #[test]
+    fn ban_vs_power_levels() {
+        Scenario::ban_vs_power_levels().assert_resolves(PduEvent::from_pdu);
+    }
+
+    #[test]
+    fn topic_conflict() {
+        Scenario::topic_conflict().assert_resolves(PduEvent::from_pdu);
+    }
+
+    #[test]
+    #[should_panic = "the resolved state is not the expected state"]
+    fn unexpected_state() {
+        let scenario = Scenario::topic_conflict();
+
+        assert_resolves_to(
+            &RoomVersionId::V6,
+            &scenario.state_sets,
+            &scenario.state_sets[1],
+            |event_id| scenario.events.get(event_id).map(|pdu| PduEvent::from_pdu(event_id, pdu)),
+        );
+    }
+}